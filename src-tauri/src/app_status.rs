@@ -0,0 +1,63 @@
+use crate::autohide::{accessibility, AutohideConfigResponse};
+use crate::speech::{SpeechConfig, SpeechRecognitionState};
+use crate::AppState;
+use serde::Serialize;
+use tauri::State;
+
+/// Current state of every subsystem, for initializing the frontend in one call
+#[derive(Debug, Clone, Serialize)]
+pub struct AppStatus {
+    pub autohide: AutohideStatus,
+    pub speech: SpeechStatus,
+    /// PIDs of the running PTY shells registered in `PtySessions`
+    /// (the PTY processes themselves are owned by the frontend)
+    pub terminal_sessions: Vec<u32>,
+    /// Whether the Accessibility permission needed by the autohide event tap is granted
+    pub accessibility_trusted: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AutohideStatus {
+    pub config: AutohideConfigResponse,
+    /// Whether the sidebar is currently shown (not docked at the edge)
+    pub visible: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SpeechStatus {
+    pub state: SpeechRecognitionState,
+    pub config: SpeechConfig,
+    /// Whether recognition was disabled after repeated errors (until `reset_speech_errors`)
+    pub disabled_by_errors: bool,
+}
+
+/// Get the state of autohide, speech recognition, terminal sessions and permissions at once
+///
+/// Each field comes from the same getter as its individual command
+/// (`get_autohide_config`, `is_sidebar_visible`, `get_speech_state`, ...).
+#[tauri::command(async)]
+pub fn get_app_status(state: State<'_, AppState>) -> Result<AppStatus, String> {
+    let autohide = {
+        let manager = state.autohide_manager.lock().map_err(|e| e.to_string())?;
+        AutohideStatus {
+            config: manager.get_config_response()?,
+            visible: manager.is_visible(),
+        }
+    };
+
+    let speech = {
+        let manager = state.speech_manager.lock().map_err(|e| e.to_string())?;
+        SpeechStatus {
+            state: manager.get_state()?,
+            config: manager.get_config()?,
+            disabled_by_errors: manager.is_disabled_by_errors(),
+        }
+    };
+
+    Ok(AppStatus {
+        autohide,
+        speech,
+        terminal_sessions: state.pty_sessions.live_pids(),
+        accessibility_trusted: accessibility::is_trusted(),
+    })
+}
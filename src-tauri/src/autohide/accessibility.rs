@@ -0,0 +1,10 @@
+#[link(name = "ApplicationServices", kind = "framework")]
+extern "C" {
+    fn AXIsProcessTrusted() -> u8;
+}
+
+/// Whether this process has been granted the Accessibility permission
+/// (required for the global event tap)
+pub fn is_trusted() -> bool {
+    unsafe { AXIsProcessTrusted() != 0 }
+}
@@ -0,0 +1,118 @@
+use crate::autohide::config::EasingKind;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{PhysicalPosition, Window};
+
+/// Frame interval of slide animations (~60fps)
+const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Slides the window between two positions on a background thread
+///
+/// Only one animation runs at a time: starting a new one (or calling `cancel`)
+/// stops the previous one where it is, and its completion callback is not called.
+pub struct SlideAnimator {
+    /// Generation of the current animation and whether it is still running
+    current: Arc<Mutex<(u64, bool)>>,
+}
+
+impl SlideAnimator {
+    pub fn new() -> Self {
+        Self {
+            current: Arc::new(Mutex::new((0, false))),
+        }
+    }
+
+    /// Stop the running animation, if any
+    /// Returns true if an animation was interrupted
+    pub fn cancel(&self) -> bool {
+        let Ok(mut current) = self.current.lock() else {
+            return false;
+        };
+        let was_running = current.1;
+        *current = (current.0 + 1, false);
+        was_running
+    }
+
+    /// Slide the window from `from` to `to` along the `easing` curve, then call `on_complete`
+    ///
+    /// The window is moved by `set_position` from a worker thread, so this must not be
+    /// waited on from the main thread.
+    pub fn start<F>(
+        &self,
+        window: &Window,
+        from: PhysicalPosition<i32>,
+        to: PhysicalPosition<i32>,
+        duration: Duration,
+        easing: EasingKind,
+        on_complete: F,
+    ) -> Result<(), String>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let generation = {
+            let mut current = self.current.lock().map_err(|e| e.to_string())?;
+            *current = (current.0 + 1, true);
+            current.0
+        };
+
+        let current = self.current.clone();
+        let window = window.clone();
+        thread::spawn(move || {
+            let started_at = Instant::now();
+            loop {
+                let progress = if duration.is_zero() {
+                    1.0
+                } else {
+                    (started_at.elapsed().as_secs_f64() / duration.as_secs_f64()).min(1.0)
+                };
+
+                {
+                    // Hold the lock while moving so a cancelled animation never moves the window again
+                    let Ok(current) = current.lock() else {
+                        return;
+                    };
+                    if current.0 != generation {
+                        return;
+                    }
+                    let position = interpolate(from, to, easing.apply(progress));
+                    if let Err(e) = window.set_position(tauri::Position::Physical(position)) {
+                        eprintln!("[autohide] Failed to move window: {}", e);
+                    }
+                }
+
+                if progress >= 1.0 {
+                    break;
+                }
+                thread::sleep(FRAME_INTERVAL);
+            }
+
+            let Ok(mut current) = current.lock() else {
+                return;
+            };
+            if current.0 == generation {
+                current.1 = false;
+                on_complete();
+            }
+        });
+
+        Ok(())
+    }
+}
+
+impl Default for SlideAnimator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn interpolate(
+    from: PhysicalPosition<i32>,
+    to: PhysicalPosition<i32>,
+    t: f64,
+) -> PhysicalPosition<i32> {
+    PhysicalPosition::new(
+        from.x + ((to.x - from.x) as f64 * t).round() as i32,
+        from.y + ((to.y - from.y) as f64 * t).round() as i32,
+    )
+}
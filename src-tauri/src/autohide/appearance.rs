@@ -0,0 +1,114 @@
+use crate::autohide::config::ScreenEdge;
+use objc2::msg_send;
+use objc2::runtime::AnyObject;
+use std::sync::{Arc, Mutex};
+use tauri::Window;
+
+/// Radius of the inner corners while the window is docked (points)
+const SIDEBAR_CORNER_RADIUS: f64 = 10.0;
+
+// CACornerMask (in layer coordinates, where y grows upwards)
+const MIN_X_MIN_Y_CORNER: usize = 1 << 0;
+const MAX_X_MIN_Y_CORNER: usize = 1 << 1;
+const MIN_X_MAX_Y_CORNER: usize = 1 << 2;
+const MAX_X_MAX_Y_CORNER: usize = 1 << 3;
+
+/// Look of the window before it was docked
+#[derive(Debug, Clone, Copy)]
+struct OriginalAppearance {
+    has_shadow: bool,
+    corner_radius: f64,
+    masks_to_bounds: bool,
+    masked_corners: usize,
+}
+
+/// Makes the docked window look like a sidebar attached to the screen edge
+///
+/// The corners on the docked side are squared off so the window sits flush against
+/// the edge (only the inner corners stay rounded), and the shadow can be turned off.
+/// The original look is remembered the first time and put back by `restore`.
+#[derive(Default)]
+pub struct DockAppearance {
+    /// Shared with the main-thread closures that read and change the NSWindow
+    original: Arc<Mutex<Option<OriginalAppearance>>>,
+}
+
+impl DockAppearance {
+    /// Apply the sidebar look for `edge`, with or without the window shadow
+    pub fn apply(&self, window: &Window, edge: ScreenEdge, shadow: bool) -> Result<(), String> {
+        let original = self.original.clone();
+        with_window_layer(window, move |ns_window, layer| unsafe {
+            if let Ok(mut saved) = original.lock() {
+                if saved.is_none() {
+                    *saved = Some(OriginalAppearance {
+                        has_shadow: msg_send![ns_window, hasShadow],
+                        corner_radius: msg_send![layer, cornerRadius],
+                        masks_to_bounds: msg_send![layer, masksToBounds],
+                        masked_corners: msg_send![layer, maskedCorners],
+                    });
+                }
+            }
+            let _: () = msg_send![layer, setCornerRadius: SIDEBAR_CORNER_RADIUS];
+            let _: () = msg_send![layer, setMaskedCorners: inner_corners(edge)];
+            let _: () = msg_send![layer, setMasksToBounds: true];
+            let _: () = msg_send![ns_window, setHasShadow: shadow];
+            let _: () = msg_send![ns_window, invalidateShadow];
+        })
+    }
+
+    /// Put back the look the window had before `apply` (no-op if it was never applied)
+    pub fn restore(&self, window: &Window) -> Result<(), String> {
+        let original = self.original.clone();
+        with_window_layer(window, move |ns_window, layer| unsafe {
+            let Some(appearance) = original.lock().ok().and_then(|mut o| o.take()) else {
+                return;
+            };
+            let _: () = msg_send![layer, setCornerRadius: appearance.corner_radius];
+            let _: () = msg_send![layer, setMaskedCorners: appearance.masked_corners];
+            let _: () = msg_send![layer, setMasksToBounds: appearance.masks_to_bounds];
+            let _: () = msg_send![ns_window, setHasShadow: appearance.has_shadow];
+            let _: () = msg_send![ns_window, invalidateShadow];
+        })
+    }
+}
+
+/// The corners facing away from the docked edge
+fn inner_corners(edge: ScreenEdge) -> usize {
+    match edge {
+        ScreenEdge::Left => MAX_X_MIN_Y_CORNER | MAX_X_MAX_Y_CORNER,
+        ScreenEdge::Right => MIN_X_MIN_Y_CORNER | MIN_X_MAX_Y_CORNER,
+        ScreenEdge::Top => MIN_X_MIN_Y_CORNER | MAX_X_MIN_Y_CORNER,
+        ScreenEdge::Bottom => MIN_X_MAX_Y_CORNER | MAX_X_MAX_Y_CORNER,
+    }
+}
+
+/// Run `f` on the main thread with the NSWindow and the layer of its content view
+fn with_window_layer<F>(window: &Window, f: F) -> Result<(), String>
+where
+    F: FnOnce(&AnyObject, &AnyObject) + Send + 'static,
+{
+    let target = window.clone();
+    window
+        .run_on_main_thread(move || {
+            let ns_window = match target.ns_window() {
+                Ok(ns_window) => ns_window,
+                Err(e) => {
+                    eprintln!("[autohide] Failed to get NSWindow: {}", e);
+                    return;
+                }
+            };
+            unsafe {
+                let ns_window = &*(ns_window as *const AnyObject);
+                let content_view: *mut AnyObject = msg_send![ns_window, contentView];
+                let Some(content_view) = content_view.as_ref() else {
+                    return;
+                };
+                let _: () = msg_send![content_view, setWantsLayer: true];
+                let layer: *mut AnyObject = msg_send![content_view, layer];
+                if let Some(layer) = layer.as_ref() {
+                    f(ns_window, layer);
+                }
+            }
+        })
+        .map_err(|e| e.to_string())
+}
@@ -1,20 +1,125 @@
 use core_graphics::display::CGRect;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// File name of the persisted autohide config (inside the app config dir)
+pub const CONFIG_FILE_NAME: &str = "autohide.json";
+
+/// Narrowest width (points) the sidebar can be dragged to
+pub const MIN_SIDEBAR_WIDTH: u32 = 240;
 
 /// Screen edge where the window can be hidden
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum ScreenEdge {
     #[default]
     Left,
     Right,
+    Top,
+    Bottom,
+}
+
+impl FromStr for ScreenEdge {
+    type Err = String;
+
+    /// Parse an edge name (case-insensitive); unknown names are an error rather than
+    /// falling back to `Left`
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s.to_lowercase().as_str() {
+            "left" => Ok(ScreenEdge::Left),
+            "right" => Ok(ScreenEdge::Right),
+            "top" => Ok(ScreenEdge::Top),
+            "bottom" => Ok(ScreenEdge::Bottom),
+            _ => Err(format!(
+                "Unknown screen edge: {} (expected left, right, top or bottom)",
+                s
+            )),
+        }
+    }
 }
 
 impl ScreenEdge {
-    pub fn from_str(s: &str) -> Self {
+    /// Whether the window slides up and down (docked at the top or bottom)
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, ScreenEdge::Top | ScreenEdge::Bottom)
+    }
+}
+
+/// Where a partial-height sidebar sits on the display
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum VerticalAnchor {
+    /// Just below the menu bar
+    #[default]
+    Top,
+    /// Centered in the area below the menu bar
+    Center,
+}
+
+impl FromStr for VerticalAnchor {
+    type Err = String;
+
+    /// Parse an anchor name (case-insensitive); unknown names are an error rather than
+    /// falling back to `Top`
+    fn from_str(s: &str) -> Result<Self, String> {
         match s.to_lowercase().as_str() {
-            "right" => ScreenEdge::Right,
-            _ => ScreenEdge::Left,
+            "top" => Ok(VerticalAnchor::Top),
+            "center" => Ok(VerticalAnchor::Center),
+            _ => Err(format!(
+                "Unknown vertical anchor: {} (expected top or center)",
+                s
+            )),
+        }
+    }
+}
+
+/// Easing curve of the slide animation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum EasingKind {
+    Linear,
+    /// Starts slowly and accelerates
+    EaseIn,
+    /// Decelerates so the window settles gently
+    EaseOut,
+    #[default]
+    EaseInOut,
+}
+
+impl EasingKind {
+    /// Parse a curve name ("ease_in_out", "ease-in-out" and "easeInOut" are all accepted)
+    pub fn from_name(name: &str) -> Option<Self> {
+        let normalized: String = name
+            .chars()
+            .filter(|c| *c != '_' && *c != '-')
+            .collect::<String>()
+            .to_lowercase();
+        match normalized.as_str() {
+            "linear" => Some(EasingKind::Linear),
+            "easein" => Some(EasingKind::EaseIn),
+            "easeout" => Some(EasingKind::EaseOut),
+            "easeinout" => Some(EasingKind::EaseInOut),
+            _ => None,
+        }
+    }
+
+    /// Map linear progress (0.0..=1.0) onto the curve
+    pub fn apply(self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            EasingKind::Linear => t,
+            EasingKind::EaseIn => t.powi(3),
+            EasingKind::EaseOut => 1.0 - (1.0 - t).powi(3),
+            EasingKind::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t.powi(3)
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
         }
     }
 }
@@ -23,17 +128,75 @@ impl ScreenEdge {
 #[derive(Debug, Clone)]
 pub struct MonitorBounds {
     pub bounds: CGRect,
+    /// How far below the top of `bounds` windows can start (menu bar or notch, points)
+    pub top_inset: f64,
+}
+
+/// Display information returned by `list_displays`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisplayInfo {
+    pub id: u32,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub is_main: bool,
 }
 
 /// Autohide configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AutohideConfig {
     pub enabled: bool,
     pub edge: ScreenEdge,
     /// Pixels visible when window is hidden (the "handle" width)
+    /// 0 moves the window fully off screen, leaving no handle to click
     pub visible_pixels: f64,
     /// Animation duration in milliseconds
     pub animation_duration_ms: u64,
+    /// Easing curve of the slide animation
+    pub easing: EasingKind,
+    /// Focus the window when it is shown (otherwise it is only brought to the front)
+    pub steal_focus_on_show: bool,
+    /// Show the window when the cursor touches the docked edge
+    pub hover_reveal: bool,
+    /// Limit the hover trigger to the handle's vertical range instead of the whole edge
+    pub trigger_on_handle_only: bool,
+    /// Thickness of the hover trigger strip along the docked edge (points)
+    pub trigger_zone_pixels: f64,
+    /// How long the cursor has to stay in the hover trigger before the window is revealed
+    pub reveal_delay_ms: u64,
+    /// How long to wait after the cursor leaves a hover-revealed window before hiding it
+    pub auto_hide_delay_ms: u64,
+    /// Pick `edge` automatically from the display layout (re-evaluated when displays change);
+    /// cleared when the edge is set manually
+    pub auto_edge: bool,
+    /// Hide the window when it loses focus (e.g. another app is clicked)
+    pub hide_on_blur: bool,
+    /// Last y of the window on each edge it was docked to (offset from the top of the
+    /// display), restored when switching back to that edge
+    pub edge_y: HashMap<ScreenEdge, i32>,
+    /// Fraction of the usable display height the sidebar occupies on the left or right
+    /// edge (1.0 keeps the window's own height and y)
+    pub height_fraction: f64,
+    /// Where the sidebar sits when `height_fraction` is below 1.0
+    pub vertical_anchor: VerticalAnchor,
+    /// Keep the window above other apps (applied at startup; the hidden handle floats
+    /// above them too)
+    pub always_on_top: bool,
+    /// Square off the corners on the docked side while autohide is enabled, so the
+    /// sidebar sits flush against the edge (the original look is restored on disable)
+    pub dock_appearance: bool,
+    /// Keep the window shadow while docked (only with `dock_appearance`)
+    pub dock_shadow: bool,
+    /// Space left above the sidebar (points); it is placed this far below the top of
+    /// the display
+    pub top_margin: f64,
+    /// Space left below the sidebar (points)
+    pub bottom_margin: f64,
+    /// Width of the sidebar (points) set by dragging its inner edge
+    /// (None keeps the width the window is created with)
+    pub sidebar_width: Option<u32>,
 }
 
 impl Default for AutohideConfig {
@@ -43,10 +206,95 @@ impl Default for AutohideConfig {
             edge: ScreenEdge::Left,
             visible_pixels: 8.0,
             animation_duration_ms: 200,
+            easing: EasingKind::EaseInOut,
+            steal_focus_on_show: false,
+            hover_reveal: false,
+            trigger_on_handle_only: true,
+            trigger_zone_pixels: 2.0,
+            reveal_delay_ms: 300,
+            auto_hide_delay_ms: 400,
+            auto_edge: false,
+            hide_on_blur: true,
+            edge_y: HashMap::new(),
+            height_fraction: 1.0,
+            vertical_anchor: VerticalAnchor::Top,
+            always_on_top: false,
+            dock_appearance: true,
+            dock_shadow: false,
+            top_margin: 100.0,
+            bottom_margin: 100.0,
+            sidebar_width: None,
         }
     }
 }
 
+impl AutohideConfig {
+    /// Load config from a JSON file, falling back to defaults if it is missing or corrupt
+    pub fn load(path: &Path) -> Self {
+        let Ok(content) = fs::read_to_string(path) else {
+            return Self::default();
+        };
+        serde_json::from_str(&content).unwrap_or_else(|e| {
+            eprintln!("[autohide] Ignoring corrupt config {:?}: {}", path, e);
+            Self::default()
+        })
+    }
+
+    /// Save config to a JSON file
+    /// Written to a temporary file first so a crash mid-write can't leave a truncated config
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, content).map_err(|e| e.to_string())?;
+        fs::rename(&temp_path, path).map_err(|e| e.to_string())
+    }
+}
+
+/// Payload of the `window:visibility-changed` event
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct WindowVisibilityPayload {
+    pub visible: bool,
+}
+
+/// What made the sidebar slide in or out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VisibilityReason {
+    /// Toggled manually (shortcut, handle click, enabling/disabling autohide)
+    Toggle,
+    /// The app lost focus
+    Blur,
+    /// Hovering the docked edge, or the delay after the cursor left a hover-revealed window
+    Edge,
+    /// Pinning or unpinning the sidebar
+    Pin,
+}
+
+/// Payload of the `sidebar:visibility-changed` event
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SidebarVisibilityPayload {
+    pub visible: bool,
+    pub reason: VisibilityReason,
+}
+
+/// Response of `get_autohide_config`: the saved config and the runtime state next to it
+#[derive(Debug, Clone, Serialize)]
+pub struct AutohideConfigResponse {
+    #[serde(flatten)]
+    pub config: AutohideConfig,
+    /// Whether the sidebar is pinned open (kept by the manager, never saved)
+    pub pinned: bool,
+}
+
+/// Payload of the `sidebar:edge-changed` event
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SidebarEdgePayload {
+    pub edge: ScreenEdge,
+}
+
 /// Window visibility state
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WindowState {
@@ -54,4 +302,78 @@ pub enum WindowState {
     Hidden,
     /// Window is fully visible
     Visible,
+    /// Window is sliding in from the edge
+    Showing,
+    /// Window is sliding out to the edge
+    Hiding,
+    /// Window is sliding between positions (while enabling/disabling autohide)
+    Animating,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_EASINGS: [EasingKind; 4] = [
+        EasingKind::Linear,
+        EasingKind::EaseIn,
+        EasingKind::EaseOut,
+        EasingKind::EaseInOut,
+    ];
+
+    #[test]
+    fn easing_keeps_the_end_points() {
+        for easing in ALL_EASINGS {
+            assert_eq!(easing.apply(0.0), 0.0, "{:?}", easing);
+            assert_eq!(easing.apply(1.0), 1.0, "{:?}", easing);
+        }
+    }
+
+    #[test]
+    fn easing_is_monotonic() {
+        for easing in ALL_EASINGS {
+            let mut previous = easing.apply(0.0);
+            for step in 1..=100 {
+                let value = easing.apply(step as f64 / 100.0);
+                assert!(value >= previous, "{:?} decreases at step {}", easing, step);
+                previous = value;
+            }
+        }
+    }
+
+    #[test]
+    fn screen_edge_names_are_parsed_case_insensitively() {
+        assert_eq!(ScreenEdge::from_str("left"), Ok(ScreenEdge::Left));
+        assert_eq!(ScreenEdge::from_str("Right"), Ok(ScreenEdge::Right));
+        assert_eq!(ScreenEdge::from_str("TOP"), Ok(ScreenEdge::Top));
+        assert_eq!(ScreenEdge::from_str("bottom"), Ok(ScreenEdge::Bottom));
+    }
+
+    #[test]
+    fn unknown_screen_edge_is_an_error() {
+        assert!(ScreenEdge::from_str("rigth").is_err());
+        assert!(ScreenEdge::from_str("").is_err());
+    }
+
+    #[test]
+    fn vertical_anchor_names_are_parsed_and_typos_are_errors() {
+        assert_eq!(
+            VerticalAnchor::from_str("Center"),
+            Ok(VerticalAnchor::Center)
+        );
+        assert_eq!(VerticalAnchor::from_str("top"), Ok(VerticalAnchor::Top));
+        assert!(VerticalAnchor::from_str("centre").is_err());
+    }
+
+    #[test]
+    fn easing_names_accept_common_spellings() {
+        assert_eq!(
+            EasingKind::from_name("ease_in_out"),
+            Some(EasingKind::EaseInOut)
+        );
+        assert_eq!(EasingKind::from_name("ease-in"), Some(EasingKind::EaseIn));
+        assert_eq!(EasingKind::from_name("easeOut"), Some(EasingKind::EaseOut));
+        assert_eq!(EasingKind::from_name("Linear"), Some(EasingKind::Linear));
+        assert_eq!(EasingKind::from_name("bounce"), None);
+    }
 }
@@ -0,0 +1,73 @@
+use std::ffi::c_void;
+
+type CGDirectDisplayID = u32;
+type CGDisplayChangeSummaryFlags = u32;
+type CGDisplayReconfigurationCallBack =
+    extern "C" fn(CGDirectDisplayID, CGDisplayChangeSummaryFlags, *mut c_void);
+
+/// Sent before the configuration changes (a second call follows once it has)
+const BEGIN_CONFIGURATION_FLAG: CGDisplayChangeSummaryFlags = 1 << 0;
+const MOVED_FLAG: CGDisplayChangeSummaryFlags = 1 << 1;
+const SET_MAIN_FLAG: CGDisplayChangeSummaryFlags = 1 << 2;
+const SET_MODE_FLAG: CGDisplayChangeSummaryFlags = 1 << 3;
+const ADD_FLAG: CGDisplayChangeSummaryFlags = 1 << 4;
+const REMOVE_FLAG: CGDisplayChangeSummaryFlags = 1 << 5;
+const ENABLED_FLAG: CGDisplayChangeSummaryFlags = 1 << 8;
+const DISABLED_FLAG: CGDisplayChangeSummaryFlags = 1 << 9;
+const DESKTOP_SHAPE_CHANGED_FLAG: CGDisplayChangeSummaryFlags = 1 << 12;
+
+/// Changes that can move or remove the display the sidebar is docked to
+const LAYOUT_CHANGE_FLAGS: CGDisplayChangeSummaryFlags = MOVED_FLAG
+    | SET_MAIN_FLAG
+    | SET_MODE_FLAG
+    | ADD_FLAG
+    | REMOVE_FLAG
+    | ENABLED_FLAG
+    | DISABLED_FLAG
+    | DESKTOP_SHAPE_CHANGED_FLAG;
+
+#[link(name = "CoreGraphics", kind = "framework")]
+extern "C" {
+    fn CGDisplayRegisterReconfigurationCallback(
+        callback: CGDisplayReconfigurationCallBack,
+        user_info: *mut c_void,
+    ) -> i32;
+}
+
+type ChangeHandler = Box<dyn Fn() + 'static>;
+
+/// Call `on_change` after a display is connected, disconnected, rearranged or changes
+/// resolution
+///
+/// Must be called on the main thread: the callback is delivered on the main run loop.
+/// It runs once per affected display, so a single change can call `on_change` more
+/// than once. The registration lasts for the rest of the process.
+pub fn watch_display_changes<F>(on_change: F) -> Result<(), String>
+where
+    F: Fn() + 'static,
+{
+    let handler: Box<ChangeHandler> = Box::new(Box::new(on_change));
+    let user_info = Box::into_raw(handler) as *mut c_void;
+    let error = unsafe { CGDisplayRegisterReconfigurationCallback(on_reconfigured, user_info) };
+    if error != 0 {
+        // Not registered, so the handler is still ours to free
+        drop(unsafe { Box::from_raw(user_info as *mut ChangeHandler) });
+        return Err(format!(
+            "Failed to watch display changes (CGError {})",
+            error
+        ));
+    }
+    Ok(())
+}
+
+extern "C" fn on_reconfigured(
+    _display: CGDirectDisplayID,
+    flags: CGDisplayChangeSummaryFlags,
+    user_info: *mut c_void,
+) {
+    if flags & BEGIN_CONFIGURATION_FLAG != 0 || flags & LAYOUT_CHANGE_FLAGS == 0 {
+        return;
+    }
+    let handler = unsafe { &*(user_info as *const ChangeHandler) };
+    handler();
+}
@@ -0,0 +1,216 @@
+use core_foundation::runloop::{kCFRunLoopCommonModes, CFRunLoop};
+use core_graphics::display::{CGPoint, CGRect};
+use core_graphics::event::{
+    CGEvent, CGEventTap, CGEventTapLocation, CGEventTapOptions, CGEventTapPlacement, CGEventType,
+};
+use core_graphics::event_source::{CGEventSource, CGEventSourceStateID};
+use std::cell::Cell;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// What the event tap observed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TapEvent {
+    /// The handle was clicked
+    HandleClicked,
+    /// The cursor entered the hover-reveal trigger
+    TriggerEntered,
+    /// The cursor left the hover-reveal trigger
+    TriggerLeft,
+    /// The cursor left the watched window rectangle
+    WindowLeft,
+    /// The cursor came back into the watched window rectangle
+    WindowEntered,
+}
+
+/// Global mouse event tap for the autohide handle
+///
+/// Listens (without consuming events) for clicks and mouse moves anywhere on screen
+/// and calls the callback only when a click lands inside the handle rectangle or the
+/// cursor crosses the hover trigger rectangle or the watched window rectangle, so other
+/// events pass through untouched.
+pub struct HandleEventTap {
+    /// Handle rectangle in global display coordinates (None while visible)
+    handle_rect: Arc<Mutex<Option<CGRect>>>,
+    /// Hover-reveal trigger rectangle in global display coordinates
+    /// (None while visible or when hover-reveal is off)
+    trigger_rect: Arc<Mutex<Option<CGRect>>>,
+    /// Window rectangle watched for the cursor leaving it
+    /// (only set while the window is shown by hover-reveal)
+    window_rect: Arc<Mutex<Option<CGRect>>>,
+    /// Run loop of the tap thread (used to stop it)
+    run_loop: Option<CFRunLoop>,
+}
+
+impl HandleEventTap {
+    pub fn new() -> Self {
+        Self {
+            handle_rect: Arc::new(Mutex::new(None)),
+            trigger_rect: Arc::new(Mutex::new(None)),
+            window_rect: Arc::new(Mutex::new(None)),
+            run_loop: None,
+        }
+    }
+
+    /// Start the event tap on a dedicated thread
+    pub fn start<F>(&mut self, on_event: F) -> Result<(), String>
+    where
+        F: Fn(TapEvent) + Send + 'static,
+    {
+        if self.run_loop.is_some() {
+            return Ok(());
+        }
+
+        let handle_rect = self.handle_rect.clone();
+        let trigger_rect = self.trigger_rect.clone();
+        let window_rect = self.window_rect.clone();
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            // Only fire when the cursor enters or leaves the trigger, not on every move inside it
+            let was_in_trigger = Cell::new(false);
+            // None until the first move after a window rectangle is set
+            let was_in_window = Cell::new(None);
+            let tap = CGEventTap::new(
+                CGEventTapLocation::Session,
+                CGEventTapPlacement::HeadInsertEventTap,
+                CGEventTapOptions::ListenOnly,
+                vec![CGEventType::LeftMouseDown, CGEventType::MouseMoved],
+                move |_proxy, event_type, event| {
+                    let point = event.location();
+                    match event_type {
+                        CGEventType::LeftMouseDown if hit_test(&handle_rect, &point) => {
+                            on_event(TapEvent::HandleClicked);
+                        }
+                        CGEventType::MouseMoved => {
+                            let in_trigger = hit_test(&trigger_rect, &point);
+                            let was_inside = was_in_trigger.replace(in_trigger);
+                            if in_trigger && !was_inside {
+                                on_event(TapEvent::TriggerEntered);
+                            } else if !in_trigger && was_inside {
+                                on_event(TapEvent::TriggerLeft);
+                            }
+
+                            let Some(rect) = window_rect.lock().ok().and_then(|rect| *rect) else {
+                                was_in_window.set(None);
+                                return None;
+                            };
+                            let in_window = rect_contains(&rect, &point);
+                            match was_in_window.replace(Some(in_window)) {
+                                Some(true) | None if !in_window => on_event(TapEvent::WindowLeft),
+                                Some(false) if in_window => on_event(TapEvent::WindowEntered),
+                                _ => {}
+                            }
+                        }
+                        _ => {}
+                    }
+                    None
+                },
+            );
+
+            let tap = match tap {
+                Ok(tap) => tap,
+                Err(_) => {
+                    let _ = tx.send(Err(
+                        "Failed to create event tap (Accessibility permission may be required)"
+                            .to_string(),
+                    ));
+                    return;
+                }
+            };
+
+            let source = match tap.mach_port.create_runloop_source(0) {
+                Ok(source) => source,
+                Err(_) => {
+                    let _ = tx.send(Err("Failed to create run loop source".to_string()));
+                    return;
+                }
+            };
+
+            let run_loop = CFRunLoop::get_current();
+            unsafe {
+                run_loop.add_source(&source, kCFRunLoopCommonModes);
+            }
+            tap.enable();
+            let _ = tx.send(Ok(run_loop));
+
+            // Blocks until `stop` is called
+            CFRunLoop::run_current();
+        });
+
+        let run_loop = rx
+            .recv()
+            .map_err(|_| "Event tap thread exited unexpectedly".to_string())??;
+        self.run_loop = Some(run_loop);
+        Ok(())
+    }
+
+    /// Stop the event tap and its thread
+    pub fn stop(&mut self) {
+        if let Some(run_loop) = self.run_loop.take() {
+            run_loop.stop();
+        }
+        self.set_handle_rect(None);
+        self.set_trigger_rect(None);
+        self.set_window_rect(None);
+    }
+
+    /// Update the clickable handle rectangle (None disables hit testing)
+    pub fn set_handle_rect(&self, rect: Option<CGRect>) {
+        if let Ok(mut handle_rect) = self.handle_rect.lock() {
+            *handle_rect = rect;
+        }
+    }
+
+    /// Update the hover-reveal trigger rectangle (None disables hover-reveal)
+    pub fn set_trigger_rect(&self, rect: Option<CGRect>) {
+        if let Ok(mut trigger_rect) = self.trigger_rect.lock() {
+            *trigger_rect = rect;
+        }
+    }
+
+    /// Update the window rectangle watched for the cursor leaving (None stops watching)
+    pub fn set_window_rect(&self, rect: Option<CGRect>) {
+        if let Ok(mut window_rect) = self.window_rect.lock() {
+            *window_rect = rect;
+        }
+    }
+
+    /// Whether the cursor leaving the window is being watched
+    pub fn watches_window(&self) -> bool {
+        self.window_rect
+            .lock()
+            .map(|rect| rect.is_some())
+            .unwrap_or(false)
+    }
+}
+
+impl Default for HandleEventTap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Check whether a point lies inside the rectangle currently stored in `rect`
+fn hit_test(rect: &Mutex<Option<CGRect>>, point: &CGPoint) -> bool {
+    rect.lock()
+        .ok()
+        .and_then(|rect| *rect)
+        .map(|rect| rect_contains(&rect, point))
+        .unwrap_or(false)
+}
+
+/// Current cursor position in global display coordinates (the same space as the tap's)
+pub fn cursor_location() -> Option<CGPoint> {
+    let source = CGEventSource::new(CGEventSourceStateID::CombinedSessionState).ok()?;
+    CGEvent::new(source).ok().map(|event| event.location())
+}
+
+/// Check whether a point lies inside a rectangle
+pub fn rect_contains(rect: &CGRect, point: &CGPoint) -> bool {
+    point.x >= rect.origin.x
+        && point.x <= rect.origin.x + rect.size.width
+        && point.y >= rect.origin.y
+        && point.y <= rect.origin.y + rect.size.height
+}
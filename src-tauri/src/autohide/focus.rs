@@ -0,0 +1,27 @@
+use objc2::msg_send;
+use objc2::runtime::AnyObject;
+use tauri::Window;
+
+/// Bring the window to the front without making it the key window
+///
+/// `Window::show` / `set_focus` activate the app and take keyboard focus away from
+/// whatever the user is typing in. `orderFrontRegardless` only changes the stacking
+/// order, which is what a sidebar usually wants.
+pub fn order_front_without_focus(window: &Window) -> Result<(), String> {
+    let target = window.clone();
+    window
+        .run_on_main_thread(move || {
+            let ns_window = match target.ns_window() {
+                Ok(ns_window) => ns_window,
+                Err(e) => {
+                    eprintln!("[autohide] Failed to get NSWindow: {}", e);
+                    return;
+                }
+            };
+            unsafe {
+                let ns_window = &*(ns_window as *const AnyObject);
+                let _: () = msg_send![ns_window, orderFrontRegardless];
+            }
+        })
+        .map_err(|e| e.to_string())
+}
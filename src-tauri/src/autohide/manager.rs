@@ -1,153 +1,1220 @@
-use crate::autohide::config::{AutohideConfig, ScreenEdge, WindowState};
+use crate::autohide::accessibility;
+use crate::autohide::animation::SlideAnimator;
+use crate::autohide::appearance::DockAppearance;
+use crate::autohide::config::{
+    AutohideConfig, AutohideConfigResponse, EasingKind, ScreenEdge, SidebarEdgePayload,
+    SidebarVisibilityPayload, VerticalAnchor, VisibilityReason, WindowState,
+    WindowVisibilityPayload, MIN_SIDEBAR_WIDTH,
+};
+use crate::autohide::event_tap::{self, HandleEventTap, TapEvent};
 use crate::autohide::window_controller::WindowController;
-use std::sync::Mutex;
-use tauri::Window;
+use crate::events;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{PhysicalPosition, PhysicalSize, Window};
 
 /// Autohide manager - Sidenotes-style toggle sidebar
 ///
 /// The window slides in/out from the screen edge when toggled.
 /// A small portion remains visible as a "handle" when hidden.
+///
+/// Every show/hide emits `window:visibility-changed`, so the frontend can pause
+/// rendering and polling. Each real change between on screen and hidden also emits
+/// `sidebar:visibility-changed` with what caused it.
 pub struct AutohideManager {
     config: Mutex<AutohideConfig>,
-    state: Mutex<WindowState>,
+    /// Shared with the slide animation, which sets the final state when it completes
+    state: Arc<Mutex<WindowState>>,
     window_controller: Mutex<WindowController>,
+    /// Where the config is persisted (set once the app config dir is known)
+    config_path: Mutex<Option<PathBuf>>,
+    /// Global click tap so that clicking the handle reveals the window
+    event_tap: Mutex<HandleEventTap>,
+    /// Slide animation used when showing/hiding and enabling/disabling autohide
+    animator: SlideAnimator,
+    /// Generation of the pending delayed hide (bumped to cancel it)
+    auto_hide_generation: Mutex<u64>,
+    /// Generation of the pending hover reveal (bumped to cancel it)
+    reveal_generation: Mutex<u64>,
+    /// Pinned open: hiding is suspended until unpinned
+    pinned: Mutex<bool>,
+    /// Visibility last reported through `sidebar:visibility-changed`
+    /// (shared with the slide animation, which reports the end of a hide)
+    reported_visible: Arc<Mutex<bool>>,
+    /// Sidebar look of the docked window (and its original look to restore)
+    appearance: DockAppearance,
 }
 
 impl AutohideManager {
     pub fn new() -> Self {
         Self {
             config: Mutex::new(AutohideConfig::default()),
-            state: Mutex::new(WindowState::Visible),
+            state: Arc::new(Mutex::new(WindowState::Visible)),
             window_controller: Mutex::new(WindowController::new()),
+            config_path: Mutex::new(None),
+            event_tap: Mutex::new(HandleEventTap::new()),
+            animator: SlideAnimator::new(),
+            auto_hide_generation: Mutex::new(0),
+            reveal_generation: Mutex::new(0),
+            pinned: Mutex::new(false),
+            reported_visible: Arc::new(Mutex::new(true)),
+            appearance: DockAppearance::default(),
         }
     }
 
-    /// Enable autohide mode - moves window to hidden position
-    pub fn enable(&self, window: &Window) -> Result<(), String> {
-        // Update config
+    /// Start watching for handle clicks (and edge hovers, if enabled) while hidden
+    /// Fails with a description of what to do if the Accessibility permission is missing
+    pub fn start_event_tap<F>(&self, on_event: F) -> Result<(), String>
+    where
+        F: Fn(TapEvent) + Send + 'static,
+    {
+        ensure_accessibility()?;
+        let mut event_tap = self.event_tap.lock().map_err(|e| e.to_string())?;
+        event_tap.start(on_event)
+    }
+
+    /// Load the persisted config and remember where to save future changes
+    pub fn load_config(&self, path: PathBuf) -> Result<AutohideConfig, String> {
+        let loaded = AutohideConfig::load(&path);
         {
             let mut config = self.config.lock().map_err(|e| e.to_string())?;
-            config.enabled = true;
+            *config = loaded.clone();
         }
-
-        // Cache window state
         {
             let mut controller = self.window_controller.lock().map_err(|e| e.to_string())?;
-            controller.cache_window_state(window)?;
+            controller.set_edge_y(loaded.edge_y.clone());
         }
+        {
+            let mut config_path = self.config_path.lock().map_err(|e| e.to_string())?;
+            *config_path = Some(path);
+        }
+        Ok(loaded)
+    }
 
-        // Hide window initially
-        self.hide(window)?;
-
+    /// Persist the current config (no-op until `load_config` has been called)
+    fn save_config(&self) -> Result<(), String> {
+        let config_path = self.config_path.lock().map_err(|e| e.to_string())?;
+        if let Some(ref path) = *config_path {
+            let config = self.config.lock().map_err(|e| e.to_string())?;
+            config.save(path)?;
+        }
         Ok(())
     }
 
-    /// Disable autohide mode - restores window to original position
+    /// Enable autohide mode - slides window to hidden position
+    ///
+    /// Safe to call while a previous enable/disable is still animating: the running
+    /// animation is stopped and the window slides to the hidden position from where it is.
+    pub fn enable(&self, window: &Window) -> Result<(), String> {
+        let interrupted = self.animator.cancel();
+
+        // Update config
+        let config = {
+            let mut config = self.config.lock().map_err(|e| e.to_string())?;
+            config.enabled = true;
+            config.clone()
+        };
+        self.save_config()?;
+
+        // The window geometry cannot be read reliably while it is hidden or minimized
+        if !window.is_visible().unwrap_or(false) {
+            window.show().map_err(|e| e.to_string())?;
+        }
+        if window.is_minimized().unwrap_or(false) {
+            window.unminimize().map_err(|e| e.to_string())?;
+        }
+
+        // Cache window state (unless it is already cached or a restore was interrupted,
+        // in which case the current position is somewhere in the middle of the screen)
+        let scale_factor = window.scale_factor().map_err(|e| e.to_string())?;
+        let hidden_pos = {
+            let mut controller = self.window_controller.lock().map_err(|e| e.to_string())?;
+            if !controller.has_cached_state() {
+                let resumed = interrupted && controller.resume_interrupted_restore();
+                if !resumed {
+                    // Dock on the display the window is on rather than the main display
+                    controller.attach_to_window_display(window)?;
+                    controller.cache_window_state(window)?;
+                }
+            }
+            fit_height(window, &mut controller, &config)?;
+            controller.calculate_hidden_position(&config, scale_factor)?
+        };
+        self.update_handle_rect(window, &config)?;
+        self.update_appearance(window, &config)?;
+
+        // Slide out to the edge
+        self.set_state(WindowState::Animating)?;
+        let from = window.outer_position().map_err(|e| e.to_string())?;
+        let state = self.state.clone();
+        let hidden_window = window.clone();
+        let reported_visible = self.reported_visible.clone();
+        self.animator.start(
+            window,
+            from,
+            hidden_pos,
+            Duration::from_millis(config.animation_duration_ms),
+            config.easing,
+            move || {
+                if let Ok(mut state) = state.lock() {
+                    *state = WindowState::Hidden;
+                }
+                emit_visibility(
+                    &hidden_window,
+                    &reported_visible,
+                    false,
+                    VisibilityReason::Toggle,
+                );
+            },
+        )
+    }
+
+    /// Disable autohide mode - slides window back to original position
     pub fn disable(&self, window: &Window) -> Result<(), String> {
+        self.animator.cancel();
+
         // Update config
-        {
+        let (duration, easing) = {
             let mut config = self.config.lock().map_err(|e| e.to_string())?;
             config.enabled = false;
-        }
+            (
+                Duration::from_millis(config.animation_duration_ms),
+                config.easing,
+            )
+        };
+        self.save_config()?;
 
-        // Restore original position
+        // Stop watching for handle clicks
         {
-            let mut controller = self.window_controller.lock().map_err(|e| e.to_string())?;
-            controller.restore_original_position(window)?;
+            let mut event_tap = self.event_tap.lock().map_err(|e| e.to_string())?;
+            event_tap.stop();
         }
+        self.appearance.restore(window)?;
 
-        // Update state
-        {
-            let mut state = self.state.lock().map_err(|e| e.to_string())?;
-            *state = WindowState::Visible;
+        // Restore original height and position
+        let scale_factor = window.scale_factor().map_err(|e| e.to_string())?;
+        let (original_pos, unfitted_size) = {
+            let mut controller = self.window_controller.lock().map_err(|e| e.to_string())?;
+            let unfitted_size = controller.unfit_height();
+            (controller.begin_restore(scale_factor), unfitted_size)
+        };
+        if let Some(size) = unfitted_size {
+            window
+                .set_size(tauri::Size::Physical(size))
+                .map_err(|e| e.to_string())?;
         }
+        let Some(original_pos) = original_pos else {
+            emit_visibility(
+                window,
+                &self.reported_visible,
+                true,
+                VisibilityReason::Toggle,
+            );
+            return self.set_state(WindowState::Visible);
+        };
 
-        Ok(())
+        self.set_state(WindowState::Animating)?;
+        let from = window.outer_position().map_err(|e| e.to_string())?;
+        let state = self.state.clone();
+        let restored_window = window.clone();
+        let reported_visible = self.reported_visible.clone();
+        self.animator
+            .start(window, from, original_pos, duration, easing, move || {
+                if let Ok(mut state) = state.lock() {
+                    *state = WindowState::Visible;
+                }
+                emit_visibility(
+                    &restored_window,
+                    &reported_visible,
+                    true,
+                    VisibilityReason::Toggle,
+                );
+            })
     }
 
     /// Toggle window visibility (show/hide)
+    ///
+    /// While autohide is disabled the first toggle enables it (caching the window state
+    /// and sliding the window out to the edge); later toggles slide it in and out.
+    /// Autohide is only turned off again explicitly through `disable`.
+    /// Returns true if the window is now visible.
     pub fn toggle(&self, window: &Window) -> Result<bool, String> {
-        let current_state = {
-            let state = self.state.lock().map_err(|e| e.to_string())?;
-            *state
-        };
+        let enabled = self.config.lock().map_err(|e| e.to_string())?.enabled;
+        let current_state = *self.state.lock().map_err(|e| e.to_string())?;
 
-        match current_state {
-            WindowState::Hidden => {
-                self.show(window)?;
-                Ok(true) // Now visible
+        match toggle_action(enabled, current_state) {
+            ToggleAction::Enable => {
+                self.enable(window)?;
+                Ok(false) // Sliding out to the edge
             }
-            WindowState::Visible => {
-                self.hide(window)?;
-                Ok(false) // Now hidden
+            ToggleAction::Show => {
+                self.show(window, VisibilityReason::Toggle)?;
+                Ok(true)
+            }
+            ToggleAction::Hide => {
+                self.hide(window, VisibilityReason::Toggle)?;
+                Ok(false)
             }
         }
     }
 
     /// Show the window (slide in from edge)
-    pub fn show(&self, window: &Window) -> Result<(), String> {
-        let config = self.config.lock().map_err(|e| e.to_string())?;
+    ///
+    /// A slide that is still running is stopped where it is, so showing mid-hide
+    /// reverses from the current position.
+    pub fn show(&self, window: &Window, reason: VisibilityReason) -> Result<(), String> {
+        let config = {
+            let config = self.config.lock().map_err(|e| e.to_string())?;
+            if !config.enabled {
+                return Ok(());
+            }
+            config.clone()
+        };
+        self.animator.cancel();
+
+        let scale_factor = window.scale_factor().map_err(|e| e.to_string())?;
+        let visible_pos = {
+            let mut controller = self.window_controller.lock().map_err(|e| e.to_string())?;
+            controller.bring_to_front(window, &config)?;
+            fit_height(window, &mut controller, &config)?;
+            controller.calculate_visible_position(&config, scale_factor)?
+        };
+        // The edge may have changed since the look was applied
+        self.update_appearance(window, &config)?;
+
+        // The handle is no longer clickable once the window is shown
+        {
+            let event_tap = self.event_tap.lock().map_err(|e| e.to_string())?;
+            event_tap.set_handle_rect(None);
+            event_tap.set_trigger_rect(None);
+            event_tap.set_window_rect(None);
+        }
+        self.cancel_auto_hide()?;
+        self.cancel_reveal()?;
+
+        // Resume rendering right away so the content is there while sliding in
+        self.set_state(WindowState::Showing)?;
+        emit_visibility(window, &self.reported_visible, true, reason);
+
+        let from = window.outer_position().map_err(|e| e.to_string())?;
+        let state = self.state.clone();
+        self.animator.start(
+            window,
+            from,
+            visible_pos,
+            Duration::from_millis(config.animation_duration_ms),
+            config.easing,
+            move || {
+                if let Ok(mut state) = state.lock() {
+                    *state = WindowState::Visible;
+                }
+            },
+        )
+    }
+
+    /// Hide the window (slide out to edge, leaving handle visible)
+    ///
+    /// A slide that is still running is stopped where it is, so hiding mid-show
+    /// reverses from the current position. Does nothing while the sidebar is pinned.
+    pub fn hide(&self, window: &Window, reason: VisibilityReason) -> Result<(), String> {
+        let config = {
+            let config = self.config.lock().map_err(|e| e.to_string())?;
+            if !config.enabled {
+                return Ok(());
+            }
+            config.clone()
+        };
+        if *self.pinned.lock().map_err(|e| e.to_string())? {
+            return Ok(());
+        }
+        self.animator.cancel();
+
+        let scale_factor = window.scale_factor().map_err(|e| e.to_string())?;
+        let hidden_pos = {
+            let mut controller = self.window_controller.lock().map_err(|e| e.to_string())?;
+            fit_height(window, &mut controller, &config)?;
+            controller.calculate_hidden_position(&config, scale_factor)?
+        };
+        self.update_handle_rect(window, &config)?;
+        self.update_appearance(window, &config)?;
+        {
+            let event_tap = self.event_tap.lock().map_err(|e| e.to_string())?;
+            event_tap.set_window_rect(None);
+        }
+        self.cancel_auto_hide()?;
+
+        self.set_state(WindowState::Hiding)?;
+        let from = window.outer_position().map_err(|e| e.to_string())?;
+        let state = self.state.clone();
+        let hidden_window = window.clone();
+        let reported_visible = self.reported_visible.clone();
+        self.animator.start(
+            window,
+            from,
+            hidden_pos,
+            Duration::from_millis(config.animation_duration_ms),
+            config.easing,
+            move || {
+                if let Ok(mut state) = state.lock() {
+                    *state = WindowState::Hidden;
+                }
+                emit_visibility(&hidden_window, &reported_visible, false, reason);
+            },
+        )
+    }
+
+    /// Show the window unless it is already on screen (or sliding in)
+    /// Returns whether it is visible afterwards.
+    pub fn show_if_hidden(&self, window: &Window) -> Result<bool, String> {
+        if !self.is_visible() {
+            self.show(window, VisibilityReason::Toggle)?;
+        }
+        Ok(self.is_visible())
+    }
+
+    /// Hide the window unless it is already hidden (or sliding out)
+    /// Returns whether it is still visible afterwards, which it is while autohide is
+    /// disabled or the sidebar is pinned.
+    pub fn hide_if_visible(&self, window: &Window) -> Result<bool, String> {
+        if self.is_visible() {
+            self.hide(window, VisibilityReason::Toggle)?;
+        }
+        Ok(self.is_visible())
+    }
+
+    /// Show the window because the cursor touched the edge, and hide it again once
+    /// the cursor has left it for `auto_hide_delay_ms`
+    pub fn reveal_on_hover(&self, window: &Window) -> Result<(), String> {
+        self.show(window, VisibilityReason::Edge)?;
 
+        let config = self.get_config()?;
         if !config.enabled {
             return Ok(());
         }
+        self.watch_window_rect(window, &config)
+    }
+
+    /// Watch for the cursor leaving the shown window
+    fn watch_window_rect(&self, window: &Window, config: &AutohideConfig) -> Result<(), String> {
+        let scale_factor = window.scale_factor().map_err(|e| e.to_string())?;
+        let window_rect = {
+            let controller = self.window_controller.lock().map_err(|e| e.to_string())?;
+            controller.calculate_window_rect(config, scale_factor)?
+        };
+
+        let event_tap = self.event_tap.lock().map_err(|e| e.to_string())?;
+        event_tap.set_window_rect(Some(window_rect));
+        Ok(())
+    }
 
-        let controller = self.window_controller.lock().map_err(|e| e.to_string())?;
-        controller.show_window(window, &config)?;
+    /// Start the delayed reveal after the cursor entered the hover trigger
+    ///
+    /// Returns the token to pass to `finish_reveal` and how long the cursor has to
+    /// stay in the trigger first. Any previously pending reveal is cancelled.
+    pub fn begin_reveal(&self) -> Result<(u64, Duration), String> {
+        let delay = {
+            let config = self.config.lock().map_err(|e| e.to_string())?;
+            Duration::from_millis(config.reveal_delay_ms)
+        };
+        let mut generation = self.reveal_generation.lock().map_err(|e| e.to_string())?;
+        *generation += 1;
+        Ok((*generation, delay))
+    }
 
-        drop(config);
-        drop(controller);
+    /// Cancel the pending hover reveal (the cursor left the trigger before the delay passed)
+    pub fn cancel_reveal(&self) -> Result<(), String> {
+        let mut generation = self.reveal_generation.lock().map_err(|e| e.to_string())?;
+        *generation += 1;
+        Ok(())
+    }
 
+    /// Reveal the window once the delay has passed, unless the reveal was cancelled meanwhile
+    pub fn finish_reveal(&self, window: &Window, token: u64) -> Result<(), String> {
+        {
+            let generation = self.reveal_generation.lock().map_err(|e| e.to_string())?;
+            if *generation != token {
+                return Ok(());
+            }
+        }
+        if self.is_visible() {
+            return Ok(());
+        }
+        self.reveal_on_hover(window)
+    }
+
+    /// Start the delayed hide after the cursor left a hover-revealed window
+    ///
+    /// Returns the token to pass to `finish_auto_hide` and how long to wait first.
+    /// Any previously pending hide is cancelled.
+    pub fn begin_auto_hide(&self) -> Result<(u64, Duration), String> {
+        let delay = {
+            let config = self.config.lock().map_err(|e| e.to_string())?;
+            Duration::from_millis(config.auto_hide_delay_ms)
+        };
+        let mut generation = self.auto_hide_generation.lock().map_err(|e| e.to_string())?;
+        *generation += 1;
+        Ok((*generation, delay))
+    }
+
+    /// Cancel the pending delayed hide (the cursor came back, or the window was
+    /// shown/hidden some other way)
+    pub fn cancel_auto_hide(&self) -> Result<(), String> {
+        let mut generation = self.auto_hide_generation.lock().map_err(|e| e.to_string())?;
+        *generation += 1;
+        Ok(())
+    }
+
+    /// Hide the window once the delay has passed, unless the hide was cancelled meanwhile
+    pub fn finish_auto_hide(&self, window: &Window, token: u64) -> Result<(), String> {
+        {
+            let generation = self.auto_hide_generation.lock().map_err(|e| e.to_string())?;
+            if *generation != token {
+                return Ok(());
+            }
+        }
+        if !self.is_visible() {
+            return Ok(());
+        }
+        self.hide(window, VisibilityReason::Edge)
+    }
+
+    /// Recalculate the clickable handle area and the hover trigger for the current
+    /// edge and display
+    fn update_handle_rect(&self, window: &Window, config: &AutohideConfig) -> Result<(), String> {
+        let scale_factor = window.scale_factor().map_err(|e| e.to_string())?;
+        let (handle_rect, trigger_rect) = {
+            let controller = self.window_controller.lock().map_err(|e| e.to_string())?;
+            (
+                controller.calculate_handle_rect(config, scale_factor)?,
+                controller.calculate_trigger_rect(config, scale_factor)?,
+            )
+        };
+
+        // A fully hidden window leaves no handle to click
+        let handle_rect = Some(handle_rect).filter(|_| config.visible_pixels > 0.0);
+
+        let event_tap = self.event_tap.lock().map_err(|e| e.to_string())?;
+        event_tap.set_handle_rect(handle_rect);
+        event_tap.set_trigger_rect(trigger_rect);
+        Ok(())
+    }
+
+    fn set_state(&self, new_state: WindowState) -> Result<(), String> {
         let mut state = self.state.lock().map_err(|e| e.to_string())?;
-        *state = WindowState::Visible;
+        *state = new_state;
+        Ok(())
+    }
+
+    /// Move the sidebar to another display and re-place it on that display's edge
+    ///
+    /// Works in both hidden and visible states; when autohide is disabled the window
+    /// is simply moved onto the display.
+    pub fn move_to_display(&self, window: &Window, display_id: u32) -> Result<(), String> {
+        let enabled = {
+            let config = self.config.lock().map_err(|e| e.to_string())?;
+            config.enabled
+        };
+
+        let scale_factor = window.scale_factor().map_err(|e| e.to_string())?;
+        if !enabled {
+            let mut controller = self.window_controller.lock().map_err(|e| e.to_string())?;
+            let old_bounds = controller.monitor_bounds();
+            controller.move_to_display(display_id, scale_factor)?;
+            let new_bounds = controller.monitor_bounds().ok_or("No monitor found")?;
+
+            let position = window.outer_position().map_err(|e| e.to_string())?;
+            let size = window.outer_size().map_err(|e| e.to_string())?;
+            let new_position = WindowController::position_on_display(
+                position,
+                size,
+                old_bounds,
+                new_bounds,
+                scale_factor,
+            );
+            return window
+                .set_position(tauri::Position::Physical(new_position))
+                .map_err(|e| e.to_string());
+        }
+
+        {
+            let mut controller = self.window_controller.lock().map_err(|e| e.to_string())?;
+            controller.move_to_display(display_id, scale_factor)?;
+        }
+
+        // Re-place the window in its current state on the new display
+        if self.is_visible() {
+            self.show(window, VisibilityReason::Toggle)
+        } else {
+            self.hide(window, VisibilityReason::Toggle)
+        }
+    }
+
+    /// Re-place the sidebar after a display was connected, disconnected or rearranged
+    ///
+    /// The monitor bounds are refreshed (falling back to the main display if the docked
+    /// one is gone) and the window is put back at its hidden or visible position there,
+    /// so it does not stay stranded on a display that no longer exists. The handle, the
+    /// hover trigger and the watched window rectangle are recalculated for the new
+    /// layout, and the automatic edge is re-evaluated too. While autohide is disabled
+    /// only the bounds are refreshed; macOS moves ordinary windows off removed displays
+    /// itself.
+    pub fn handle_display_change(&self, window: &Window) -> Result<(), String> {
+        let scale_factor = window.scale_factor().map_err(|e| e.to_string())?;
+        {
+            let mut controller = self.window_controller.lock().map_err(|e| e.to_string())?;
+            controller.handle_display_change(scale_factor);
+        }
+        let config = self.get_config()?;
+        if !config.enabled {
+            return Ok(());
+        }
+
+        if self.is_visible() {
+            // Showing stops watching the cursor, so a hover-revealed window would stay
+            // shown after the cursor leaves it
+            let watching = {
+                let event_tap = self.event_tap.lock().map_err(|e| e.to_string())?;
+                event_tap.watches_window()
+            };
+            self.show(window, VisibilityReason::Toggle)?;
+            if watching {
+                self.watch_window_rect(window, &config)?;
+            }
+        } else {
+            self.hide(window, VisibilityReason::Toggle)?;
+        }
+        self.reevaluate_auto_edge(window)
+    }
 
+    /// Set autohide edge
+    /// A manually chosen edge turns off automatic edge selection. The window takes the
+    /// y it last had on that edge.
+    pub fn set_edge(&self, window: &Window, edge: ScreenEdge) -> Result<(), String> {
+        let changed = {
+            let mut config = self.config.lock().map_err(|e| e.to_string())?;
+            let changed = config.edge != edge;
+            self.switch_edge(&mut config, edge)?;
+            config.auto_edge = false;
+            changed
+        };
+        self.save_config()?;
+        if changed {
+            emit_edge(window, edge);
+        }
         Ok(())
     }
 
-    /// Hide the window (slide out to edge, leaving handle visible)
-    pub fn hide(&self, window: &Window) -> Result<(), String> {
-        let config = self.config.lock().map_err(|e| e.to_string())?;
+    /// Change `config.edge`, carrying the window's y over per edge
+    fn switch_edge(&self, config: &mut AutohideConfig, edge: ScreenEdge) -> Result<(), String> {
+        if config.edge != edge {
+            let mut controller = self.window_controller.lock().map_err(|e| e.to_string())?;
+            config.edge_y = controller.switch_edge(config.edge, edge);
+            config.edge = edge;
+        }
+        Ok(())
+    }
+
+    /// Choose the edge automatically and keep re-evaluating it when displays change
+    /// Returns the chosen edge
+    pub fn set_edge_auto(&self, window: &Window) -> Result<ScreenEdge, String> {
+        {
+            let mut config = self.config.lock().map_err(|e| e.to_string())?;
+            config.auto_edge = true;
+        }
+        self.save_config()?;
+        self.apply_auto_edge(window)
+    }
+
+    /// Re-evaluate the automatic edge after the display configuration changed
+    /// (no-op while the edge is set manually)
+    pub fn reevaluate_auto_edge(&self, window: &Window) -> Result<(), String> {
+        if !self.get_config()?.auto_edge {
+            return Ok(());
+        }
+        {
+            let mut controller = self.window_controller.lock().map_err(|e| e.to_string())?;
+            controller.refresh_monitor_bounds();
+        }
+        self.apply_auto_edge(window).map(|_| ())
+    }
+
+    /// Switch to the preferred edge, re-placing the window if autohide is active
+    fn apply_auto_edge(&self, window: &Window) -> Result<ScreenEdge, String> {
+        let current = self.get_config()?.edge;
+        let edge = {
+            let controller = self.window_controller.lock().map_err(|e| e.to_string())?;
+            controller.preferred_edge(window, current)?
+        };
+        if edge == current {
+            return Ok(edge);
+        }
+
+        let enabled = {
+            let mut config = self.config.lock().map_err(|e| e.to_string())?;
+            self.switch_edge(&mut config, edge)?;
+            config.enabled
+        };
+        self.save_config()?;
+        emit_edge(window, edge);
+
+        if enabled {
+            if self.is_visible() {
+                self.show(window, VisibilityReason::Toggle)?;
+            } else {
+                self.hide(window, VisibilityReason::Toggle)?;
+            }
+        }
+        Ok(edge)
+    }
+
+    /// Set whether showing the window also focuses it
+    pub fn set_steal_focus_on_show(&self, steal_focus: bool) -> Result<(), String> {
+        {
+            let mut config = self.config.lock().map_err(|e| e.to_string())?;
+            config.steal_focus_on_show = steal_focus;
+        }
+        self.save_config()
+    }
+
+    /// Set hover-reveal and whether its trigger is limited to the handle's range
+    /// Takes effect immediately if the window is currently hidden.
+    /// Turning hover-reveal off is refused while the hidden window leaves no handle on
+    /// screen, as nothing could reveal it then (see `ensure_reachable`).
+    pub fn set_hover_reveal(
+        &self,
+        window: &Window,
+        hover_reveal: bool,
+        trigger_on_handle_only: bool,
+    ) -> Result<(), String> {
+        if hover_reveal {
+            ensure_accessibility()?;
+        }
+        let config = {
+            let mut config = self.config.lock().map_err(|e| e.to_string())?;
+            ensure_reachable(config.visible_pixels, hover_reveal)?;
+            config.hover_reveal = hover_reveal;
+            config.trigger_on_handle_only = trigger_on_handle_only;
+            config.clone()
+        };
+        self.save_config()?;
+
+        if config.enabled && !self.is_visible() {
+            self.update_handle_rect(window, &config)?;
+        }
+        Ok(())
+    }
+
+    /// Set how thick the hover trigger strip along the docked edge is (points)
+    /// Takes effect immediately if the window is currently hidden.
+    pub fn set_trigger_zone_pixels(&self, window: &Window, pixels: f64) -> Result<(), String> {
+        if !pixels.is_finite() || pixels <= 0.0 {
+            return Err(format!("Invalid trigger zone size: {}", pixels));
+        }
+        let config = {
+            let mut config = self.config.lock().map_err(|e| e.to_string())?;
+            config.trigger_zone_pixels = pixels;
+            config.clone()
+        };
+        self.save_config()?;
+
+        if config.enabled && !self.is_visible() {
+            self.update_handle_rect(window, &config)?;
+        }
+        Ok(())
+    }
+
+    /// Set how many pixels of the hidden window stay on screen as the handle
+    /// Must be between 0 and the window's extent across the edge (its width for the
+    /// left/right edges, its height for top/bottom). A hidden window is re-placed right away.
+    ///
+    /// 0 hides the window completely, which is only allowed with hover-reveal on
+    /// (see `ensure_reachable`).
+    pub fn set_visible_pixels(&self, window: &Window, pixels: f64) -> Result<(), String> {
+        let size = window.outer_size().map_err(|e| e.to_string())?;
+        let edge = self.get_config()?.edge;
+        let extent = if edge.is_horizontal() { size.height } else { size.width };
+        if !pixels.is_finite() || pixels < 0.0 {
+            return Err(format!("Invalid handle width: {} (must not be negative)", pixels));
+        }
+        if pixels > extent as f64 {
+            return Err(format!(
+                "Invalid handle width: {} (larger than the window's {} pixels)",
+                pixels, extent
+            ));
+        }
+
+        let config = {
+            let mut config = self.config.lock().map_err(|e| e.to_string())?;
+            ensure_reachable(pixels, config.hover_reveal)?;
+            config.visible_pixels = pixels;
+            config.clone()
+        };
+        self.save_config()?;
+
+        if config.enabled && !self.is_visible() {
+            self.hide(window, VisibilityReason::Toggle)?;
+        }
+        Ok(())
+    }
+
+    /// Set how much of the display height the sidebar occupies on the left or right edge
+    /// and where it sits (`fraction` must be in 0.0 < fraction <= 1.0; 1.0 brings back
+    /// the window's own height and y). The window is re-placed right away.
+    pub fn set_height(
+        &self,
+        window: &Window,
+        fraction: f64,
+        anchor: VerticalAnchor,
+    ) -> Result<(), String> {
+        if !fraction.is_finite() || fraction <= 0.0 || fraction > 1.0 {
+            return Err(format!(
+                "Invalid height fraction: {} (must be above 0 and at most 1)",
+                fraction
+            ));
+        }
+        let enabled = {
+            let mut config = self.config.lock().map_err(|e| e.to_string())?;
+            config.height_fraction = fraction;
+            config.vertical_anchor = anchor;
+            config.enabled
+        };
+        self.save_config()?;
+
+        if !enabled {
+            return Ok(());
+        }
+        if self.is_visible() {
+            self.show(window, VisibilityReason::Toggle)
+        } else {
+            self.hide(window, VisibilityReason::Toggle)
+        }
+    }
+
+    /// Resize the sidebar to `width` points (dragging its inner edge) and remember it
+    ///
+    /// The width must be at least `MIN_SIDEBAR_WIDTH` and fit on the display. A visible
+    /// sidebar stays snapped to its edge; a hidden one is moved to the hidden position
+    /// for the new width right away. The config file is only written when `persist` is
+    /// set, so a drag can resize live and save once when it ends.
+    pub fn set_sidebar_width(
+        &self,
+        window: &Window,
+        width: u32,
+        persist: bool,
+    ) -> Result<(), String> {
+        if width < MIN_SIDEBAR_WIDTH {
+            return Err(format!(
+                "Invalid sidebar width: {} (must be at least {})",
+                width, MIN_SIDEBAR_WIDTH
+            ));
+        }
+        let display_width = {
+            let controller = self.window_controller.lock().map_err(|e| e.to_string())?;
+            controller.monitor_bounds().map(|bounds| bounds.size.width)
+        };
+        if let Some(display_width) = display_width.filter(|w| width as f64 > *w) {
+            return Err(format!(
+                "Invalid sidebar width: {} (wider than the display's {} points)",
+                width, display_width
+            ));
+        }
+
+        self.resize_width(window, width)?;
+        let config = {
+            let mut config = self.config.lock().map_err(|e| e.to_string())?;
+            config.sidebar_width = Some(width);
+            config.clone()
+        };
+        if persist {
+            self.save_config()?;
+        }
 
         if !config.enabled {
             return Ok(());
         }
+        let state = *self.state.lock().map_err(|e| e.to_string())?;
+        if state == WindowState::Visible {
+            // Re-snap without sliding (a right-docked window keeps its left x when resized)
+            let scale_factor = window.scale_factor().map_err(|e| e.to_string())?;
+            let visible_pos = {
+                let controller = self.window_controller.lock().map_err(|e| e.to_string())?;
+                controller.calculate_visible_position(&config, scale_factor)?
+            };
+            window
+                .set_position(tauri::Position::Physical(visible_pos))
+                .map_err(|e| e.to_string())
+        } else if self.is_visible() {
+            self.show(window, VisibilityReason::Toggle)
+        } else {
+            self.hide(window, VisibilityReason::Toggle)
+        }
+    }
 
-        let controller = self.window_controller.lock().map_err(|e| e.to_string())?;
-        controller.hide_window(window, &config)?;
+    /// Apply the remembered sidebar width (at startup, before autohide is restored)
+    pub fn restore_sidebar_width(&self, window: &Window) -> Result<(), String> {
+        match self.get_config()?.sidebar_width {
+            Some(width) => self.resize_width(window, width),
+            None => Ok(()),
+        }
+    }
 
-        drop(config);
-        drop(controller);
+    /// Resize the window to `width` points, keeping its height, and update the cached size
+    fn resize_width(&self, window: &Window, width: u32) -> Result<(), String> {
+        let scale_factor = window.scale_factor().map_err(|e| e.to_string())?;
+        let height = window.outer_size().map_err(|e| e.to_string())?.height;
+        let width = (width as f64 * scale_factor).round() as u32;
+        window
+            .set_size(tauri::Size::Physical(PhysicalSize::new(width, height)))
+            .map_err(|e| e.to_string())?;
+        let mut controller = self.window_controller.lock().map_err(|e| e.to_string())?;
+        controller.set_window_width(width);
+        Ok(())
+    }
 
-        let mut state = self.state.lock().map_err(|e| e.to_string())?;
-        *state = WindowState::Hidden;
+    /// Set the space (points) left above and below the sidebar and re-place it
+    ///
+    /// The window is moved `top` below the top of its display and resized to fill the
+    /// rest of the height minus `bottom`. With a partial height (`height_fraction`
+    /// below 1.0) the fraction decides the height and y instead.
+    pub fn set_margins(&self, window: &Window, top: f64, bottom: f64) -> Result<(), String> {
+        if !top.is_finite() || !bottom.is_finite() || top < 0.0 || bottom < 0.0 {
+            return Err(format!(
+                "Invalid margins: {} and {} (must not be negative)",
+                top, bottom
+            ));
+        }
+        let bounds = {
+            let controller = self.window_controller.lock().map_err(|e| e.to_string())?;
+            controller.monitor_bounds().ok_or("No monitor found")?
+        };
+        // Leave at least the narrowest sidebar width as height, so it stays usable
+        if bounds.size.height - top - bottom < MIN_SIDEBAR_WIDTH as f64 {
+            return Err(format!(
+                "Invalid margins: {} and {} (too large for the display's {} points)",
+                top, bottom, bounds.size.height
+            ));
+        }
+
+        let config = {
+            let mut config = self.config.lock().map_err(|e| e.to_string())?;
+            config.top_margin = top;
+            config.bottom_margin = bottom;
+            config.clone()
+        };
+        self.save_config()?;
+
+        let scale_factor = window.scale_factor().map_err(|e| e.to_string())?;
+        let y = ((bounds.origin.y + top) * scale_factor) as i32;
+        let height = ((bounds.size.height - top - bottom) * scale_factor) as u32;
+        let width = window.outer_size().map_err(|e| e.to_string())?.width;
+        window
+            .set_size(tauri::Size::Physical(PhysicalSize::new(width, height)))
+            .map_err(|e| e.to_string())?;
+
+        if !config.enabled {
+            let x = window.outer_position().map_err(|e| e.to_string())?.x;
+            return window
+                .set_position(tauri::Position::Physical(PhysicalPosition::new(x, y)))
+                .map_err(|e| e.to_string());
+        }
+        {
+            let mut controller = self.window_controller.lock().map_err(|e| e.to_string())?;
+            controller.set_vertical_layout(y, height);
+        }
+        if self.is_visible() {
+            self.show(window, VisibilityReason::Toggle)
+        } else {
+            self.hide(window, VisibilityReason::Toggle)
+        }
+    }
+
+    /// Keep the window above other apps, or let it be covered again
+    ///
+    /// Only the window level changes, so sliding to and from the edge works as before;
+    /// while hidden the handle stays above other apps and can always be clicked.
+    pub fn set_always_on_top(&self, window: &Window, enabled: bool) -> Result<(), String> {
+        window
+            .set_always_on_top(enabled)
+            .map_err(|e| e.to_string())?;
+        {
+            let mut config = self.config.lock().map_err(|e| e.to_string())?;
+            config.always_on_top = enabled;
+        }
+        self.save_config()
+    }
+
+    /// Set whether the docked window gets the sidebar look, and whether it keeps its shadow
+    /// Takes effect immediately while autohide is enabled.
+    pub fn set_dock_appearance(
+        &self,
+        window: &Window,
+        enabled: bool,
+        shadow: bool,
+    ) -> Result<(), String> {
+        let config = {
+            let mut config = self.config.lock().map_err(|e| e.to_string())?;
+            config.dock_appearance = enabled;
+            config.dock_shadow = shadow;
+            config.clone()
+        };
+        self.save_config()?;
 
+        if config.enabled {
+            self.update_appearance(window, &config)?;
+        }
         Ok(())
     }
 
-    /// Set autohide edge
-    pub fn set_edge(&self, edge: ScreenEdge) -> Result<(), String> {
-        let mut config = self.config.lock().map_err(|e| e.to_string())?;
-        config.edge = edge;
+    /// Apply the sidebar look for the current edge, or restore the original look if
+    /// it is turned off
+    fn update_appearance(&self, window: &Window, config: &AutohideConfig) -> Result<(), String> {
+        if config.dock_appearance {
+            self.appearance.apply(window, config.edge, config.dock_shadow)
+        } else {
+            self.appearance.restore(window)
+        }
+    }
+
+    /// Set the easing curve of the slide animation
+    pub fn set_easing(&self, easing: EasingKind) -> Result<(), String> {
+        {
+            let mut config = self.config.lock().map_err(|e| e.to_string())?;
+            config.easing = easing;
+        }
+        self.save_config()
+    }
+
+    /// Set how long to wait after the cursor leaves a hover-revealed window before hiding it
+    pub fn set_auto_hide_delay(&self, delay_ms: u64) -> Result<(), String> {
+        {
+            let mut config = self.config.lock().map_err(|e| e.to_string())?;
+            config.auto_hide_delay_ms = delay_ms;
+        }
+        self.save_config()
+    }
+
+    /// Set how long the cursor has to stay in the hover trigger before the window is revealed
+    pub fn set_reveal_delay(&self, delay_ms: u64) -> Result<(), String> {
+        {
+            let mut config = self.config.lock().map_err(|e| e.to_string())?;
+            config.reveal_delay_ms = delay_ms;
+        }
+        self.save_config()
+    }
+
+    /// Set whether the window hides itself when it loses focus
+    pub fn set_hide_on_blur(&self, hide_on_blur: bool) -> Result<(), String> {
+        {
+            let mut config = self.config.lock().map_err(|e| e.to_string())?;
+            config.hide_on_blur = hide_on_blur;
+        }
+        self.save_config()
+    }
+
+    /// Hide the window after it lost focus, if autohide and `hide_on_blur` are enabled
+    pub fn hide_on_blur(&self, window: &Window) -> Result<(), String> {
+        let config = self.get_config()?;
+        if !config.enabled || !config.hide_on_blur || !self.is_visible() {
+            return Ok(());
+        }
+        self.hide(window, VisibilityReason::Blur)
+    }
+
+    /// Pin the sidebar open (showing it if hidden), or unpin it
+    ///
+    /// While pinned every hide is skipped. Unpinning hides the window right away if
+    /// the cursor is away from it; otherwise it is hidden once the cursor leaves, as
+    /// with a hover-revealed window.
+    pub fn set_pinned(&self, window: &Window, pinned: bool) -> Result<(), String> {
+        *self.pinned.lock().map_err(|e| e.to_string())? = pinned;
+
+        let config = self.config.lock().map_err(|e| e.to_string())?.clone();
+        if !config.enabled {
+            return Ok(());
+        }
+        if pinned {
+            self.cancel_auto_hide()?;
+            if !self.is_visible() {
+                self.show(window, VisibilityReason::Pin)?;
+            }
+            return Ok(());
+        }
+        if !self.is_visible() {
+            return Ok(());
+        }
+
+        let scale_factor = window.scale_factor().map_err(|e| e.to_string())?;
+        let window_rect = {
+            let controller = self.window_controller.lock().map_err(|e| e.to_string())?;
+            controller.calculate_window_rect(&config, scale_factor)?
+        };
+        let cursor_inside = event_tap::cursor_location()
+            .is_some_and(|cursor| event_tap::rect_contains(&window_rect, &cursor));
+        if !cursor_inside {
+            return self.hide(window, VisibilityReason::Pin);
+        }
+        let event_tap = self.event_tap.lock().map_err(|e| e.to_string())?;
+        event_tap.set_window_rect(Some(window_rect));
         Ok(())
     }
 
     /// Get current config
     pub fn get_config(&self) -> Result<AutohideConfig, String> {
-        let config = self.config.lock().map_err(|e| e.to_string())?;
-        Ok(config.clone())
+        Ok(self.config.lock().map_err(|e| e.to_string())?.clone())
     }
 
-    /// Check if window is currently visible
+    /// Get current config together with whether the sidebar is pinned
+    pub fn get_config_response(&self) -> Result<AutohideConfigResponse, String> {
+        Ok(AutohideConfigResponse {
+            config: self.get_config()?,
+            pinned: *self.pinned.lock().map_err(|e| e.to_string())?,
+        })
+    }
+
+    /// Check if window is currently visible (or sliding in)
     pub fn is_visible(&self) -> bool {
         self.state
             .lock()
-            .map(|s| *s == WindowState::Visible)
+            .map(|s| matches!(*s, WindowState::Visible | WindowState::Showing))
             .unwrap_or(true)
     }
 }
 
+/// Fail with instructions unless the Accessibility permission needed by the event tap
+/// has been granted
+fn ensure_accessibility() -> Result<(), String> {
+    if accessibility::is_trusted() {
+        return Ok(());
+    }
+    Err("Accessibility permission is required to reveal the sidebar from the screen edge. \
+         Allow this app in System Settings > Privacy & Security > Accessibility, then try again."
+        .to_string())
+}
+
+/// Refuse a window that hides completely (no handle) with hover-reveal off, since
+/// nothing on screen could reveal it then
+fn ensure_reachable(visible_pixels: f64, hover_reveal: bool) -> Result<(), String> {
+    if visible_pixels > 0.0 || hover_reveal {
+        return Ok(());
+    }
+    Err("The hidden sidebar would leave no handle on screen with hover-reveal off, \
+         so it could not be brought back. Keep some pixels visible or turn on hover-reveal."
+        .to_string())
+}
+
+/// Resize the window to the configured partial height, if that changes its size
+fn fit_height(
+    window: &Window,
+    controller: &mut WindowController,
+    config: &AutohideConfig,
+) -> Result<(), String> {
+    let scale_factor = window.scale_factor().map_err(|e| e.to_string())?;
+    match controller.fit_height(config, scale_factor) {
+        Some(size) => window
+            .set_size(tauri::Size::Physical(size))
+            .map_err(|e| e.to_string()),
+        None => Ok(()),
+    }
+}
+
+/// Tell the frontend whether the window is now on screen
+///
+/// `sidebar:visibility-changed` is only sent when the visibility differs from the
+/// last one reported, so re-placing a window in the same state does not repeat it.
+fn emit_visibility(
+    window: &Window,
+    reported_visible: &Mutex<bool>,
+    visible: bool,
+    reason: VisibilityReason,
+) {
+    if let Err(e) = events::emit(window, events::WINDOW_VISIBILITY_CHANGED, WindowVisibilityPayload { visible }) {
+        eprintln!("[autohide] Failed to emit visibility event: {}", e);
+    }
+
+    let Ok(mut reported) = reported_visible.lock() else {
+        return;
+    };
+    if *reported == visible {
+        return;
+    }
+    *reported = visible;
+    let payload = SidebarVisibilityPayload { visible, reason };
+    if let Err(e) = events::emit(window, events::SIDEBAR_VISIBILITY_CHANGED, payload) {
+        eprintln!("[autohide] Failed to emit sidebar visibility event: {}", e);
+    }
+}
+
+/// Report a new autohide edge through `sidebar:edge-changed`
+fn emit_edge(window: &Window, edge: ScreenEdge) {
+    if let Err(e) = events::emit(
+        window,
+        events::SIDEBAR_EDGE_CHANGED,
+        SidebarEdgePayload { edge },
+    ) {
+        eprintln!("[autohide] Failed to emit edge event: {}", e);
+    }
+}
+
+/// What `toggle` does in the current autohide state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ToggleAction {
+    /// Autohide is disabled: enable it, which hides the window at the edge
+    Enable,
+    Show,
+    Hide,
+}
+
+fn toggle_action(enabled: bool, state: WindowState) -> ToggleAction {
+    match (enabled, state) {
+        (false, _) => ToggleAction::Enable,
+        // Reverse a slide that is still running; `Animating` only happens while
+        // enabling (sliding out), so reveal it
+        (true, WindowState::Hidden | WindowState::Hiding | WindowState::Animating) => {
+            ToggleAction::Show
+        }
+        (true, WindowState::Visible | WindowState::Showing) => ToggleAction::Hide,
+    }
+}
+
 impl Default for AutohideManager {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggle_enables_autohide_while_disabled() {
+        for state in [WindowState::Visible, WindowState::Hidden, WindowState::Animating] {
+            assert_eq!(toggle_action(false, state), ToggleAction::Enable);
+        }
+    }
+
+    #[test]
+    fn toggle_follows_disabled_enabled_hidden_transitions() {
+        // Disabled: the first toggle enables autohide, which slides out and ends Hidden
+        assert_eq!(toggle_action(false, WindowState::Visible), ToggleAction::Enable);
+        // Still sliding out, or hidden at the edge: reveal
+        assert_eq!(toggle_action(true, WindowState::Animating), ToggleAction::Show);
+        assert_eq!(toggle_action(true, WindowState::Hidden), ToggleAction::Show);
+        // Revealed: hide again rather than disabling autohide
+        assert_eq!(toggle_action(true, WindowState::Visible), ToggleAction::Hide);
+    }
+
+    #[test]
+    fn toggle_reverses_a_running_slide() {
+        assert_eq!(toggle_action(true, WindowState::Showing), ToggleAction::Hide);
+        assert_eq!(toggle_action(true, WindowState::Hiding), ToggleAction::Show);
+    }
+
+    #[test]
+    fn a_completely_hidden_window_needs_hover_reveal() {
+        assert!(ensure_reachable(0.0, false).is_err());
+        assert!(ensure_reachable(0.0, true).is_ok());
+        assert!(ensure_reachable(4.0, false).is_ok());
+    }
+}
@@ -1,6 +1,18 @@
+pub mod accessibility;
+pub mod animation;
+pub mod appearance;
 pub mod config;
+pub mod display_watch;
+pub mod event_tap;
+pub mod focus;
 pub mod manager;
+pub mod screen;
 pub mod window_controller;
+pub mod worker;
 
-pub use config::{AutohideConfig, ScreenEdge};
+pub use config::{
+    AutohideConfig, AutohideConfigResponse, DisplayInfo, EasingKind, ScreenEdge, VerticalAnchor, VisibilityReason,
+    CONFIG_FILE_NAME,
+};
 pub use manager::AutohideManager;
+pub use worker::AutohideWorker;
@@ -0,0 +1,51 @@
+use objc2::rc::Retained;
+use objc2::runtime::{AnyClass, AnyObject};
+use objc2::{msg_send, sel};
+use objc2_foundation::{NSArray, NSEdgeInsets, NSNumber, NSRect, NSString};
+
+/// How far below the top of a display windows can start (points)
+///
+/// The larger of the menu bar height (the gap between `frame` and `visibleFrame`,
+/// which is 0 when the menu bar hides itself) and, on displays with a camera housing,
+/// the notch (`safeAreaInsets.top`). None if no NSScreen matches the display.
+pub fn top_inset(display_id: u32) -> Option<f64> {
+    let screen = screen_for_display(display_id)?;
+    unsafe {
+        let frame: NSRect = msg_send![&*screen, frame];
+        let visible: NSRect = msg_send![&*screen, visibleFrame];
+        let menu_bar =
+            (frame.origin.y + frame.size.height) - (visible.origin.y + visible.size.height);
+
+        // safeAreaInsets is only available on macOS 12 and later
+        let has_safe_area: bool = msg_send![&*screen, respondsToSelector: sel!(safeAreaInsets)];
+        let notch = if has_safe_area {
+            let insets: NSEdgeInsets = msg_send![&*screen, safeAreaInsets];
+            insets.top
+        } else {
+            0.0
+        };
+
+        Some(menu_bar.max(notch).max(0.0))
+    }
+}
+
+/// The NSScreen showing a Core Graphics display
+fn screen_for_display(display_id: u32) -> Option<Retained<AnyObject>> {
+    let class = AnyClass::get(c"NSScreen")?;
+    let key = NSString::from_str("NSScreenNumber");
+    unsafe {
+        let screens: Option<Retained<NSArray>> = msg_send![class, screens];
+        let screens = screens?;
+        (0..screens.count())
+            .map(|i| screens.objectAtIndex(i))
+            .find(|screen| {
+                let description: Option<Retained<AnyObject>> =
+                    msg_send![&**screen, deviceDescription];
+                let number: Option<Retained<NSNumber>> = match description {
+                    Some(description) => msg_send![&*description, objectForKey: &*key],
+                    None => None,
+                };
+                number.is_some_and(|number| number.unsignedIntValue() == display_id)
+            })
+    }
+}
@@ -1,7 +1,37 @@
-use crate::autohide::config::{AutohideConfig, MonitorBounds, ScreenEdge};
-use core_graphics::display::CGDisplay;
+use crate::autohide::config::{
+    AutohideConfig, DisplayInfo, MonitorBounds, ScreenEdge, VerticalAnchor,
+};
+use crate::autohide::focus;
+use crate::autohide::screen;
+use core_graphics::display::{CGDisplay, CGPoint, CGRect, CGSize};
+use std::collections::HashMap;
+use std::thread;
+use std::time::Duration;
 use tauri::{PhysicalPosition, PhysicalSize, Window};
 
+/// How far beyond both ends of the handle the hover trigger extends (points)
+const HANDLE_TRIGGER_MARGIN: f64 = 24.0;
+
+/// Height of the macOS menu bar (points), which is shown at the top of every display
+/// and which windows cannot be placed under
+/// (used when the usable area of a display cannot be queried)
+const MENU_BAR_HEIGHT: f64 = 25.0;
+
+/// Displays at least this wide (width / height) are treated as ultra-wide
+const ULTRA_WIDE_ASPECT_RATIO: f64 = 2.1;
+
+/// Attempts to read the window geometry (it can fail right after the window is shown)
+const GEOMETRY_READ_ATTEMPTS: usize = 3;
+
+/// Wait between geometry read attempts
+const GEOMETRY_RETRY_DELAY: Duration = Duration::from_millis(30);
+
+/// Window size used when the real size cannot be read
+const FALLBACK_WINDOW_SIZE: PhysicalSize<u32> = PhysicalSize {
+    width: 400,
+    height: 800,
+};
+
 /// Window controller for positioning and showing/hiding the window
 pub struct WindowController {
     /// Cached monitor bounds for performance
@@ -10,6 +40,15 @@ pub struct WindowController {
     original_position: Option<PhysicalPosition<i32>>,
     /// Original window size
     window_size: Option<PhysicalSize<u32>>,
+    /// Display the sidebar is attached to (None = main display)
+    display_id: Option<u32>,
+    /// Position and size the window is sliding back to after autohide was disabled,
+    /// so that re-enabling mid-animation does not cache an in-between position
+    restoring: Option<(PhysicalPosition<i32>, PhysicalSize<u32>)>,
+    /// Last y of the window on each edge (offset from the top of the display)
+    edge_y: HashMap<ScreenEdge, i32>,
+    /// y and height of the window before `fit_height` changed them
+    unfitted: Option<(i32, u32)>,
 }
 
 impl WindowController {
@@ -18,116 +57,959 @@ impl WindowController {
             monitor_bounds: None,
             original_position: None,
             window_size: None,
+            display_id: None,
+            restoring: None,
+            edge_y: HashMap::new(),
+            unfitted: None,
         };
         controller.refresh_monitor_bounds();
         controller
     }
 
     /// Refresh cached monitor bounds
+    ///
+    /// Falls back to the main display if the selected display is no longer active.
     pub fn refresh_monitor_bounds(&mut self) {
-        let main_display = CGDisplay::main();
-        let main_bounds = main_display.bounds();
-        self.monitor_bounds = Some(MonitorBounds {
-            bounds: main_bounds,
-        });
+        let display = self
+            .display_id
+            .map(CGDisplay::new)
+            .filter(|display| display.is_active())
+            .unwrap_or_else(CGDisplay::main);
+        self.monitor_bounds = Some(monitor_for(display));
+    }
+
+    /// Refresh the monitor bounds after the display configuration changed
+    ///
+    /// If the docked display was disconnected the main display is used instead (and the
+    /// sidebar returns once it is reconnected). The cached original position is carried
+    /// over and clamped so the window stays reachable on the display now in use.
+    pub fn handle_display_change(&mut self, scale_factor: f64) {
+        let old_bounds = self.monitor_bounds();
+        self.refresh_monitor_bounds();
+        if let (Some(position), Some(size), Some(new_bounds)) = (
+            self.original_position,
+            self.window_size,
+            self.monitor_bounds(),
+        ) {
+            self.original_position = Some(Self::position_on_display(
+                position,
+                size,
+                old_bounds,
+                new_bounds,
+                scale_factor,
+            ));
+        }
+    }
+
+    /// Attach the sidebar to the display the window currently occupies
+    ///
+    /// The display containing the window's center is picked (or the nearest one if the
+    /// center is in a gap between displays), so displays left of or above the main
+    /// display (with negative coordinates) work too. Keeps the current display if the
+    /// window position cannot be read.
+    pub fn attach_to_window_display(&mut self, window: &Window) -> Result<(), String> {
+        let scale_factor = window.scale_factor().map_err(|e| e.to_string())?;
+        let Some(position) = read_with_retry(|| window.outer_position()) else {
+            self.refresh_monitor_bounds();
+            return Ok(());
+        };
+        let size = read_with_retry(|| window.outer_size()).unwrap_or(FALLBACK_WINDOW_SIZE);
+        let center = CGPoint::new(
+            (position.x as f64 + size.width as f64 / 2.0) / scale_factor,
+            (position.y as f64 + size.height as f64 / 2.0) / scale_factor,
+        );
+
+        let displays: Vec<(u32, CGRect)> = CGDisplay::active_displays()
+            .map_err(|e| format!("Failed to list displays (CGError {})", e))?
+            .into_iter()
+            .map(|id| (id, CGDisplay::new(id).bounds()))
+            .collect();
+        if let Some(display_id) = display_for_point(&center, &displays) {
+            self.display_id = Some(display_id);
+        }
+        self.refresh_monitor_bounds();
+        Ok(())
+    }
+
+    /// Attach the sidebar to another display
+    ///
+    /// The cached original position is carried over to the new display (keeping its
+    /// offset from the display origin) and clamped so the whole window, and therefore
+    /// the handle, stays inside the new bounds.
+    pub fn move_to_display(&mut self, display_id: u32, scale_factor: f64) -> Result<(), String> {
+        let display = CGDisplay::new(display_id);
+        if !display.is_active() {
+            return Err(format!("Display {} is not active", display_id));
+        }
+
+        let old_bounds = self.monitor_bounds.as_ref().map(|m| m.bounds);
+        let new_bounds = display.bounds();
+
+        self.display_id = Some(display_id);
+        self.monitor_bounds = Some(monitor_for(display));
+
+        if let (Some(position), Some(size)) = (self.original_position, self.window_size) {
+            self.original_position = Some(Self::position_on_display(
+                position,
+                size,
+                old_bounds,
+                new_bounds,
+                scale_factor,
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Translate a window position from one display to another and clamp it into the new bounds
+    ///
+    /// The bounds are in points, so the offset and the clamping are worked out in points
+    /// (using `scale_factor`) and the result is converted back to physical pixels.
+    pub fn position_on_display(
+        position: PhysicalPosition<i32>,
+        size: PhysicalSize<u32>,
+        old_bounds: Option<CGRect>,
+        new_bounds: CGRect,
+        scale_factor: f64,
+    ) -> PhysicalPosition<i32> {
+        let x = position.x as f64 / scale_factor;
+        let y = position.y as f64 / scale_factor;
+        let (offset_x, offset_y) = match old_bounds {
+            Some(old) => (x - old.origin.x, y - old.origin.y),
+            None => (0.0, 0.0),
+        };
+
+        let min_x = new_bounds.origin.x;
+        let min_y = new_bounds.origin.y;
+        let max_x = (min_x + new_bounds.size.width - size.width as f64 / scale_factor).max(min_x);
+        let max_y = (min_y + new_bounds.size.height - size.height as f64 / scale_factor).max(min_y);
+
+        PhysicalPosition::new(
+            ((min_x + offset_x).clamp(min_x, max_x) * scale_factor).round() as i32,
+            ((min_y + offset_y).clamp(min_y, max_y) * scale_factor).round() as i32,
+        )
+    }
+
+    /// Bounds of the display the sidebar is currently attached to
+    pub fn monitor_bounds(&self) -> Option<CGRect> {
+        self.monitor_bounds.as_ref().map(|m| m.bounds)
+    }
+
+    /// Top of the area a window can occupy on the display (below the menu bar and notch)
+    fn usable_top(monitor: &MonitorBounds) -> f64 {
+        monitor.bounds.origin.y + monitor.top_inset
+    }
+
+    /// Restore the per-edge y positions saved with the config
+    pub fn set_edge_y(&mut self, edge_y: HashMap<ScreenEdge, i32>) {
+        self.edge_y = edge_y;
+    }
+
+    /// Move the window's y from one edge to another
+    ///
+    /// The current y is remembered for `from`, and the y last used on `to` is restored.
+    /// An edge used for the first time keeps the current y. Returns the per-edge
+    /// positions to persist.
+    pub fn switch_edge(&mut self, from: ScreenEdge, to: ScreenEdge) -> HashMap<ScreenEdge, i32> {
+        let top = self
+            .monitor_bounds()
+            .map_or(0, |bounds| bounds.origin.y as i32);
+        if let Some(position) = self.original_position.as_mut() {
+            self.edge_y.insert(from, position.y - top);
+            if let Some(y) = self.edge_y.get(&to) {
+                position.y = top + y;
+            }
+        }
+        self.edge_y.clone()
+    }
+
+    /// Resize the cached window to `config.height_fraction` of the usable display height
+    /// and move it to `config.vertical_anchor`
+    ///
+    /// Only applies on the left and right edges with a fraction below 1.0; otherwise the
+    /// y and height the window had before it was fitted are put back. The hidden
+    /// position and the handle follow the cached geometry, so they stay aligned with the
+    /// resized window. Returns the new size to apply to the window, if it changed.
+    pub fn fit_height(
+        &mut self,
+        config: &AutohideConfig,
+        scale_factor: f64,
+    ) -> Option<PhysicalSize<u32>> {
+        if config.edge.is_horizontal() || config.height_fraction >= 1.0 {
+            return self.unfit_height();
+        }
+        let monitor = self.monitor_bounds.as_ref()?;
+        let usable_top = Self::usable_top(monitor);
+        let usable_height = monitor.bounds.size.height - monitor.top_inset;
+        let height = usable_height * config.height_fraction.max(0.0);
+        let y = match config.vertical_anchor {
+            VerticalAnchor::Top => usable_top,
+            VerticalAnchor::Center => usable_top + (usable_height - height) / 2.0,
+        };
+
+        let (Some(position), Some(size)) =
+            (self.original_position.as_mut(), self.window_size.as_mut())
+        else {
+            return None;
+        };
+        self.unfitted.get_or_insert((position.y, size.height));
+        position.y = (y * scale_factor).round() as i32;
+        let height = (height * scale_factor).round() as u32;
+        if size.height == height {
+            return None;
+        }
+        size.height = height;
+        Some(*size)
+    }
+
+    /// Put back the y and height the window had before `fit_height` changed them
+    /// Returns the size to apply to the window, if it changed.
+    pub fn unfit_height(&mut self) -> Option<PhysicalSize<u32>> {
+        let (y, height) = self.unfitted.take()?;
+        let position = self.original_position.as_mut()?;
+        let size = self.window_size.as_mut()?;
+        position.y = y;
+        if size.height == height {
+            return None;
+        }
+        size.height = height;
+        Some(*size)
+    }
+
+    /// Update the cached width after the window was resized (physical pixels), so the
+    /// hidden and visible positions are calculated for the new width
+    pub fn set_window_width(&mut self, width: u32) {
+        if let Some(size) = self.window_size.as_mut() {
+            size.width = width;
+        }
+    }
+
+    /// Update the cached y and height after the window was moved and resized vertically
+    /// (this becomes the geometry `fit_height` returns to at full height)
+    pub fn set_vertical_layout(&mut self, y: i32, height: u32) {
+        self.unfitted = None;
+        if let Some(position) = self.original_position.as_mut() {
+            position.y = y;
+        }
+        if let Some(size) = self.window_size.as_mut() {
+            size.height = height;
+        }
+    }
+
+    /// Whether the original window position and size are cached
+    pub fn has_cached_state(&self) -> bool {
+        self.original_position.is_some() && self.window_size.is_some()
     }
 
     /// Cache window position and size
+    ///
+    /// Reading the geometry is retried a few times; if it still fails, the inner size
+    /// (or a default size) and the top-left of the display are used instead. A window
+    /// that is (partly) off screen is pulled back inside the display.
     pub fn cache_window_state(&mut self, window: &Window) -> Result<(), String> {
-        self.original_position = window.outer_position().ok();
-        self.window_size = window.outer_size().ok();
+        self.restoring = None;
+        self.unfitted = None;
 
-        if self.original_position.is_none() || self.window_size.is_none() {
-            return Err("Failed to get window state".to_string());
-        }
+        let scale_factor = window.scale_factor().map_err(|e| e.to_string())?;
+        let size = read_with_retry(|| window.outer_size())
+            .or_else(|| window.inner_size().ok())
+            .unwrap_or(FALLBACK_WINDOW_SIZE);
+        let bounds = self.monitor_bounds();
+        let position = match (read_with_retry(|| window.outer_position()), bounds) {
+            (Some(position), Some(bounds)) => {
+                Self::position_on_display(position, size, Some(bounds), bounds, scale_factor)
+            }
+            (Some(position), None) => position,
+            (None, Some(bounds)) => PhysicalPosition::new(
+                (bounds.origin.x * scale_factor).round() as i32,
+                ((bounds.origin.y + 100.0) * scale_factor).round() as i32,
+            ),
+            (None, None) => PhysicalPosition::new(0, 100),
+        };
+
+        self.original_position = Some(position);
+        self.window_size = Some(size);
         Ok(())
     }
 
+    /// The edge the sidebar fits best on the current display (see `edge_for_display`)
+    pub fn preferred_edge(
+        &self,
+        window: &Window,
+        current: ScreenEdge,
+    ) -> Result<ScreenEdge, String> {
+        let bounds = self.monitor_bounds().ok_or("No monitor found")?;
+        let scale_factor = window.scale_factor().map_err(|e| e.to_string())?;
+
+        // While hidden the window sits at the edge, so use the cached position if any
+        let (position, size) = match (self.original_position, self.window_size) {
+            (Some(position), Some(size)) => (position, size),
+            _ => (
+                window.outer_position().map_err(|e| e.to_string())?,
+                window.outer_size().map_err(|e| e.to_string())?,
+            ),
+        };
+        let center = CGPoint::new(
+            (position.x as f64 + size.width as f64 / 2.0) / scale_factor,
+            (position.y as f64 + size.height as f64 / 2.0) / scale_factor,
+        );
+        Ok(edge_for_display(&bounds, &center, current))
+    }
+
     /// Calculate hidden position for the window
-    fn calculate_hidden_position(
+    ///
+    /// The display bounds (points) are converted with `scale_factor`, as window positions
+    /// are in physical pixels.
+    pub fn calculate_hidden_position(
         &self,
         config: &AutohideConfig,
+        scale_factor: f64,
     ) -> Result<PhysicalPosition<i32>, String> {
         let size = self.window_size.ok_or("Failed to get window size")?;
         let monitor = self.monitor_bounds.as_ref().ok_or("No monitor found")?;
-        let bounds = &monitor.bounds;
+        let edges = PhysicalEdges::of(monitor, scale_factor);
+        let (x, y) = self.original_offsets(monitor, scale_factor);
+        let handle = config.visible_pixels as i32;
 
         let hidden_pos = match config.edge {
-            ScreenEdge::Left => PhysicalPosition::new(
-                bounds.origin.x as i32 - size.width as i32 + config.visible_pixels as i32,
-                self.original_position.map(|p| p.y).unwrap_or(100),
-            ),
-            ScreenEdge::Right => PhysicalPosition::new(
-                (bounds.origin.x + bounds.size.width) as i32 - config.visible_pixels as i32,
-                self.original_position.map(|p| p.y).unwrap_or(100),
-            ),
+            ScreenEdge::Left => PhysicalPosition::new(edges.left - size.width as i32 + handle, y),
+            ScreenEdge::Right => PhysicalPosition::new(edges.right - handle, y),
+            ScreenEdge::Top => {
+                PhysicalPosition::new(x, edges.usable_top - size.height as i32 + handle)
+            }
+            ScreenEdge::Bottom => PhysicalPosition::new(x, edges.bottom - handle),
         };
 
         Ok(hidden_pos)
     }
 
     /// Calculate visible position for the window (snapped to edge)
-    fn calculate_visible_position(
+    pub fn calculate_visible_position(
         &self,
         config: &AutohideConfig,
+        scale_factor: f64,
     ) -> Result<PhysicalPosition<i32>, String> {
         let monitor = self.monitor_bounds.as_ref().ok_or("No monitor found")?;
-        let bounds = &monitor.bounds;
         let size = self.window_size.ok_or("Window size not cached")?;
+        let edges = PhysicalEdges::of(monitor, scale_factor);
+        let (x, y) = self.original_offsets(monitor, scale_factor);
 
         let visible_pos = match config.edge {
-            ScreenEdge::Left => PhysicalPosition::new(
-                bounds.origin.x as i32,
-                self.original_position.map(|p| p.y).unwrap_or(100),
-            ),
-            ScreenEdge::Right => PhysicalPosition::new(
-                (bounds.origin.x + bounds.size.width) as i32 - size.width as i32,
-                self.original_position.map(|p| p.y).unwrap_or(100),
+            ScreenEdge::Left => PhysicalPosition::new(edges.left, y),
+            ScreenEdge::Right => PhysicalPosition::new(edges.right - size.width as i32, y),
+            ScreenEdge::Top => PhysicalPosition::new(x, edges.usable_top),
+            ScreenEdge::Bottom => PhysicalPosition::new(
+                x,
+                (edges.bottom - size.height as i32).max(edges.usable_top),
             ),
         };
 
         Ok(visible_pos)
     }
 
-    /// Move window to hidden position
-    pub fn hide_window(
+    /// Original x and y of the window, kept along the edge it slides out from
+    /// (y is kept below the menu bar and notch)
+    fn original_offsets(&self, monitor: &MonitorBounds, scale_factor: f64) -> (i32, i32) {
+        let edges = PhysicalEdges::of(monitor, scale_factor);
+        let (x, y) = match self.original_position {
+            Some(position) => (position.x, position.y),
+            None => (edges.left, 100),
+        };
+        (x, y.max(edges.usable_top))
+    }
+
+    /// Original x and y of the window in points, for the rectangles the event tap tests
+    /// the cursor against (y is kept below the menu bar and notch)
+    fn original_origin(&self, monitor: &MonitorBounds, scale_factor: f64) -> (f64, f64) {
+        let (x, y) = match self.original_position {
+            Some(position) => (
+                position.x as f64 / scale_factor,
+                position.y as f64 / scale_factor,
+            ),
+            None => (monitor.bounds.origin.x, Self::usable_top(monitor)),
+        };
+        (x, y.max(Self::usable_top(monitor)))
+    }
+
+    /// Calculate the handle rectangle (the part left on screen while hidden)
+    /// in global display coordinates, for hit testing
+    pub fn calculate_handle_rect(
         &self,
-        window: &Window,
         config: &AutohideConfig,
-    ) -> Result<(), String> {
-        let hidden_pos = self.calculate_hidden_position(config)?;
-        window
-            .set_position(tauri::Position::Physical(hidden_pos))
-            .map_err(|e| e.to_string())
+        scale_factor: f64,
+    ) -> Result<CGRect, String> {
+        let monitor = self.monitor_bounds.as_ref().ok_or("No monitor found")?;
+        let bounds = &monitor.bounds;
+        let size = self.window_size.ok_or("Window size not cached")?;
+        let (x, y) = self.original_origin(monitor, scale_factor);
+        let (width, height) = (
+            size.width as f64 / scale_factor,
+            size.height as f64 / scale_factor,
+        );
+
+        let handle_size = config.visible_pixels / scale_factor;
+        let (origin, size) = match config.edge {
+            ScreenEdge::Left => (
+                CGPoint::new(bounds.origin.x, y),
+                CGSize::new(handle_size, height),
+            ),
+            ScreenEdge::Right => (
+                CGPoint::new(bounds.origin.x + bounds.size.width - handle_size, y),
+                CGSize::new(handle_size, height),
+            ),
+            ScreenEdge::Top => (
+                CGPoint::new(x, Self::usable_top(monitor)),
+                CGSize::new(width, handle_size),
+            ),
+            ScreenEdge::Bottom => (
+                CGPoint::new(x, bounds.origin.y + bounds.size.height - handle_size),
+                CGSize::new(width, handle_size),
+            ),
+        };
+
+        Ok(CGRect::new(&origin, &size))
+    }
+
+    /// Calculate the rectangle of the shown window in global display coordinates,
+    /// for detecting the cursor leaving it
+    pub fn calculate_window_rect(
+        &self,
+        config: &AutohideConfig,
+        scale_factor: f64,
+    ) -> Result<CGRect, String> {
+        let monitor = self.monitor_bounds.as_ref().ok_or("No monitor found")?;
+        let bounds = &monitor.bounds;
+        let size = self.window_size.ok_or("Window size not cached")?;
+        let (x, y) = self.original_origin(monitor, scale_factor);
+
+        let width = size.width as f64 / scale_factor;
+        let height = size.height as f64 / scale_factor;
+        let origin = match config.edge {
+            ScreenEdge::Left => CGPoint::new(bounds.origin.x, y),
+            ScreenEdge::Right => CGPoint::new(bounds.origin.x + bounds.size.width - width, y),
+            ScreenEdge::Top => CGPoint::new(x, Self::usable_top(monitor)),
+            ScreenEdge::Bottom => CGPoint::new(x, bounds.origin.y + bounds.size.height - height),
+        };
+
+        Ok(CGRect::new(&origin, &CGSize::new(width, height)))
+    }
+
+    /// Calculate the hover-reveal trigger rectangle in global display coordinates
+    ///
+    /// A thin strip along the docked edge, spanning the whole edge or, with
+    /// `trigger_on_handle_only`, just the handle's range plus a small margin. On the top
+    /// edge the strip lies just below the menu bar. Returns None when hover-reveal is off.
+    pub fn calculate_trigger_rect(
+        &self,
+        config: &AutohideConfig,
+        scale_factor: f64,
+    ) -> Result<Option<CGRect>, String> {
+        if !config.hover_reveal {
+            return Ok(None);
+        }
+
+        let monitor = self.monitor_bounds.as_ref().ok_or("No monitor found")?;
+        let bounds = &monitor.bounds;
+
+        // Range along the edge: (start, end) on the y axis for left/right, x for top/bottom
+        let (edge_start, edge_end) = if config.edge.is_horizontal() {
+            (bounds.origin.x, bounds.origin.x + bounds.size.width)
+        } else {
+            (bounds.origin.y, bounds.origin.y + bounds.size.height)
+        };
+        let (start, end) = if config.trigger_on_handle_only {
+            let handle = self.calculate_handle_rect(config, scale_factor)?;
+            let (handle_start, handle_length) = if config.edge.is_horizontal() {
+                (handle.origin.x, handle.size.width)
+            } else {
+                (handle.origin.y, handle.size.height)
+            };
+            (
+                (handle_start - HANDLE_TRIGGER_MARGIN).max(edge_start),
+                (handle_start + handle_length + HANDLE_TRIGGER_MARGIN).min(edge_end),
+            )
+        } else {
+            (edge_start, edge_end)
+        };
+        let length = (end - start).max(0.0);
+        let zone = config.trigger_zone_pixels;
+
+        let rect = match config.edge {
+            ScreenEdge::Left => CGRect::new(
+                &CGPoint::new(bounds.origin.x, start),
+                &CGSize::new(zone, length),
+            ),
+            ScreenEdge::Right => CGRect::new(
+                &CGPoint::new(bounds.origin.x + bounds.size.width - zone, start),
+                &CGSize::new(zone, length),
+            ),
+            ScreenEdge::Top => CGRect::new(
+                &CGPoint::new(start, Self::usable_top(monitor)),
+                &CGSize::new(length, zone),
+            ),
+            ScreenEdge::Bottom => CGRect::new(
+                &CGPoint::new(start, bounds.origin.y + bounds.size.height - zone),
+                &CGSize::new(length, zone),
+            ),
+        };
+
+        Ok(Some(rect))
     }
 
-    /// Move window to visible position
-    pub fn show_window(
+    /// Bring the window in front of other apps before it slides in
+    /// (it may have ended up behind them while hidden)
+    pub fn bring_to_front(
         &self,
         window: &Window,
         config: &AutohideConfig,
     ) -> Result<(), String> {
-        let visible_pos = self.calculate_visible_position(config)?;
-        window
-            .set_position(tauri::Position::Physical(visible_pos))
-            .map_err(|e| e.to_string())
+        if config.steal_focus_on_show {
+            window.show().map_err(|e| e.to_string())?;
+            window.set_focus().map_err(|e| e.to_string())
+        } else {
+            focus::order_front_without_focus(window)
+        }
     }
 
-    /// Restore window to original position
-    pub fn restore_original_position(&mut self, window: &Window) -> Result<(), String> {
-        if let Some(pos) = self.original_position {
-            window
-                .set_position(tauri::Position::Physical(pos))
-                .map_err(|e| e.to_string())?;
+    /// Forget the cached state and return the original position to slide back to
+    ///
+    /// If the position is no longer fully on screen (the display was disconnected or its
+    /// resolution changed since it was cached), it is pulled into the nearest display.
+    /// Calling this again while the restore is still animating returns the same target.
+    pub fn begin_restore(&mut self, scale_factor: f64) -> Option<PhysicalPosition<i32>> {
+        if let (Some(position), Some(size)) = (self.original_position.take(), self.window_size.take()) {
+            let position =
+                clamp_into_displays(position, size, &active_display_bounds(), scale_factor);
+            self.restoring = Some((position, size));
         }
-        self.original_position = None;
-        self.window_size = None;
-        Ok(())
+        self.restoring.map(|(position, _)| position)
+    }
+
+    /// Re-cache the state of an interrupted restore (autohide re-enabled mid-animation)
+    /// Returns false if there was no restore to resume
+    pub fn resume_interrupted_restore(&mut self) -> bool {
+        match self.restoring.take() {
+            Some((position, size)) => {
+                self.original_position = Some(position);
+                self.window_size = Some(size);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Edges of a display in physical pixels (the unit of window positions)
+struct PhysicalEdges {
+    left: i32,
+    right: i32,
+    bottom: i32,
+    /// Below the menu bar and notch
+    usable_top: i32,
+}
+
+impl PhysicalEdges {
+    fn of(monitor: &MonitorBounds, scale_factor: f64) -> Self {
+        let bounds = &monitor.bounds;
+        let to_physical = |points: f64| (points * scale_factor).round() as i32;
+        Self {
+            left: to_physical(bounds.origin.x),
+            right: to_physical(bounds.origin.x + bounds.size.width),
+            bottom: to_physical(bounds.origin.y + bounds.size.height),
+            usable_top: to_physical(WindowController::usable_top(monitor)),
+        }
+    }
+}
+
+/// Bounds and usable top of a display
+fn monitor_for(display: CGDisplay) -> MonitorBounds {
+    MonitorBounds {
+        bounds: display.bounds(),
+        top_inset: screen::top_inset(display.id).unwrap_or(MENU_BAR_HEIGHT),
+    }
+}
+
+/// The display containing `point`, or the one nearest to it
+fn display_for_point(point: &CGPoint, displays: &[(u32, CGRect)]) -> Option<u32> {
+    displays
+        .iter()
+        .min_by(|(_, a), (_, b)| distance_to_rect(point, a).total_cmp(&distance_to_rect(point, b)))
+        .map(|(id, _)| *id)
+}
+
+/// Squared distance from a point to a rectangle (0 inside it)
+fn distance_to_rect(point: &CGPoint, rect: &CGRect) -> f64 {
+    let dx = (rect.origin.x - point.x)
+        .max(point.x - (rect.origin.x + rect.size.width))
+        .max(0.0);
+    let dy = (rect.origin.y - point.y)
+        .max(point.y - (rect.origin.y + rect.size.height))
+        .max(0.0);
+    dx * dx + dy * dy
+}
+
+/// Bounds of all active displays (empty if they cannot be listed)
+fn active_display_bounds() -> Vec<CGRect> {
+    CGDisplay::active_displays()
+        .map(|ids| ids.into_iter().map(|id| CGDisplay::new(id).bounds()).collect())
+        .unwrap_or_default()
+}
+
+/// The edge a window centered at `center` (points) fits best on a display
+///
+/// Landscape displays dock the sidebar on the left or right and portrait displays on
+/// the top or bottom, so it runs along the longer side. Of the two, the one nearest to
+/// the window is chosen, leaving the larger part of the display free. On very long
+/// displays the window is compared with the middle third instead of the center, so a
+/// window near the middle keeps `current` (if it is one of the two).
+fn edge_for_display(bounds: &CGRect, center: &CGPoint, current: ScreenEdge) -> ScreenEdge {
+    let portrait = bounds.size.height > bounds.size.width;
+    let (offset, aspect_ratio, near, far) = if portrait {
+        (
+            (center.y - bounds.origin.y) / bounds.size.height,
+            bounds.size.height / bounds.size.width,
+            ScreenEdge::Top,
+            ScreenEdge::Bottom,
+        )
+    } else {
+        (
+            (center.x - bounds.origin.x) / bounds.size.width,
+            bounds.size.width / bounds.size.height,
+            ScreenEdge::Left,
+            ScreenEdge::Right,
+        )
+    };
+
+    let undecided = if aspect_ratio >= ULTRA_WIDE_ASPECT_RATIO {
+        1.0 / 3.0..=2.0 / 3.0
+    } else {
+        0.5..=0.5
+    };
+
+    if undecided.contains(&offset) && (current == near || current == far) {
+        current
+    } else if offset <= 0.5 {
+        near
+    } else {
+        far
     }
 }
 
+/// Keep a window position that lies fully inside one of the displays, otherwise clamp
+/// it into the display nearest to the window's center
+/// (unchanged when no display is known)
+///
+/// The display bounds are in points, so the window is converted with `scale_factor`
+/// before comparing and the clamped position is converted back to physical pixels.
+fn clamp_into_displays(
+    position: PhysicalPosition<i32>,
+    size: PhysicalSize<u32>,
+    displays: &[CGRect],
+    scale_factor: f64,
+) -> PhysicalPosition<i32> {
+    let origin = CGPoint::new(
+        position.x as f64 / scale_factor,
+        position.y as f64 / scale_factor,
+    );
+    let width = size.width as f64 / scale_factor;
+    let height = size.height as f64 / scale_factor;
+    let fits = |bounds: &CGRect| {
+        origin.x >= bounds.origin.x
+            && origin.y >= bounds.origin.y
+            && origin.x + width <= bounds.origin.x + bounds.size.width
+            && origin.y + height <= bounds.origin.y + bounds.size.height
+    };
+    if displays.iter().any(fits) {
+        return position;
+    }
+
+    let center = CGPoint::new(origin.x + width / 2.0, origin.y + height / 2.0);
+    match displays
+        .iter()
+        .min_by(|a, b| distance_to_rect(&center, a).total_cmp(&distance_to_rect(&center, b)))
+    {
+        Some(bounds) => WindowController::position_on_display(
+            position,
+            size,
+            Some(*bounds),
+            *bounds,
+            scale_factor,
+        ),
+        None => position,
+    }
+}
+
+/// Call a fallible window getter a few times, waiting briefly between attempts
+fn read_with_retry<T, E>(read: impl Fn() -> Result<T, E>) -> Option<T> {
+    for attempt in 0..GEOMETRY_READ_ATTEMPTS {
+        if attempt > 0 {
+            thread::sleep(GEOMETRY_RETRY_DELAY);
+        }
+        if let Ok(value) = read() {
+            return Some(value);
+        }
+    }
+    None
+}
+
+/// List the active displays
+pub fn list_displays() -> Result<Vec<DisplayInfo>, String> {
+    let ids = CGDisplay::active_displays()
+        .map_err(|e| format!("Failed to list displays (CGError {})", e))?;
+
+    Ok(ids
+        .into_iter()
+        .map(|id| {
+            let display = CGDisplay::new(id);
+            let bounds = display.bounds();
+            DisplayInfo {
+                id,
+                x: bounds.origin.x,
+                y: bounds.origin.y,
+                width: bounds.size.width,
+                height: bounds.size.height,
+                is_main: display.is_main(),
+            }
+        })
+        .collect())
+}
+
 impl Default for WindowController {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: f64, y: f64, width: f64, height: f64) -> CGRect {
+        CGRect::new(&CGPoint::new(x, y), &CGSize::new(width, height))
+    }
+
+    const SIZE: PhysicalSize<u32> = PhysicalSize {
+        width: 400,
+        height: 800,
+    };
+
+    #[test]
+    fn restore_keeps_a_position_that_is_on_screen() {
+        let displays = [rect(0.0, 0.0, 1920.0, 1080.0), rect(-1440.0, 0.0, 1440.0, 900.0)];
+        let position = PhysicalPosition::new(-1200, 50);
+        assert_eq!(
+            clamp_into_displays(position, SIZE, &displays, 1.0),
+            position
+        );
+    }
+
+    #[test]
+    fn restore_after_disconnecting_the_display_moves_onto_a_remaining_one() {
+        // The window was on a display left of the main one, which is gone now
+        let displays = [rect(0.0, 0.0, 1920.0, 1080.0)];
+        let restored = clamp_into_displays(PhysicalPosition::new(-1200, 50), SIZE, &displays, 1.0);
+        assert_eq!(restored, PhysicalPosition::new(0, 50));
+    }
+
+    #[test]
+    fn restore_after_a_resolution_change_stays_inside_the_smaller_display() {
+        // Cached on 2560x1440, restored after switching to 1440x900
+        let displays = [rect(0.0, 0.0, 1440.0, 900.0)];
+        let restored = clamp_into_displays(PhysicalPosition::new(2100, 600), SIZE, &displays, 1.0);
+        assert_eq!(restored, PhysicalPosition::new(1040, 100));
+    }
+
+    #[test]
+    fn restore_without_known_displays_keeps_the_position() {
+        let position = PhysicalPosition::new(-5000, -5000);
+        assert_eq!(clamp_into_displays(position, SIZE, &[], 1.0), position);
+    }
+
+    #[test]
+    fn restore_on_a_retina_display_compares_in_points() {
+        // 2000px is 1000pt, so the window lies on the right half of the 1512pt display
+        let displays = [rect(0.0, 0.0, 1512.0, 982.0)];
+        let position = PhysicalPosition::new(2000, 200);
+        assert_eq!(
+            clamp_into_displays(position, SIZE, &displays, 2.0),
+            position
+        );
+    }
+
+    #[test]
+    fn restore_on_a_retina_display_clamps_in_physical_pixels() {
+        // 1450pt + 200pt runs past the right edge, so it is pulled back to 1312pt
+        let displays = [rect(0.0, 0.0, 1512.0, 982.0)];
+        let restored = clamp_into_displays(PhysicalPosition::new(2900, 200), SIZE, &displays, 2.0);
+        assert_eq!(restored, PhysicalPosition::new(2624, 200));
+    }
+
+    /// A 1512x982 display whose usable area starts `top_inset` below its origin
+    fn controller_on_display(top_inset: f64, position: PhysicalPosition<i32>) -> WindowController {
+        WindowController {
+            monitor_bounds: Some(MonitorBounds {
+                bounds: rect(0.0, 0.0, 1512.0, 982.0),
+                top_inset,
+            }),
+            original_position: Some(position),
+            window_size: Some(SIZE),
+            display_id: None,
+            restoring: None,
+            edge_y: HashMap::new(),
+            unfitted: None,
+        }
+    }
+
+    fn config_for(edge: ScreenEdge) -> AutohideConfig {
+        AutohideConfig {
+            edge,
+            ..AutohideConfig::default()
+        }
+    }
+
+    #[test]
+    fn visible_position_starts_below_the_menu_bar_and_notch() {
+        // The notch pushes the usable top 38pt (76 pixels at 2x) below the display origin
+        let controller = controller_on_display(38.0, PhysicalPosition::new(0, 10));
+        for edge in [ScreenEdge::Left, ScreenEdge::Right, ScreenEdge::Top] {
+            let visible = controller
+                .calculate_visible_position(&config_for(edge), 2.0)
+                .unwrap();
+            assert_eq!(visible.y, 76, "{:?}", edge);
+        }
+    }
+
+    #[test]
+    fn hidden_position_slides_along_the_clamped_y() {
+        let controller = controller_on_display(38.0, PhysicalPosition::new(0, 10));
+        let hidden = controller
+            .calculate_hidden_position(&config_for(ScreenEdge::Left), 2.0)
+            .unwrap();
+        assert_eq!(hidden.y, 76);
+    }
+
+    #[test]
+    fn window_below_the_usable_top_keeps_its_y() {
+        // 120 pixels is 60pt at 2x, below the 38pt notch
+        let controller = controller_on_display(38.0, PhysicalPosition::new(0, 120));
+        let visible = controller
+            .calculate_visible_position(&config_for(ScreenEdge::Right), 2.0)
+            .unwrap();
+        assert_eq!(visible, PhysicalPosition::new(3024 - 400, 120));
+    }
+
+    #[test]
+    fn window_rect_starts_below_the_usable_top() {
+        let controller = controller_on_display(38.0, PhysicalPosition::new(0, 10));
+        let config = config_for(ScreenEdge::Left);
+        let rect = controller.calculate_window_rect(&config, 2.0).unwrap();
+        assert_eq!(rect.origin.y, 38.0);
+        // The rect and the window are at the same place
+        let visible = controller.calculate_visible_position(&config, 2.0).unwrap();
+        assert_eq!(visible.y as f64 / 2.0, rect.origin.y);
+    }
+
+    #[test]
+    fn partial_height_is_anchored_below_the_menu_bar_or_centered() {
+        let mut controller = controller_on_display(38.0, PhysicalPosition::new(0, 300));
+        let mut config = AutohideConfig {
+            height_fraction: 0.5,
+            ..config_for(ScreenEdge::Left)
+        };
+
+        // Half of the 944pt below the notch, at 2x
+        let size = controller.fit_height(&config, 2.0).unwrap();
+        assert_eq!(size.height, 944);
+        assert_eq!(controller.original_position.unwrap().y, 76);
+
+        // 38pt + (944pt - 472pt) / 2 = 274pt
+        config.vertical_anchor = VerticalAnchor::Center;
+        assert_eq!(controller.fit_height(&config, 2.0), None);
+        assert_eq!(controller.original_position.unwrap().y, 548);
+        let hidden = controller.calculate_hidden_position(&config, 2.0).unwrap();
+        assert_eq!(hidden.y, 548);
+    }
+
+    #[test]
+    fn full_height_after_a_partial_one_restores_the_window_geometry() {
+        let mut controller = controller_on_display(38.0, PhysicalPosition::new(0, 300));
+        let partial = AutohideConfig {
+            height_fraction: 0.5,
+            ..config_for(ScreenEdge::Left)
+        };
+        controller.fit_height(&partial, 2.0);
+        controller.fit_height(&partial, 2.0);
+
+        let restored = controller.fit_height(&config_for(ScreenEdge::Left), 2.0);
+        assert_eq!(restored, Some(SIZE));
+        assert_eq!(controller.original_position.unwrap().y, 300);
+    }
+
+    #[test]
+    fn full_height_keeps_the_window_geometry() {
+        let mut controller = controller_on_display(38.0, PhysicalPosition::new(0, 300));
+        assert_eq!(
+            controller.fit_height(&config_for(ScreenEdge::Left), 2.0),
+            None
+        );
+        assert_eq!(controller.original_position.unwrap().y, 300);
+        assert_eq!(controller.window_size, Some(SIZE));
+    }
+
+    #[test]
+    fn right_edge_positions_follow_a_new_width() {
+        let mut controller = controller_on_display(38.0, PhysicalPosition::new(0, 120));
+        let config = config_for(ScreenEdge::Right);
+        controller.set_window_width(600);
+        let visible = controller.calculate_visible_position(&config, 1.0).unwrap();
+        assert_eq!(visible.x, 1512 - 600);
+        let hidden = controller.calculate_hidden_position(&config, 1.0).unwrap();
+        assert_eq!(hidden.x, 1512 - config.visible_pixels as i32);
+    }
+
+    #[test]
+    fn landscape_displays_pick_the_nearer_side() {
+        let display = rect(0.0, 0.0, 1920.0, 1080.0);
+        let edge = |x, current| edge_for_display(&display, &CGPoint::new(x, 540.0), current);
+        assert_eq!(edge(300.0, ScreenEdge::Right), ScreenEdge::Left);
+        assert_eq!(edge(1600.0, ScreenEdge::Left), ScreenEdge::Right);
+    }
+
+    #[test]
+    fn portrait_displays_pick_the_top_or_bottom() {
+        let display = rect(-1080.0, 0.0, 1080.0, 1920.0);
+        let edge = |y, current| edge_for_display(&display, &CGPoint::new(-540.0, y), current);
+        assert_eq!(edge(500.0, ScreenEdge::Left), ScreenEdge::Top);
+        assert_eq!(edge(1500.0, ScreenEdge::Right), ScreenEdge::Bottom);
+        // A window in the middle keeps the current edge only if it is the top or bottom
+        assert_eq!(edge(960.0, ScreenEdge::Bottom), ScreenEdge::Bottom);
+        assert_eq!(edge(960.0, ScreenEdge::Left), ScreenEdge::Top);
+    }
+
+    #[test]
+    fn ultra_wide_displays_keep_the_edge_for_a_window_near_the_middle() {
+        let display = rect(0.0, 0.0, 3440.0, 1440.0);
+        let center = CGPoint::new(1500.0, 720.0);
+        assert_eq!(
+            edge_for_display(&display, &center, ScreenEdge::Right),
+            ScreenEdge::Right
+        );
+    }
+
+    #[test]
+    fn switching_edges_restores_the_y_last_used_on_each_edge() {
+        let mut controller = controller_on_display(25.0, PhysicalPosition::new(0, 300));
+
+        // The first switch to an edge keeps the current y
+        let saved = controller.switch_edge(ScreenEdge::Left, ScreenEdge::Right);
+        assert_eq!(saved.get(&ScreenEdge::Left), Some(&300));
+        assert_eq!(controller.original_position.unwrap().y, 300);
+
+        controller.original_position = Some(PhysicalPosition::new(0, 500));
+        controller.switch_edge(ScreenEdge::Right, ScreenEdge::Left);
+        assert_eq!(controller.original_position.unwrap().y, 300);
+
+        controller.switch_edge(ScreenEdge::Left, ScreenEdge::Right);
+        assert_eq!(controller.original_position.unwrap().y, 500);
+    }
+}
@@ -0,0 +1,62 @@
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+use std::time::Duration;
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// Runs autohide work that is not started by a command (event tap, window and display
+/// events) one job at a time on a background thread
+///
+/// The manager calls window getters, which wait for the main thread to answer, so its
+/// lock must never be waited for on the main thread. Handing the work to this thread
+/// also keeps the event tap callback short, so macOS does not disable the tap.
+pub struct AutohideWorker {
+    sender: Sender<Job>,
+}
+
+impl AutohideWorker {
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let spawned = thread::Builder::new()
+            .name("autohide-worker".to_string())
+            .spawn(move || {
+                for job in receiver {
+                    job();
+                }
+            });
+        if let Err(e) = spawned {
+            eprintln!("[autohide] Failed to start the worker thread: {}", e);
+        }
+        Self { sender }
+    }
+
+    /// Queue `job` to run after the jobs queued before it
+    pub fn post<F>(&self, job: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        if self.sender.send(Box::new(job)).is_err() {
+            eprintln!("[autohide] The worker thread is not running");
+        }
+    }
+
+    /// Queue `job` once `delay` has passed (nothing is locked while waiting)
+    pub fn post_after<F>(&self, delay: Duration, job: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            thread::sleep(delay);
+            if sender.send(Box::new(job)).is_err() {
+                eprintln!("[autohide] The worker thread is not running");
+            }
+        });
+    }
+}
+
+impl Default for AutohideWorker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
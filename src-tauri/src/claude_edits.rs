@@ -0,0 +1,298 @@
+use crate::claude_export;
+use crate::claude_logs::{self, ClaudeLogEntry};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Input keys that hold the target path, across tool versions and MCP tools
+const PATH_KEYS: &[&str] = &[
+    "file_path",
+    "filePath",
+    "notebook_path",
+    "path",
+    "target_file",
+    "filename",
+];
+
+/// Files a session changed, grouped by the last operation on each file
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SessionEditedFiles {
+    /// Files written that had not been read or edited earlier in the session
+    pub created: Vec<String>,
+    pub edited: Vec<String>,
+    /// Files removed with `rm` / `git rm` in a Bash call
+    pub deleted: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileOperation {
+    Created,
+    Edited,
+    Deleted,
+}
+
+/// List the files a session created, edited or deleted
+///
+/// Collected from the file-editing tool calls (Write, Edit, MultiEdit, NotebookEdit, ...)
+/// and `rm` in Bash commands; calls whose result was an error are skipped. Each file
+/// appears once, under its last operation (a file created and then edited stays
+/// "created"), in the order it was first touched. Relative paths are resolved against
+/// the session's cwd.
+#[tauri::command]
+pub fn get_session_edited_files(
+    project_path: String,
+    session_id: String,
+) -> Result<SessionEditedFiles, String> {
+    let session_file = claude_export::main_session_file(&project_path, &session_id)?;
+    let content = claude_logs::read_session_content(&session_file)?;
+    let entries: Vec<ClaudeLogEntry> = content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+
+    let failed = failed_tool_use_ids(&entries);
+    let mut order: Vec<String> = Vec::new();
+    let mut operations: HashMap<String, FileOperation> = HashMap::new();
+    let mut seen: HashSet<String> = HashSet::new();
+
+    for entry in &entries {
+        let cwd = entry.cwd.as_deref();
+        for tool_use in tool_uses(entry) {
+            if tool_use.id.is_some_and(|id| failed.contains(id)) {
+                continue;
+            }
+            for (path, operation) in file_operations(&tool_use, cwd) {
+                let previous = operations.get(&path).copied();
+                let operation = merge_operation(operation, previous, seen.contains(&path));
+                if previous.is_none() {
+                    order.push(path.clone());
+                }
+                operations.insert(path, operation);
+            }
+            // Files that were read exist already, so a later Write edits them
+            if tool_use.name == "Read" {
+                if let Some(path) = input_path(tool_use.input, cwd) {
+                    seen.insert(path);
+                }
+            }
+        }
+    }
+
+    let mut files = SessionEditedFiles::default();
+    for path in order {
+        match operations[&path] {
+            FileOperation::Created => files.created.push(path),
+            FileOperation::Edited => files.edited.push(path),
+            FileOperation::Deleted => files.deleted.push(path),
+        }
+    }
+    Ok(files)
+}
+
+/// How a file is classified after `operation`, given its earlier operation in the
+/// session and whether it was read before
+fn merge_operation(
+    operation: FileOperation,
+    previous: Option<FileOperation>,
+    read_before: bool,
+) -> FileOperation {
+    match (operation, previous) {
+        // Editing a file created in this session keeps it "created"
+        (FileOperation::Edited, Some(FileOperation::Created)) => FileOperation::Created,
+        // Writing over a file that was already edited (so it existed) is an edit
+        (FileOperation::Created, Some(FileOperation::Edited)) => FileOperation::Edited,
+        // Writing a file that was read first is an edit; one never read is new
+        (FileOperation::Created, None) if read_before => FileOperation::Edited,
+        (operation, _) => operation,
+    }
+}
+
+struct ToolUse<'a> {
+    id: Option<&'a str>,
+    name: &'a str,
+    input: &'a serde_json::Value,
+}
+
+fn tool_uses(entry: &ClaudeLogEntry) -> Vec<ToolUse<'_>> {
+    let Some(serde_json::Value::Array(blocks)) =
+        entry.message.as_ref().and_then(|m| m.content.as_ref())
+    else {
+        return Vec::new();
+    };
+    blocks
+        .iter()
+        .filter(|block| block.get("type").and_then(|t| t.as_str()) == Some("tool_use"))
+        .filter_map(|block| {
+            Some(ToolUse {
+                id: block.get("id").and_then(|id| id.as_str()),
+                name: block.get("name").and_then(|n| n.as_str())?,
+                input: block.get("input")?,
+            })
+        })
+        .collect()
+}
+
+/// IDs of the tool calls whose result was an error
+fn failed_tool_use_ids(entries: &[ClaudeLogEntry]) -> HashSet<&str> {
+    entries
+        .iter()
+        .filter_map(|entry| match entry.message.as_ref()?.content.as_ref()? {
+            serde_json::Value::Array(blocks) => Some(blocks),
+            _ => None,
+        })
+        .flatten()
+        .filter(|block| {
+            block.get("type").and_then(|t| t.as_str()) == Some("tool_result")
+                && block.get("is_error").and_then(|e| e.as_bool()) == Some(true)
+        })
+        .filter_map(|block| block.get("tool_use_id").and_then(|id| id.as_str()))
+        .collect()
+}
+
+/// The files a tool call changes and how
+fn file_operations(tool_use: &ToolUse, cwd: Option<&str>) -> Vec<(String, FileOperation)> {
+    let name = tool_use.name.to_lowercase();
+    let input = tool_use.input;
+
+    if name == "bash" {
+        let command = input
+            .get("command")
+            .and_then(|c| c.as_str())
+            .unwrap_or_default();
+        return removed_paths(command)
+            .into_iter()
+            .map(|path| (resolve(&path, cwd), FileOperation::Deleted))
+            .collect();
+    }
+
+    let operation =
+        if name == "write" || name.ends_with("write_file") || name.ends_with("create_file") {
+            FileOperation::Created
+        } else if name.contains("edit") || name.contains("str_replace") {
+            // The text editor tool creates files through its "create" command
+            match input.get("command").and_then(|c| c.as_str()) {
+                Some("create") => FileOperation::Created,
+                Some("view") => return Vec::new(),
+                _ => FileOperation::Edited,
+            }
+        } else {
+            return Vec::new();
+        };
+
+    input_path(input, cwd)
+        .map(|path| vec![(path, operation)])
+        .unwrap_or_default()
+}
+
+/// The first path-like string among the known input keys
+fn input_path(input: &serde_json::Value, cwd: Option<&str>) -> Option<String> {
+    PATH_KEYS
+        .iter()
+        .filter_map(|key| input.get(*key).and_then(|v| v.as_str()))
+        .map(str::trim)
+        .find(|path| !path.is_empty())
+        .map(|path| resolve(path, cwd))
+}
+
+/// Arguments of `rm` / `git rm` in a shell command (options and globs are skipped)
+fn removed_paths(command: &str) -> Vec<String> {
+    command
+        .split(['\n', ';', '|', '&'])
+        .filter_map(|segment| {
+            let words: Vec<&str> = segment.split_whitespace().collect();
+            match words.as_slice() {
+                ["rm", args @ ..] | ["git", "rm", args @ ..] => Some(args.to_vec()),
+                _ => None,
+            }
+        })
+        .flatten()
+        .filter(|arg| !arg.starts_with('-') && !arg.contains(['*', '?', '$']))
+        .map(|arg| arg.trim_matches(['\'', '"']).to_string())
+        .filter(|arg| !arg.is_empty())
+        .collect()
+}
+
+fn resolve(path: &str, cwd: Option<&str>) -> String {
+    match cwd {
+        Some(cwd) if Path::new(path).is_relative() => {
+            Path::new(cwd).join(path).to_string_lossy().to_string()
+        }
+        _ => path.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use FileOperation::{Created, Deleted, Edited};
+
+    #[test]
+    fn write_of_an_untouched_file_is_a_creation() {
+        assert_eq!(merge_operation(Created, None, false), Created);
+    }
+
+    #[test]
+    fn write_of_a_file_read_first_is_an_edit() {
+        assert_eq!(merge_operation(Created, None, true), Edited);
+    }
+
+    #[test]
+    fn edits_of_a_created_file_keep_it_created() {
+        assert_eq!(merge_operation(Edited, Some(Created), false), Created);
+        assert_eq!(merge_operation(Edited, Some(Created), true), Created);
+    }
+
+    #[test]
+    fn write_over_an_edited_file_stays_an_edit() {
+        assert_eq!(merge_operation(Created, Some(Edited), false), Edited);
+    }
+
+    fn operations(name: &str, input: serde_json::Value) -> Vec<(String, FileOperation)> {
+        let tool_use = ToolUse {
+            id: None,
+            name,
+            input: &input,
+        };
+        file_operations(&tool_use, Some("/repo"))
+    }
+
+    #[test]
+    fn tools_are_classified_by_name_and_command() {
+        let path = serde_json::json!({ "file_path": "src/main.rs" });
+        assert_eq!(
+            operations("Write", path.clone()),
+            [("/repo/src/main.rs".to_string(), Created)]
+        );
+        assert_eq!(
+            operations("MultiEdit", path.clone()),
+            [("/repo/src/main.rs".to_string(), Edited)]
+        );
+        assert!(operations("Read", path).is_empty());
+
+        let create = serde_json::json!({ "command": "create", "path": "/tmp/a.txt" });
+        assert_eq!(
+            operations("str_replace_editor", create),
+            [("/tmp/a.txt".to_string(), Created)]
+        );
+        let view = serde_json::json!({ "command": "view", "path": "/tmp/a.txt" });
+        assert!(operations("str_replace_editor", view).is_empty());
+    }
+
+    #[test]
+    fn rm_arguments_are_deletions_without_options_or_globs() {
+        let command = serde_json::json!({ "command": "rm -f old.txt *.log && git rm 'b.txt'" });
+        assert_eq!(
+            operations("Bash", command),
+            [
+                ("/repo/old.txt".to_string(), Deleted),
+                ("/repo/b.txt".to_string(), Deleted),
+            ]
+        );
+    }
+
+    #[test]
+    fn write_after_a_deletion_creates_the_file_again() {
+        assert_eq!(merge_operation(Created, Some(Deleted), true), Created);
+        assert_eq!(merge_operation(Deleted, Some(Created), false), Deleted);
+    }
+}
@@ -0,0 +1,743 @@
+use crate::claude_logs::{self, ClaudeLogEntry, ConversationMessage, ImageBlock, MessageKind};
+use crate::claude_redact::{self, Redactor};
+use crate::fs_util::ensure_writable;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+/// File name of the persisted export settings (inside the app config dir)
+const EXPORT_SETTINGS_FILE_NAME: &str = "claude_export.json";
+
+/// Maximum length of the session title part of generated file names
+const MAX_TITLE_CHARS: usize = 40;
+
+/// Persisted export settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct ExportSettings {
+    /// Directory used when an export is requested without an output path
+    export_dir: Option<String>,
+}
+
+impl ExportSettings {
+    fn path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+        let config_dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+        Ok(config_dir.join(EXPORT_SETTINGS_FILE_NAME))
+    }
+
+    fn load(app: &tauri::AppHandle) -> Result<Self, String> {
+        let content = match fs::read_to_string(Self::path(app)?) {
+            Ok(content) => content,
+            Err(_) => return Ok(Self::default()),
+        };
+        serde_json::from_str(&content).map_err(|e| format!("Invalid export settings: {}", e))
+    }
+
+    fn save(&self, app: &tauri::AppHandle) -> Result<(), String> {
+        let path = Self::path(app)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, content).map_err(|e| e.to_string())
+    }
+}
+
+/// Set the default export directory (None to go back to the Downloads folder)
+/// The directory is created if missing and rejected if it is not writable.
+#[tauri::command]
+pub fn set_export_dir(app: tauri::AppHandle, path: Option<String>) -> Result<(), String> {
+    if let Some(ref path) = path {
+        ensure_writable(Path::new(path), "export dir")?;
+    }
+    let mut settings = ExportSettings::load(&app)?;
+    settings.export_dir = path;
+    settings.save(&app)
+}
+
+/// Get the directory exports go to when no output path is given
+#[tauri::command]
+pub fn get_export_dir(app: tauri::AppHandle) -> Result<String, String> {
+    Ok(default_export_dir(&app)?.to_string_lossy().to_string())
+}
+
+/// Result of `export_claude_session`
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionExport {
+    /// The written file
+    pub path: String,
+    /// How many matches were replaced with `[REDACTED]`
+    pub redacted_count: usize,
+    /// How many messages were left out as system text
+    pub excluded_count: usize,
+}
+
+/// Export a session as Markdown
+///
+/// If `output_path` is omitted, the file is written to the default export directory
+/// with a name generated from the session's date and title.
+///
+/// With `redact`, the export is cleaned up before sharing: matches of `redact_patterns`
+/// (regular expressions) are replaced with `[REDACTED]`, and unless turned off,
+/// strings that look like API keys or secrets are detected and replaced too
+/// (`detect_api_keys`) and the system text Claude Code adds to the conversation is
+/// left out (`exclude_system`).
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub fn export_claude_session(
+    app: tauri::AppHandle,
+    project_path: String,
+    session_id: String,
+    output_path: Option<String>,
+    redact: Option<bool>,
+    redact_patterns: Option<Vec<String>>,
+    exclude_system: Option<bool>,
+    detect_api_keys: Option<bool>,
+) -> Result<SessionExport, String> {
+    let redact = redact.unwrap_or(false);
+    let mut redactor = match redact {
+        true => Some(Redactor::new(
+            &redact_patterns.unwrap_or_default(),
+            detect_api_keys.unwrap_or(true),
+        )?),
+        false => None,
+    };
+
+    let messages = claude_logs::read_claude_session(
+        project_path.clone(),
+        session_id.clone(),
+        None,
+        None,
+        None,
+        None,
+        None,
+    )?;
+    let header = SessionHeader::load(&project_path, &session_id)?;
+    let output = output_file(&app, output_path, &header, "md")?;
+
+    let message_count = messages.len();
+    let messages: Vec<ConversationMessage> = if redact && exclude_system.unwrap_or(true) {
+        messages
+            .iter()
+            .filter_map(claude_redact::without_system_text)
+            .collect()
+    } else {
+        messages
+    };
+    let excluded_count = message_count - messages.len();
+
+    let mut markdown =
+        render_markdown(&header.title, &session_id, header.cwd.as_deref(), &messages);
+    if let Some(ref mut redactor) = redactor {
+        markdown = redactor.redact(&markdown);
+    }
+    fs::write(&output, markdown)
+        .map_err(|e| format!("Failed to write {}: {}", output.display(), e))?;
+
+    Ok(SessionExport {
+        path: output.to_string_lossy().to_string(),
+        redacted_count: redactor.map_or(0, |redactor| redactor.count()),
+        excluded_count,
+    })
+}
+
+/// Export a session as a self-contained HTML file
+///
+/// Styles are inlined and attached images are embedded as data URIs, so the file can
+/// be shared on its own. The page includes print styles, so it can also be turned into
+/// a PDF (e.g. by printing it from a webview). `output_path` works as in
+/// `export_claude_session`. Returns the written path.
+#[tauri::command]
+pub fn export_claude_session_html(
+    app: tauri::AppHandle,
+    project_path: String,
+    session_id: String,
+    output_path: Option<String>,
+) -> Result<String, String> {
+    let session_file = main_session_file(&project_path, &session_id)?;
+    let messages = html_messages(&claude_logs::read_session_content(&session_file)?);
+
+    let header = SessionHeader::load(&project_path, &session_id)?;
+    let output = output_file(&app, output_path, &header, "html")?;
+
+    let html = render_html(&header.title, &session_id, header.cwd.as_deref(), &messages);
+    fs::write(&output, html)
+        .map_err(|e| format!("Failed to write {}: {}", output.display(), e))?;
+
+    Ok(output.to_string_lossy().to_string())
+}
+
+/// Get a whole session as one plain text, e.g. to have another LLM summarize it
+///
+/// Messages are joined in order, each under a role label ("User:", "Assistant:").
+/// With `include_tool_output`, tool calls and tool results are included as "Tool:"
+/// parts. `max_chars_per_part` truncates each message or tool part to save tokens.
+#[tauri::command]
+pub fn get_claude_session_text(
+    project_path: String,
+    session_id: String,
+    include_tool_output: Option<bool>,
+    max_chars_per_part: Option<usize>,
+) -> Result<String, String> {
+    let session_file = main_session_file(&project_path, &session_id)?;
+    let content = claude_logs::read_session_content(&session_file)?;
+    let include_tool_output = include_tool_output.unwrap_or(false);
+
+    let mut parts: Vec<(&str, String)> = Vec::new();
+    for entry in content
+        .lines()
+        .filter_map(|line| serde_json::from_str::<ClaudeLogEntry>(line).ok())
+    {
+        for message in claude_logs::entry_messages(&entry, false) {
+            parts.push((role_label(&message), message.content));
+        }
+        if include_tool_output {
+            if let Some(ref msg) = entry.message {
+                parts.extend(tool_parts(&msg.content).into_iter().map(|part| ("Tool", part)));
+            }
+        }
+    }
+
+    let text = parts
+        .into_iter()
+        .map(|(label, part)| {
+            let part = part.trim();
+            let part = match max_chars_per_part {
+                Some(max_chars) => claude_logs::truncate_chars(part, max_chars),
+                None => part.to_string(),
+            };
+            format!("{}:\n{}", label, part)
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    Ok(text)
+}
+
+/// The session file of a main (non-sidechain) session
+pub(crate) fn main_session_file(project_path: &str, session_id: &str) -> Result<PathBuf, String> {
+    let session_file = claude_logs::get_claude_project_dir(project_path)?
+        .join(format!("{}.jsonl", session_id));
+    if !session_file.exists() {
+        return Err(format!("Session file not found: {:?}", session_file));
+    }
+    if claude_logs::is_sidechain_session(&session_file) {
+        return Err(format!("Session {} is a sidechain session and cannot be exported", session_id));
+    }
+    Ok(session_file)
+}
+
+/// Tool calls and tool results in message content, one text per block
+fn tool_parts(content: &Option<serde_json::Value>) -> Vec<String> {
+    let Some(serde_json::Value::Array(blocks)) = content else {
+        return Vec::new();
+    };
+
+    blocks
+        .iter()
+        .filter_map(|block| {
+            let obj = block.as_object()?;
+            match obj.get("type").and_then(|t| t.as_str())? {
+                "tool_use" => {
+                    let name = obj.get("name").and_then(|n| n.as_str()).unwrap_or("tool");
+                    let input = obj.get("input").map(|i| i.to_string()).unwrap_or_default();
+                    Some(format!("[{}] {}", name, input))
+                }
+                "tool_result" => {
+                    let output = match obj.get("content") {
+                        Some(serde_json::Value::String(s)) => s.clone(),
+                        Some(serde_json::Value::Array(items)) => items
+                            .iter()
+                            .filter_map(|item| item.get("text").and_then(|t| t.as_str()))
+                            .collect::<Vec<_>>()
+                            .join("\n"),
+                        _ => String::new(),
+                    };
+                    let is_error = obj.get("is_error").and_then(|e| e.as_bool()) == Some(true);
+                    let label = if is_error { "[result: error]" } else { "[result]" };
+                    Some(format!("{} {}", label, output))
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Title, start time and directory of a session, used for headings and file names
+struct SessionHeader {
+    title: String,
+    timestamp: Option<String>,
+    cwd: Option<String>,
+}
+
+impl SessionHeader {
+    fn load(project_path: &str, session_id: &str) -> Result<Self, String> {
+        let session_file = claude_logs::get_claude_project_dir(project_path)?
+            .join(format!("{}.jsonl", session_id));
+        let summary = claude_logs::summarize_session_file(&session_file, project_path)?;
+
+        Ok(Self {
+            title: summary
+                .as_ref()
+                .and_then(|s| s.first_message.clone())
+                .unwrap_or_else(|| session_id.to_string()),
+            timestamp: summary.as_ref().and_then(|s| s.timestamp.clone()),
+            cwd: summary.and_then(|s| s.cwd),
+        })
+    }
+}
+
+/// The given output path, or a new file in the default export directory
+fn output_file(
+    app: &tauri::AppHandle,
+    output_path: Option<String>,
+    header: &SessionHeader,
+    extension: &str,
+) -> Result<PathBuf, String> {
+    match output_path {
+        Some(path) => Ok(PathBuf::from(path)),
+        None => {
+            let dir = default_export_dir(app)?;
+            ensure_writable(&dir, "export dir")?;
+            let stem = export_file_stem(&header.title, header.timestamp.as_deref());
+            Ok(unique_path(&dir, &stem, extension))
+        }
+    }
+}
+
+/// Configured export directory, or the Downloads folder if none is set
+fn default_export_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    if let Some(dir) = ExportSettings::load(app)?.export_dir {
+        return Ok(PathBuf::from(dir));
+    }
+    dirs::download_dir()
+        .or_else(dirs::home_dir)
+        .ok_or_else(|| "Could not find a default export directory".to_string())
+}
+
+/// File name (without extension) like "2025-01-15_1830_Fix the login bug"
+fn export_file_stem(title: &str, timestamp: Option<&str>) -> String {
+    let date = timestamp
+        .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+        .map(|t| t.with_timezone(&chrono::Local))
+        .unwrap_or_else(chrono::Local::now)
+        .format("%Y-%m-%d_%H%M");
+
+    let title: String = title
+        .lines()
+        .next()
+        .unwrap_or("")
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .take(MAX_TITLE_CHARS)
+        .collect();
+    let title = title.trim().trim_start_matches('.');
+
+    if title.is_empty() {
+        date.to_string()
+    } else {
+        format!("{}_{}", date, title)
+    }
+}
+
+/// `<dir>/<stem>.<ext>`, with a numeric suffix if that file already exists
+fn unique_path(dir: &Path, stem: &str, extension: &str) -> PathBuf {
+    let mut path = dir.join(format!("{}.{}", stem, extension));
+    let mut n = 2;
+    while path.exists() {
+        path = dir.join(format!("{} ({}).{}", stem, n, extension));
+        n += 1;
+    }
+    path
+}
+
+fn render_markdown(
+    title: &str,
+    session_id: &str,
+    cwd: Option<&str>,
+    messages: &[ConversationMessage],
+) -> String {
+    let mut markdown = format!("# {}\n\n", title.lines().next().unwrap_or(title));
+    markdown.push_str(&format!("- Session: `{}`\n", session_id));
+    if let Some(cwd) = cwd {
+        markdown.push_str(&format!("- Directory: `{}`\n", cwd));
+    }
+    markdown.push('\n');
+
+    for message in messages {
+        let role = role_label(message);
+        match message.timestamp_local {
+            Some(ref time) => markdown.push_str(&format!("## {} ({})\n\n", role, time)),
+            None => markdown.push_str(&format!("## {}\n\n", role)),
+        }
+        markdown.push_str(message.content.trim_end());
+        markdown.push_str("\n\n");
+    }
+
+    markdown
+}
+
+/// Heading used for a message in exports
+fn role_label(message: &ConversationMessage) -> &'static str {
+    match (message.role.as_str(), message.kind) {
+        ("user", _) => "User",
+        (_, MessageKind::Thinking) => "Thinking",
+        _ => "Assistant",
+    }
+}
+
+/// Styles embedded in HTML exports (including print styles for PDF conversion)
+const HTML_STYLE: &str = r#"
+:root { color-scheme: light; }
+body { margin: 0; background: #f6f7f9; color: #1f2328;
+  font-family: -apple-system, BlinkMacSystemFont, "Hiragino Sans", "Segoe UI", sans-serif;
+  font-size: 15px; line-height: 1.6; }
+main { max-width: 860px; margin: 0 auto; padding: 32px 24px; }
+h1 { font-size: 22px; margin: 0 0 8px; }
+.meta { color: #59636e; font-size: 13px; margin: 0 0 24px; }
+.meta code { font-size: 12px; }
+.message { background: #fff; border: 1px solid #d1d9e0; border-left-width: 4px;
+  border-radius: 6px; margin: 0 0 16px; padding: 12px 16px; }
+.message.user { border-left-color: #0969da; }
+.message.assistant { border-left-color: #8250df; }
+.message.thinking { border-left-color: #9a6700; color: #59636e; font-style: italic; }
+.message-header { display: flex; justify-content: space-between; gap: 12px;
+  font-size: 13px; margin-bottom: 6px; }
+.role { font-weight: 600; }
+time { color: #59636e; }
+.message-body p { margin: 0 0 10px; white-space: pre-wrap; overflow-wrap: anywhere; }
+.message-body p:last-child { margin-bottom: 0; }
+code { font-family: ui-monospace, SFMono-Regular, Menlo, Consolas, monospace; font-size: 13px; }
+p code { background: #eff1f3; border-radius: 4px; padding: 1px 4px; }
+.code-block { margin: 0 0 10px; border-radius: 6px; overflow: hidden; background: #0d1117; }
+.code-block .language { display: block; padding: 4px 12px; background: #161b22;
+  color: #9198a1; font-size: 12px; }
+.code-block pre { margin: 0; padding: 12px; overflow-x: auto; color: #e6edf3; }
+.tok-keyword { color: #ff7b72; }
+.tok-string { color: #a5d6ff; }
+.tok-number { color: #79c0ff; }
+.tok-comment { color: #8b949e; font-style: italic; }
+.images { display: flex; flex-wrap: wrap; gap: 8px; margin-top: 8px; }
+.images img { max-width: 100%; max-height: 480px; border: 1px solid #d1d9e0; border-radius: 4px; }
+@page { margin: 16mm; }
+@media print {
+  body { background: #fff; }
+  main { max-width: none; padding: 0; }
+  .message { break-inside: avoid; }
+  .code-block pre { white-space: pre-wrap; overflow-wrap: anywhere; }
+}
+"#;
+
+/// A message together with the images attached to its log entry
+struct HtmlMessage {
+    message: ConversationMessage,
+    images: Vec<ImageBlock>,
+}
+
+/// Text messages of a session with their images
+/// Images are attached to the last message of their entry; an entry with only images
+/// becomes a message without text.
+fn html_messages(content: &str) -> Vec<HtmlMessage> {
+    let mut result = Vec::new();
+
+    for entry in content
+        .lines()
+        .filter_map(|line| serde_json::from_str::<ClaudeLogEntry>(line).ok())
+    {
+        let images = entry
+            .message
+            .as_ref()
+            .map(|msg| claude_logs::extract_images(&msg.content))
+            .unwrap_or_default();
+
+        let mut messages: Vec<HtmlMessage> = claude_logs::entry_messages(&entry, false)
+            .into_iter()
+            .map(|message| HtmlMessage {
+                message,
+                images: Vec::new(),
+            })
+            .collect();
+
+        if !images.is_empty() {
+            match messages.last_mut() {
+                Some(last) => last.images = images,
+                None => {
+                    if let Some(message) = image_only_message(&entry) {
+                        messages.push(HtmlMessage { message, images });
+                    }
+                }
+            }
+        }
+        result.extend(messages);
+    }
+
+    result
+}
+
+/// Empty message carrying the role and time of an entry that only has images
+fn image_only_message(entry: &ClaudeLogEntry) -> Option<ConversationMessage> {
+    let entry_type = entry.entry_type.as_deref()?;
+    if entry_type != "user" && entry_type != "assistant" {
+        return None;
+    }
+    let role = entry
+        .message
+        .as_ref()
+        .and_then(|msg| msg.role.clone())
+        .unwrap_or_else(|| entry_type.to_string());
+
+    Some(ConversationMessage {
+        role,
+        content: String::new(),
+        timestamp: entry.timestamp.clone().unwrap_or_default(),
+        timestamp_local: entry
+            .timestamp
+            .as_deref()
+            .and_then(claude_logs::format_local_timestamp),
+        kind: MessageKind::Text,
+        index: 0,
+    })
+}
+
+fn render_html(
+    title: &str,
+    session_id: &str,
+    cwd: Option<&str>,
+    messages: &[HtmlMessage],
+) -> String {
+    let title = escape_html(title.lines().next().unwrap_or(title));
+
+    let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    html.push_str(&format!("<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n<main>\n", title, HTML_STYLE));
+
+    html.push_str(&format!("<h1>{}</h1>\n<p class=\"meta\">Session <code>{}</code>", title, escape_html(session_id)));
+    if let Some(cwd) = cwd {
+        html.push_str(&format!(" · Directory <code>{}</code>", escape_html(cwd)));
+    }
+    html.push_str("</p>\n");
+
+    for HtmlMessage { message, images } in messages {
+        let role = role_label(message);
+        html.push_str(&format!("<article class=\"message {}\">\n", role.to_lowercase()));
+        html.push_str(&format!("<div class=\"message-header\"><span class=\"role\">{}</span>", role));
+        if let Some(ref time) = message.timestamp_local {
+            html.push_str(&format!("<time datetime=\"{}\">{}</time>", escape_html(&message.timestamp), escape_html(time)));
+        }
+        html.push_str("</div>\n");
+
+        if !message.content.trim().is_empty() {
+            html.push_str("<div class=\"message-body\">\n");
+            html.push_str(&render_message_body(&message.content));
+            html.push_str("</div>\n");
+        }
+
+        if !images.is_empty() {
+            html.push_str("<div class=\"images\">\n");
+            for image in images {
+                html.push_str(&format!(
+                    "<img src=\"data:{};base64,{}\" alt=\"\">\n",
+                    escape_html(&image.media_type),
+                    escape_html(&image.data)
+                ));
+            }
+            html.push_str("</div>\n");
+        }
+        html.push_str("</article>\n");
+    }
+
+    html.push_str("</main>\n</body>\n</html>\n");
+    html
+}
+
+/// Render message text: fenced code blocks are highlighted, the rest becomes paragraphs
+fn render_message_body(content: &str) -> String {
+    let mut html = String::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut lines = content.lines();
+
+    while let Some(line) = lines.next() {
+        let Some(language) = line.trim_start().strip_prefix("```") else {
+            if line.trim().is_empty() {
+                push_paragraph(&mut html, &mut paragraph);
+            } else {
+                paragraph.push(line);
+            }
+            continue;
+        };
+        push_paragraph(&mut html, &mut paragraph);
+
+        // An unclosed block runs to the end of the message
+        let code: Vec<&str> = lines
+            .by_ref()
+            .take_while(|line| !line.trim_start().starts_with("```"))
+            .collect();
+        let language = language.trim();
+
+        html.push_str("<div class=\"code-block\">");
+        if !language.is_empty() {
+            html.push_str(&format!("<span class=\"language\">{}</span>", escape_html(language)));
+        }
+        html.push_str(&format!(
+            "<pre><code>{}</code></pre></div>\n",
+            highlight_code(&code.join("\n"), language)
+        ));
+    }
+    push_paragraph(&mut html, &mut paragraph);
+
+    html
+}
+
+fn push_paragraph(html: &mut String, lines: &mut Vec<&str>) {
+    if lines.is_empty() {
+        return;
+    }
+    html.push_str(&format!("<p>{}</p>\n", render_inline(&lines.join("\n"))));
+    lines.clear();
+}
+
+/// Escape text, turning `inline code` spans into <code> elements
+fn render_inline(text: &str) -> String {
+    let parts: Vec<&str> = text.split('`').collect();
+    // An odd number of backticks leaves the last one unmatched; keep it as text
+    let unmatched = parts.len().is_multiple_of(2);
+
+    let mut html = String::new();
+    for (i, part) in parts.iter().enumerate() {
+        if i % 2 == 0 {
+            html.push_str(&escape_html(part));
+        } else if unmatched && i == parts.len() - 1 {
+            html.push('`');
+            html.push_str(&escape_html(part));
+        } else {
+            html.push_str(&format!("<code>{}</code>", escape_html(part)));
+        }
+    }
+    html
+}
+
+/// Words highlighted as keywords (shared by the common languages seen in sessions)
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "case", "catch", "class", "const", "continue", "def",
+    "default", "defer", "do", "elif", "else", "enum", "export", "extends", "false", "fn",
+    "for", "from", "func", "function", "if", "impl", "import", "in", "interface", "let",
+    "loop", "match", "mod", "mut", "new", "nil", "null", "package", "pub", "return",
+    "self", "static", "struct", "switch", "this", "throw", "trait", "true", "try", "type",
+    "use", "var", "where", "while", "with", "yield",
+];
+
+/// Languages whose code blocks are shown without highlighting
+const PLAIN_LANGUAGES: &[&str] = &["", "text", "txt", "plain", "plaintext", "markdown", "md", "diff", "log"];
+
+/// Start of a line comment in the given language (None if unknown)
+fn comment_prefix(language: &str) -> Option<&'static str> {
+    match language {
+        "python" | "py" | "sh" | "bash" | "zsh" | "shell" | "console" | "ruby" | "rb" | "yaml"
+        | "yml" | "toml" | "dockerfile" | "makefile" => Some("#"),
+        "sql" | "lua" | "haskell" | "hs" => Some("--"),
+        "json" => None,
+        _ => Some("//"),
+    }
+}
+
+/// Escape code and wrap keywords, strings, numbers and comments in `tok-*` spans
+///
+/// This is a simple tokenizer rather than a full grammar, which is enough to make
+/// shared logs readable without pulling in a highlighting library.
+fn highlight_code(code: &str, language: &str) -> String {
+    let language = language.to_lowercase();
+    if PLAIN_LANGUAGES.contains(&language.as_str()) {
+        return escape_html(code);
+    }
+    let comment = comment_prefix(&language);
+    // Rust uses single quotes for lifetimes, so they are not treated as strings there
+    let quotes: &[char] = if language == "rust" || language == "rs" {
+        &['"', '`']
+    } else {
+        &['"', '\'', '`']
+    };
+
+    let chars: Vec<char> = code.chars().collect();
+    let mut html = String::new();
+    let mut i = 0;
+
+    let span = |html: &mut String, class: &str, text: &[char]| {
+        let text: String = text.iter().collect();
+        html.push_str(&format!("<span class=\"tok-{}\">{}</span>", class, escape_html(&text)));
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        let rest = &chars[i..];
+
+        if let Some(prefix) = comment {
+            if rest.iter().take(prefix.len()).copied().eq(prefix.chars()) {
+                let end = rest.iter().position(|&c| c == '\n').unwrap_or(rest.len());
+                span(&mut html, "comment", &rest[..end]);
+                i += end;
+                continue;
+            }
+        }
+
+        if quotes.contains(&c) {
+            let mut end = 1;
+            while end < rest.len() && rest[end] != c && (c == '`' || rest[end] != '\n') {
+                end += if rest[end] == '\\' { 2 } else { 1 };
+            }
+            let end = (end + 1).min(rest.len());
+            span(&mut html, "string", &rest[..end]);
+            i += end;
+            continue;
+        }
+
+        let follows_word = i > 0 && (chars[i - 1].is_alphanumeric() || chars[i - 1] == '_');
+        if c.is_ascii_digit() && !follows_word {
+            let end = rest
+                .iter()
+                .position(|&c| !(c.is_ascii_alphanumeric() || c == '.' || c == '_'))
+                .unwrap_or(rest.len());
+            span(&mut html, "number", &rest[..end]);
+            i += end;
+            continue;
+        }
+
+        if (c.is_alphabetic() || c == '_') && !follows_word {
+            let end = rest
+                .iter()
+                .position(|&c| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            let word: String = rest[..end].iter().collect();
+            if KEYWORDS.contains(&word.as_str()) {
+                span(&mut html, "keyword", &rest[..end]);
+            } else {
+                html.push_str(&escape_html(&word));
+            }
+            i += end;
+            continue;
+        }
+
+        html.push_str(&escape_html(&c.to_string()));
+        i += 1;
+    }
+
+    html
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -1,7 +1,38 @@
+use crate::events;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// Lower bound for the polling interval to keep battery impact reasonable
+const MIN_POLLING_INTERVAL_MS: u64 = 500;
+
+/// Thinking blocks longer than this (in characters) are truncated
+const MAX_THINKING_CHARS: usize = 2000;
+
+/// Inserted between messages joined by `merge_consecutive`
+const MERGED_MESSAGE_SEPARATOR: &str = "\n\n";
+
+/// How many times to re-read a session file that changed while it was being read
+const MAX_READ_ATTEMPTS: usize = 3;
+
+/// Wait between re-reads of a session file that is being written
+const READ_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+/// Longest run of `-`-separated parts tried as one path component when decoding a
+/// project directory name (each separator doubles the combinations to check)
+const MAX_DECODED_COMPONENT_PARTS: usize = 6;
+
+/// Upper bound on threads summarizing session files at once
+/// Both disk reads and JSON parsing are involved, so more threads stop paying off and
+/// only compete with the UI for CPU and I/O
+const MAX_SUMMARY_WORKERS: usize = 8;
 
 /// Claude Code session log entry
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +50,16 @@ pub struct ClaudeLogEntry {
     /// Sidechain sessions are created by subagents and cannot be resumed
     #[serde(rename = "isSidechain")]
     pub is_sidechain: Option<bool>,
+    /// Meta entries (caveats, command wrappers) are not typed by the user
+    #[serde(rename = "isMeta")]
+    pub is_meta: Option<bool>,
+    /// Title written by Claude Code for `summary` entries
+    pub summary: Option<String>,
+    /// Set on assistant entries that carry an API error instead of a response
+    #[serde(rename = "isApiErrorMessage")]
+    pub is_api_error_message: Option<bool>,
+    /// Severity of `system` entries (e.g. "error")
+    pub level: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,12 +68,30 @@ pub struct ClaudeMessage {
     pub content: Option<serde_json::Value>,
 }
 
+/// Kind of a conversation message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum MessageKind {
+    #[default]
+    Text,
+    /// Extended thinking of the assistant
+    Thinking,
+}
+
 /// Parsed conversation message for display
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConversationMessage {
     pub role: String,
     pub content: String,
     pub timestamp: String,
+    /// `timestamp` converted to the system's local time zone, for display
+    #[serde(default)]
+    pub timestamp_local: Option<String>,
+    #[serde(default)]
+    pub kind: MessageKind,
+    /// Position in the session in chronological order (after merging, before
+    /// reversing or paging), so a message keeps its id whatever order it is read in
+    #[serde(default)]
+    pub index: usize,
 }
 
 /// Session summary
@@ -42,9 +101,39 @@ pub struct SessionSummary {
     pub project_path: String,
     pub cwd: Option<String>,
     pub git_branch: Option<String>,
+    /// `cwd` was not recorded in the session and was estimated (see `estimate_missing_metadata`)
+    pub cwd_estimated: bool,
+    /// `git_branch` was not recorded in the session and was taken from another session
+    pub git_branch_estimated: bool,
     pub first_message: Option<String>,
     pub timestamp: Option<String>,
+    /// `timestamp` converted to the system's local time zone, for display
+    pub timestamp_local: Option<String>,
     pub message_count: usize,
+    /// Size of the session file in bytes
+    pub file_size_bytes: u64,
+    /// Number of lines (log entries) in the session file
+    pub line_count: usize,
+    /// Whether the session clearly ended with an error (e.g. an API error)
+    pub ended_with_error: bool,
+}
+
+/// Only the fields needed to count messages and detect errors, so the remaining lines
+/// can be scanned without materializing message content
+#[derive(Debug, Deserialize)]
+struct LogEntryMarker {
+    #[serde(rename = "type")]
+    entry_type: Option<String>,
+    #[serde(rename = "isApiErrorMessage")]
+    is_api_error_message: Option<bool>,
+    level: Option<String>,
+}
+
+/// Sessions that fall into the same date bucket (e.g. "今日", "昨日")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionGroup {
+    pub label: String,
+    pub sessions: Vec<SessionSummary>,
 }
 
 /// Project directory info
@@ -54,10 +143,320 @@ pub struct ProjectInfo {
     pub path: String,
     pub session_count: usize,
     pub last_updated: Option<String>,
+    /// Main language or kind of the project ("rust", "typescript", ...), guessed from the
+    /// files in its cwd; None when the cwd is unknown, inaccessible or unrecognized
+    pub project_type: Option<String>,
+}
+
+/// Files whose presence in a project directory identifies its type, checked in order
+const PROJECT_TYPE_MARKERS: &[(&str, &str)] = &[
+    ("Cargo.toml", "rust"),
+    ("tsconfig.json", "typescript"),
+    ("package.json", "javascript"),
+    ("go.mod", "go"),
+    ("pyproject.toml", "python"),
+    ("requirements.txt", "python"),
+    ("setup.py", "python"),
+    ("Gemfile", "ruby"),
+    ("Package.swift", "swift"),
+    ("build.gradle.kts", "kotlin"),
+    ("build.gradle", "java"),
+    ("pom.xml", "java"),
+    ("composer.json", "php"),
+    ("mix.exs", "elixir"),
+    ("CMakeLists.txt", "c++"),
+];
+
+/// How a moved project was matched to the requested cwd
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectMatch {
+    /// The last component of the recorded cwd equals that of the requested cwd
+    DirectoryName,
+    /// The last component of the recorded cwd equals the requested cwd's git repository name
+    RepositoryName,
+}
+
+/// A Claude project that may belong to a cwd that was moved or renamed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectCandidate {
+    /// Claude project directory (can be passed as `project_path`)
+    pub project_dir: String,
+    /// cwd recorded in the project's sessions
+    pub cwd: Option<String>,
+    pub matched_by: ProjectMatch,
+    /// Whether the recorded cwd still exists (if not, the project has likely been moved)
+    pub cwd_exists: bool,
+    pub session_count: usize,
+    /// Modification time of the newest session (RFC 3339)
+    pub last_updated: Option<String>,
+}
+
+/// Payload of the `claude:session-updated` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaudeSessionUpdatedPayload {
+    pub project_path: String,
+    pub session_id: String,
+}
+
+/// Lightweight polling monitor for environments where a file watcher is not available
+///
+/// Periodically compares the mtime of every session file under the projects
+/// directory and emits `claude:session-updated` for the ones that changed.
+pub struct ClaudePoller {
+    stop_flag: Option<Arc<AtomicBool>>,
+}
+
+impl ClaudePoller {
+    pub fn new() -> Self {
+        Self { stop_flag: None }
+    }
+
+    /// Start polling (restarts if already running)
+    pub fn start<R: tauri::Runtime>(
+        &mut self,
+        app: &tauri::AppHandle<R>,
+        interval_ms: u64,
+    ) -> Result<(), String> {
+        self.stop();
+
+        let projects_dir = get_claude_logs_dir()?;
+        let interval = Duration::from_millis(interval_ms.max(MIN_POLLING_INTERVAL_MS));
+        let stop_flag = Arc::new(AtomicBool::new(false));
+
+        let flag = stop_flag.clone();
+        let app_handle = app.clone();
+        thread::spawn(move || {
+            let mut last_snapshot = snapshot_session_mtimes(&projects_dir);
+
+            while !flag.load(Ordering::Relaxed) {
+                thread::sleep(interval);
+                if flag.load(Ordering::Relaxed) {
+                    break;
+                }
+
+                let snapshot = snapshot_session_mtimes(&projects_dir);
+                for (path, modified) in &snapshot {
+                    if last_snapshot.get(path) == Some(modified) {
+                        continue;
+                    }
+                    let payload = ClaudeSessionUpdatedPayload {
+                        project_path: path
+                            .parent()
+                            .map(|p| p.to_string_lossy().to_string())
+                            .unwrap_or_default(),
+                        session_id: path
+                            .file_stem()
+                            .and_then(|n| n.to_str())
+                            .unwrap_or("")
+                            .to_string(),
+                    };
+                    if let Err(e) = events::emit(&app_handle, events::CLAUDE_SESSION_UPDATED, &payload) {
+                        eprintln!("[claude_logs] Failed to emit event: {}", e);
+                    }
+                }
+                last_snapshot = snapshot;
+            }
+        });
+
+        self.stop_flag = Some(stop_flag);
+        Ok(())
+    }
+
+    /// Stop polling
+    pub fn stop(&mut self) {
+        if let Some(flag) = self.stop_flag.take() {
+            flag.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+impl Default for ClaudePoller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Payload of the `claude:message` event (one parsed message of a streamed session)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaudeMessagePayload {
+    pub stream_id: String,
+    /// Position of the message in the session (0-based)
+    pub index: usize,
+    pub message: ConversationMessage,
+}
+
+/// Payload of the `claude:read-complete` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaudeReadCompletePayload {
+    pub stream_id: String,
+    /// Number of `claude-message` events sent
+    pub message_count: usize,
+    /// The stream was stopped with `cancel` before reaching the end
+    pub cancelled: bool,
+    /// Set if reading failed part-way (messages sent so far are still valid)
+    pub error: Option<String>,
+}
+
+/// Streams a session's messages as they are parsed, so large sessions can be shown
+/// progressively instead of after the whole file has been read
+///
+/// Each message is emitted as `claude-message`, followed by `claude-read-complete`.
+/// The caller picks the stream id, so it can tell its events apart from the first
+/// message on, and cancels the stream with it.
+pub struct SessionStreamer {
+    cancel_flags: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
+}
+
+impl SessionStreamer {
+    pub fn new() -> Self {
+        Self {
+            cancel_flags: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Start streaming a session under `stream_id`
+    ///
+    /// Missing files and sidechain sessions are rejected up front, like `read_claude_session`,
+    /// and so is an id that is empty or already used by a running stream.
+    pub fn start<R: tauri::Runtime>(
+        &self,
+        app: &tauri::AppHandle<R>,
+        stream_id: &str,
+        project_path: &str,
+        session_id: &str,
+        include_thinking: bool,
+    ) -> Result<(), String> {
+        if stream_id.is_empty() {
+            return Err("Stream id must not be empty".to_string());
+        }
+        let session_file = get_claude_project_dir(project_path)?.join(format!("{}.jsonl", session_id));
+        if !session_file.exists() {
+            return Err(format!("Session file not found: {:?}", session_file));
+        }
+        if is_sidechain_session(&session_file) {
+            return Err(format!("Session {} is a sidechain session and cannot be resumed", session_id));
+        }
+
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        {
+            let mut flags = self.cancel_flags.lock().map_err(|e| e.to_string())?;
+            if flags.contains_key(stream_id) {
+                return Err(format!("Stream {} is already running", stream_id));
+            }
+            flags.insert(stream_id.to_string(), cancel_flag.clone());
+        }
+
+        let app_handle = app.clone();
+        let cancel_flags = self.cancel_flags.clone();
+        let id = stream_id.to_string();
+        thread::spawn(move || {
+            let (message_count, error) = stream_session_file(&app_handle, &session_file, &id, include_thinking, &cancel_flag);
+
+            if let Ok(mut flags) = cancel_flags.lock() {
+                flags.remove(&id);
+            }
+            let payload = ClaudeReadCompletePayload {
+                stream_id: id,
+                message_count,
+                cancelled: cancel_flag.load(Ordering::Relaxed),
+                error,
+            };
+            if let Err(e) = events::emit(&app_handle, events::CLAUDE_READ_COMPLETE, &payload) {
+                eprintln!("[claude_logs] Failed to emit read-complete event: {}", e);
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Cancel a running stream
+    /// Returns false if the stream has already finished (or never existed)
+    pub fn cancel(&self, stream_id: &str) -> Result<bool, String> {
+        let flags = self.cancel_flags.lock().map_err(|e| e.to_string())?;
+        match flags.get(stream_id) {
+            Some(flag) => {
+                flag.store(true, Ordering::Relaxed);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
+
+impl Default for SessionStreamer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Emit the messages of a session file one by one
+/// Returns the number of messages sent and the read error, if any
+fn stream_session_file<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    session_file: &Path,
+    stream_id: &str,
+    include_thinking: bool,
+    cancel_flag: &AtomicBool,
+) -> (usize, Option<String>) {
+    let content = match read_session_content(session_file) {
+        Ok(content) => content,
+        Err(e) => return (0, Some(e)),
+    };
+
+    let mut index = 0;
+    for line in content.lines() {
+        if cancel_flag.load(Ordering::Relaxed) {
+            break;
+        }
+        let Ok(entry) = serde_json::from_str::<ClaudeLogEntry>(line) else {
+            continue;
+        };
+
+        for mut message in entry_messages(&entry, include_thinking) {
+            message.index = index;
+            let payload = ClaudeMessagePayload {
+                stream_id: stream_id.to_string(),
+                index,
+                message,
+            };
+            if let Err(e) = events::emit(app, events::CLAUDE_MESSAGE, &payload) {
+                return (index, Some(e.to_string()));
+            }
+            index += 1;
+        }
+    }
+
+    (index, None)
+}
+
+/// Collect the modification time of every session file under the projects directory
+pub(crate) fn snapshot_session_mtimes(projects_dir: &Path) -> HashMap<PathBuf, SystemTime> {
+    let mut snapshot = HashMap::new();
+
+    let Ok(projects) = fs::read_dir(projects_dir) else {
+        return snapshot;
+    };
+
+    for project in projects.flatten() {
+        let Ok(sessions) = fs::read_dir(project.path()) else {
+            continue;
+        };
+        for session in sessions.flatten() {
+            let path = session.path();
+            if path.extension().map(|ext| ext == "jsonl").unwrap_or(false) {
+                if let Ok(modified) = session.metadata().and_then(|m| m.modified()) {
+                    snapshot.insert(path, modified);
+                }
+            }
+        }
+    }
+
+    snapshot
 }
 
 /// Get Claude logs directory path
-fn get_claude_logs_dir() -> Result<PathBuf, String> {
+pub(crate) fn get_claude_logs_dir() -> Result<PathBuf, String> {
     let home = dirs::home_dir().ok_or("Could not find home directory")?;
     let claude_projects = home.join(".claude").join("projects");
 
@@ -90,6 +489,7 @@ pub fn list_claude_projects() -> Result<Vec<ProjectInfo>, String> {
             // Count session files and find the newest one
             let mut session_count = 0;
             let mut newest_modified: Option<std::time::SystemTime> = None;
+            let mut newest_session: Option<PathBuf> = None;
 
             if let Ok(dir_entries) = fs::read_dir(&path) {
                 for dir_entry in dir_entries.flatten() {
@@ -101,6 +501,7 @@ pub fn list_claude_projects() -> Result<Vec<ProjectInfo>, String> {
                             if let Ok(modified) = metadata.modified() {
                                 if newest_modified.is_none() || Some(modified) > newest_modified {
                                     newest_modified = Some(modified);
+                                    newest_session = Some(file_path.clone());
                                 }
                             }
                         }
@@ -115,11 +516,17 @@ pub fn list_claude_projects() -> Result<Vec<ProjectInfo>, String> {
                     datetime.to_rfc3339()
                 });
 
+                // Only the newest session is read, and only its first few entries
+                let project_type = newest_session
+                    .and_then(|session| recorded_cwd(&session))
+                    .and_then(|cwd| detect_project_type(Path::new(&cwd)));
+
                 projects.push(ProjectInfo {
                     name: dir_name.replace("-", "/"),
                     path: path.to_string_lossy().to_string(),
                     session_count,
                     last_updated,
+                    project_type,
                 });
             }
         }
@@ -132,90 +539,376 @@ pub fn list_claude_projects() -> Result<Vec<ProjectInfo>, String> {
 }
 
 /// List sessions for a specific project
+///
+/// Each session file is read once and summarized in a single pass; files are
+/// processed in parallel since projects can contain hundreds of sessions.
 #[tauri::command]
 pub fn list_claude_sessions(project_path: String) -> Result<Vec<SessionSummary>, String> {
     // Convert project_path (which might be actual cwd) to Claude's project directory
-    let project_dir = get_claude_project_dir(&project_path)?;
+    let project_dir = match get_claude_project_dir(&project_path) {
+        Ok(dir) => dir,
+        // The project may have been moved or renamed after its sessions were recorded
+        Err(e) => {
+            let mut candidates = find_claude_project_candidates(project_path.clone())?;
+            match candidates.len() {
+                0 => return Err(e),
+                // Only a project whose recorded cwd is gone can be the moved one;
+                // a single live match is a different project with the same name
+                1 if !candidates[0].cwd_exists => PathBuf::from(candidates.remove(0).project_dir),
+                n => {
+                    return Err(format!(
+                        "{} ({} candidate projects found, see find_claude_project_candidates)",
+                        e, n
+                    ))
+                }
+            }
+        }
+    };
 
-    let mut sessions = Vec::new();
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(&project_dir).map_err(|e| e.to_string())? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.extension().map(|ext| ext == "jsonl").unwrap_or(false) {
+            paths.push(path);
+        }
+    }
 
-    let entries = fs::read_dir(&project_dir).map_err(|e| e.to_string())?;
+    let mut sessions = Vec::new();
+    for result in summarize_session_files(&paths, &project_path) {
+        if let Some(session) = result? {
+            sessions.push(session);
+        }
+    }
 
-    for entry in entries {
-        let entry = entry.map_err(|e| e.to_string())?;
-        let path = entry.path();
+    // Sort by timestamp descending (by ID for equal timestamps, so the order is stable
+    // regardless of which worker finished first)
+    sessions.sort_by(|a, b| {
+        b.timestamp
+            .cmp(&a.timestamp)
+            .then_with(|| a.session_id.cmp(&b.session_id))
+    });
+    estimate_missing_metadata(&mut sessions, &project_dir);
 
-        if path.extension().map(|ext| ext == "jsonl").unwrap_or(false) {
-            let session_id = path.file_stem()
-                .and_then(|n| n.to_str())
-                .unwrap_or("")
-                .to_string();
+    Ok(sessions)
+}
 
-            // Read first few lines to get summary
-            let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
-            let lines: Vec<&str> = content.lines().take(10).collect();
+/// Fill in a cwd or git branch that a session did not record, flagging it as estimated
+///
+/// Sessions in one project directory share a cwd, so the most recent recorded one is
+/// used, falling back to decoding the project directory name. A missing branch is taken
+/// from the session closest in time that recorded one. `sessions` must be newest first.
+fn estimate_missing_metadata(sessions: &mut [SessionSummary], project_dir: &Path) {
+    let known_cwd = sessions.iter().find_map(|s| s.cwd.clone()).or_else(|| {
+        project_dir
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(decode_project_dir_name)
+    });
+    let branches: Vec<(Option<chrono::DateTime<chrono::FixedOffset>>, String)> = sessions
+        .iter()
+        .filter_map(|s| Some((parse_timestamp(s.timestamp.as_deref()), s.git_branch.clone()?)))
+        .collect();
 
-            // Skip sidechain sessions (created by subagents, not resumable)
-            if let Some(first_line) = lines.first() {
-                if let Ok(first_entry) = serde_json::from_str::<ClaudeLogEntry>(first_line) {
-                    if first_entry.is_sidechain == Some(true) {
-                        continue;
-                    }
-                }
+    for session in sessions.iter_mut() {
+        if session.cwd.is_none() {
+            if let Some(cwd) = &known_cwd {
+                session.cwd = Some(cwd.clone());
+                session.cwd_estimated = true;
             }
+        }
+        if session.git_branch.is_none() {
+            if let Some(branch) = nearest_branch(&branches, session.timestamp.as_deref()) {
+                session.git_branch = Some(branch);
+                session.git_branch_estimated = true;
+            }
+        }
+    }
+}
 
-            let mut first_message = None;
-            let mut timestamp = None;
-            let mut git_branch = None;
-            let mut cwd = None;
+fn parse_timestamp(timestamp: Option<&str>) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+    timestamp.and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+}
 
-            for line in &lines {
-                if let Ok(entry) = serde_json::from_str::<ClaudeLogEntry>(line) {
-                    // Get cwd from the first entry that has it
-                    if cwd.is_none() {
-                        cwd = entry.cwd.clone();
-                    }
-                    if entry.entry_type.as_deref() == Some("user") {
-                        if first_message.is_none() {
-                            if let Some(msg) = &entry.message {
-                                first_message = extract_text_content(&msg.content);
-                            }
-                            timestamp = entry.timestamp.clone();
-                            git_branch = entry.git_branch.clone();
-                        }
-                    }
+/// The branch recorded closest in time to `timestamp` (the most recent one without a timestamp)
+fn nearest_branch(
+    branches: &[(Option<chrono::DateTime<chrono::FixedOffset>>, String)],
+    timestamp: Option<&str>,
+) -> Option<String> {
+    let nearest = parse_timestamp(timestamp).and_then(|time| {
+        branches
+            .iter()
+            .filter_map(|(recorded, branch)| Some(((time - (*recorded)?).num_seconds().abs(), branch)))
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, branch)| branch)
+    });
+    nearest.or_else(|| branches.first().map(|(_, branch)| branch)).cloned()
+}
+
+/// Reconstruct a cwd from a Claude project directory name by finding the existing directory
+///
+/// The encoding replaces both `/` and `.` with `-` (see `get_claude_project_dir`), so each
+/// `-` may stand for any of the three; directories are resolved from the root and the
+/// first existing combination wins. Returns None if no existing directory matches.
+fn decode_project_dir_name(name: &str) -> Option<String> {
+    let parts: Vec<&str> = name.strip_prefix('-')?.split('-').collect();
+    resolve_encoded_path(Path::new("/"), &parts)
+}
+
+fn resolve_encoded_path(base: &Path, parts: &[&str]) -> Option<String> {
+    if parts.is_empty() {
+        return Some(base.to_string_lossy().to_string());
+    }
+    for end in 1..=parts.len().min(MAX_DECODED_COMPONENT_PARTS) {
+        for component in component_variants(&parts[..end]) {
+            if component.is_empty() {
+                continue;
+            }
+            let candidate = base.join(&component);
+            if candidate.is_dir() {
+                if let Some(path) = resolve_encoded_path(&candidate, &parts[end..]) {
+                    return Some(path);
                 }
             }
+        }
+    }
+    None
+}
 
-            // Count total messages
-            let message_count = content.lines()
-                .filter(|line| {
-                    serde_json::from_str::<ClaudeLogEntry>(line)
-                        .map(|e| e.entry_type.as_deref() == Some("user") || e.entry_type.as_deref() == Some("assistant"))
-                        .unwrap_or(false)
+/// Every way of joining `parts` with `-` or `.` (an empty first part becomes a leading `.`)
+fn component_variants(parts: &[&str]) -> Vec<String> {
+    let mut variants = vec![parts[0].to_string()];
+    for part in &parts[1..] {
+        variants = variants
+            .into_iter()
+            .flat_map(|variant| ['-', '.'].map(|separator| format!("{}{}{}", variant, separator, part)))
+            .collect();
+    }
+    variants
+}
+
+/// Summarize session files on up to `MAX_SUMMARY_WORKERS` threads
+///
+/// Workers take the next file from a shared counter instead of a fixed share, so a few
+/// large sessions don't leave the other workers idle. Results are in no particular order.
+fn summarize_session_files(
+    paths: &[PathBuf],
+    project_path: &str,
+) -> Vec<Result<Option<SessionSummary>, String>> {
+    let worker_count = thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(MAX_SUMMARY_WORKERS)
+        .min(paths.len());
+    if worker_count <= 1 {
+        return paths
+            .iter()
+            .map(|path| summarize_session_file(path, project_path))
+            .collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..worker_count)
+            .map(|_| {
+                let next = &next;
+                scope.spawn(move || {
+                    let mut results = Vec::new();
+                    while let Some(path) = paths.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        results.push(summarize_session_file(path, project_path));
+                    }
+                    results
                 })
-                .count();
-
-            sessions.push(SessionSummary {
-                session_id,
-                project_path: project_path.clone(),
-                cwd,
-                git_branch,
-                first_message,
-                timestamp,
-                message_count,
-            });
+            })
+            .collect();
+
+        workers
+            .into_iter()
+            .flat_map(|worker| {
+                worker
+                    .join()
+                    .unwrap_or_else(|_| vec![Err("Session summary worker panicked".to_string())])
+            })
+            .collect()
+    })
+}
+
+/// Find Claude projects that may belong to `cwd` after it was moved or renamed
+///
+/// A project matches when the last component of its recorded cwd equals the last
+/// component of `cwd` or the name of `cwd`'s git repository (from the `origin` remote,
+/// which survives renames). The project stored under `cwd` itself is excluded.
+/// Projects whose recorded cwd no longer exists come first, then the most recent.
+#[tauri::command]
+pub fn find_claude_project_candidates(cwd: String) -> Result<Vec<ProjectCandidate>, String> {
+    let projects_dir = get_claude_logs_dir()?;
+    let cwd = cwd.trim_end_matches('/');
+    let own_dir = projects_dir.join(encode_project_dir_name(cwd));
+
+    let dir_name = Path::new(cwd)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase());
+    let repo_name = git_repository_name(cwd).map(|n| n.to_lowercase());
+
+    let mut candidates = Vec::new();
+    for entry in fs::read_dir(&projects_dir).map_err(|e| e.to_string())?.flatten() {
+        let path = entry.path();
+        if !path.is_dir() || path == own_dir {
+            continue;
         }
+
+        let mut sessions: Vec<(PathBuf, SystemTime)> = fs::read_dir(&path)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|e| e.path())
+                    .filter(|p| p.extension().map(|ext| ext == "jsonl").unwrap_or(false))
+                    .map(|p| {
+                        let modified = fs::metadata(&p)
+                            .and_then(|m| m.modified())
+                            .unwrap_or(SystemTime::UNIX_EPOCH);
+                        (p, modified)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        sessions.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+
+        let Some(recorded_cwd) = sessions.iter().find_map(|(p, _)| recorded_cwd(p)) else {
+            continue;
+        };
+        let recorded_name = Path::new(&recorded_cwd)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_lowercase());
+
+        let matched_by = if recorded_name.is_some() && recorded_name == dir_name {
+            ProjectMatch::DirectoryName
+        } else if recorded_name.is_some() && recorded_name == repo_name {
+            ProjectMatch::RepositoryName
+        } else {
+            continue;
+        };
+
+        candidates.push(ProjectCandidate {
+            project_dir: path.to_string_lossy().to_string(),
+            cwd_exists: Path::new(&recorded_cwd).exists(),
+            cwd: Some(recorded_cwd),
+            matched_by,
+            session_count: sessions.len(),
+            last_updated: sessions.first().map(|(_, modified)| {
+                let datetime: chrono::DateTime<chrono::Utc> = (*modified).into();
+                datetime.to_rfc3339()
+            }),
+        });
     }
 
-    // Sort by timestamp descending
-    sessions.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    candidates.sort_by(|a, b| {
+        a.cwd_exists
+            .cmp(&b.cwd_exists)
+            .then_with(|| b.last_updated.cmp(&a.last_updated))
+    });
 
-    Ok(sessions)
+    Ok(candidates)
+}
+
+/// The cwd recorded in the first entries of a session file
+fn recorded_cwd(path: &Path) -> Option<String> {
+    /// Entries to look at before giving up (the cwd appears in the first few)
+    const MAX_LINES: usize = 20;
+
+    let file = fs::File::open(path).ok()?;
+    BufReader::new(file)
+        .lines()
+        .take(MAX_LINES)
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<ClaudeLogEntry>(&line).ok())
+        .find_map(|entry| entry.cwd)
+}
+
+/// Guess the project type from the marker files at the top of `dir`
+/// (None if the directory is missing or has no known marker)
+fn detect_project_type(dir: &Path) -> Option<String> {
+    if !dir.is_dir() {
+        return None;
+    }
+    PROJECT_TYPE_MARKERS
+        .iter()
+        .find(|(file, _)| dir.join(file).exists())
+        .map(|(_, project_type)| project_type.to_string())
+}
+
+/// Repository name from the `origin` remote of the git repository at `cwd`
+fn git_repository_name(cwd: &str) -> Option<String> {
+    let output = Command::new("git")
+        .args(["-C", cwd, "remote", "get-url", "origin"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    // git@github.com:owner/repo.git or https://github.com/owner/repo(.git)
+    let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let name = url
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .rsplit(['/', ':'])
+        .next()?
+        .to_string();
+    (!name.is_empty()).then_some(name)
+}
+
+/// List sessions for a project grouped into date buckets
+///
+/// Timestamps are interpreted in the system local time zone. Groups are returned in
+/// the order 今日, 昨日, 今週, それ以前, 不明 and empty groups are omitted.
+/// Sessions keep their descending order within each group.
+#[tauri::command]
+pub fn list_claude_sessions_grouped(project_path: String) -> Result<Vec<SessionGroup>, String> {
+    let sessions = list_claude_sessions(project_path)?;
+    let today = chrono::Local::now().date_naive();
+    Ok(group_sessions_by_day(sessions, today))
+}
+
+fn group_sessions_by_day(sessions: Vec<SessionSummary>, today: chrono::NaiveDate) -> Vec<SessionGroup> {
+    use chrono::Datelike;
+
+    const LABELS: [&str; 5] = ["今日", "昨日", "今週", "それ以前", "不明"];
+
+    let yesterday = today.pred_opt().unwrap_or(today);
+    let week_start = today - chrono::Duration::days(today.weekday().num_days_from_monday() as i64);
+
+    let mut buckets: Vec<Vec<SessionSummary>> = vec![Vec::new(); LABELS.len()];
+    for session in sessions {
+        let date = session
+            .timestamp
+            .as_deref()
+            .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| t.with_timezone(&chrono::Local).date_naive());
+
+        let index = match date {
+            // Future timestamps (clock skew) are treated as today
+            Some(d) if d >= today => 0,
+            Some(d) if d == yesterday => 1,
+            Some(d) if d >= week_start => 2,
+            Some(_) => 3,
+            None => 4,
+        };
+        buckets[index].push(session);
+    }
+
+    LABELS
+        .iter()
+        .zip(buckets)
+        .filter(|(_, sessions)| !sessions.is_empty())
+        .map(|(label, sessions)| SessionGroup {
+            label: label.to_string(),
+            sessions,
+        })
+        .collect()
 }
 
 /// Convert a project path (cwd) to Claude's project directory path
-fn get_claude_project_dir(cwd: &str) -> Result<PathBuf, String> {
+pub(crate) fn get_claude_project_dir(cwd: &str) -> Result<PathBuf, String> {
     let home = dirs::home_dir().ok_or("Could not find home directory")?;
     let claude_projects = home.join(".claude").join("projects");
 
@@ -224,12 +917,7 @@ fn get_claude_project_dir(cwd: &str) -> Result<PathBuf, String> {
         return Ok(PathBuf::from(cwd));
     }
 
-    // Convert cwd to Claude's directory name format:
-    // - Replace / with -
-    // - Replace . with -
-    // Result: /Users/foo/github.com/bar -> -Users-foo-github-com-bar
-    let encoded_name = cwd.replace("/", "-").replace(".", "-");
-    let project_dir = claude_projects.join(&encoded_name);
+    let project_dir = claude_projects.join(encode_project_dir_name(cwd));
 
     if project_dir.exists() {
         return Ok(project_dir);
@@ -238,9 +926,31 @@ fn get_claude_project_dir(cwd: &str) -> Result<PathBuf, String> {
     Err(format!("Claude project directory not found for: {} (tried: {:?})", cwd, project_dir))
 }
 
+/// Claude's directory name for a project path (cwd)
+///
+/// `/` and `.` are replaced with `-`:
+/// /Users/foo/github.com/bar -> -Users-foo-github-com-bar
+fn encode_project_dir_name(cwd: &str) -> String {
+    cwd.replace("/", "-").replace(".", "-")
+}
+
 /// Read a specific session's conversation
+/// Thinking blocks are skipped unless `include_thinking` is true
+/// With `merge_consecutive`, consecutive messages of the same role and kind are joined
+/// into one (assistant replies are otherwise split up by tool round-trips)
+/// `reverse` returns the newest message first; `offset` and `limit` page through the
+/// result in the returned order (so `reverse` + `limit: N` gives the latest N)
 #[tauri::command]
-pub fn read_claude_session(project_path: String, session_id: String) -> Result<Vec<ConversationMessage>, String> {
+pub fn read_claude_session(
+    project_path: String,
+    session_id: String,
+    include_thinking: Option<bool>,
+    merge_consecutive: Option<bool>,
+    reverse: Option<bool>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Result<Vec<ConversationMessage>, String> {
+    let include_thinking = include_thinking.unwrap_or(false);
     // Convert project_path (which might be actual cwd) to Claude's project directory
     let claude_project_dir = get_claude_project_dir(&project_path)?;
     let session_file = claude_project_dir.join(format!("{}.jsonl", session_id));
@@ -249,7 +959,7 @@ pub fn read_claude_session(project_path: String, session_id: String) -> Result<V
         return Err(format!("Session file not found: {:?}", session_file));
     }
 
-    let content = fs::read_to_string(&session_file).map_err(|e| e.to_string())?;
+    let content = read_session_content(&session_file)?;
 
     // Check if this is a sidechain session (not resumable)
     // Sidechain sessions are created by subagents and cannot be resumed with --resume
@@ -261,27 +971,263 @@ pub fn read_claude_session(project_path: String, session_id: String) -> Result<V
         }
     }
 
+    let mut messages = parse_session_messages(&content, include_thinking);
+
+    if merge_consecutive.unwrap_or(false) {
+        messages = merge_consecutive_messages(messages);
+    }
+    for (index, message) in messages.iter_mut().enumerate() {
+        message.index = index;
+    }
+
+    // Paging applies after reversing, so `reverse` with `limit` gives the latest messages
+    if reverse.unwrap_or(false) {
+        messages.reverse();
+    }
+    let offset = offset.unwrap_or(0);
+    let limit = limit.unwrap_or(usize::MAX);
+
+    Ok(messages.into_iter().skip(offset).take(limit).collect())
+}
+
+/// Conversation messages of a session file's content, in order
+pub(crate) fn parse_session_messages(content: &str, include_thinking: bool) -> Vec<ConversationMessage> {
+    content
+        .lines()
+        .filter_map(|line| serde_json::from_str::<ClaudeLogEntry>(line).ok())
+        .flat_map(|entry| entry_messages(&entry, include_thinking))
+        .collect()
+}
+
+/// Join runs of messages with the same role and kind, keeping the first timestamp
+fn merge_consecutive_messages(messages: Vec<ConversationMessage>) -> Vec<ConversationMessage> {
+    let mut merged: Vec<ConversationMessage> = Vec::with_capacity(messages.len());
+
+    for message in messages {
+        match merged.last_mut() {
+            Some(last) if last.role == message.role && last.kind == message.kind => {
+                last.content.push_str(MERGED_MESSAGE_SEPARATOR);
+                last.content.push_str(&message.content);
+            }
+            _ => merged.push(message),
+        }
+    }
+
+    merged
+}
+
+/// Read a session file without picking up a line Claude is still writing
+///
+/// The file is re-read if its size or mtime changed during the read, and a trailing
+/// line that is not valid JSON yet is dropped, so a concurrent append never yields
+/// a broken entry. The poller reports the file again once the write has finished.
+pub(crate) fn read_session_content(path: &Path) -> Result<String, String> {
+    let file_state = |path: &Path| {
+        fs::metadata(path)
+            .ok()
+            .map(|m| (m.len(), m.modified().ok()))
+    };
+
+    let mut content = String::new();
+    for attempt in 0..MAX_READ_ATTEMPTS {
+        if attempt > 0 {
+            thread::sleep(READ_RETRY_DELAY);
+        }
+        let before = file_state(path);
+        content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        if before.is_some() && before == file_state(path) {
+            break;
+        }
+    }
+
+    Ok(trim_incomplete_last_line(content))
+}
+
+/// Drop the last line if it has no trailing newline and does not parse as JSON yet
+fn trim_incomplete_last_line(mut content: String) -> String {
+    if content.ends_with('\n') {
+        return content;
+    }
+    let last_line_start = content.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    if serde_json::from_str::<serde_json::Value>(&content[last_line_start..]).is_err() {
+        content.truncate(last_line_start);
+    }
+    content
+}
+
+/// Conversation messages contained in one log entry (thinking first, then text)
+pub(crate) fn entry_messages(entry: &ClaudeLogEntry, include_thinking: bool) -> Vec<ConversationMessage> {
     let mut messages = Vec::new();
+    let entry_type = entry.entry_type.as_deref();
+
+    if entry_type != Some("user") && entry_type != Some("assistant") {
+        return messages;
+    }
+    let Some(msg) = &entry.message else {
+        return messages;
+    };
+
+    let role = msg.role.clone().unwrap_or_else(|| entry_type.unwrap_or("unknown").to_string());
+    let timestamp = entry.timestamp.clone().unwrap_or_default();
+    let timestamp_local = entry.timestamp.as_deref().and_then(format_local_timestamp);
+
+    if include_thinking {
+        if let Some(thinking) = extract_thinking_content(&msg.content) {
+            messages.push(ConversationMessage {
+                role: role.clone(),
+                content: thinking,
+                timestamp: timestamp.clone(),
+                timestamp_local: timestamp_local.clone(),
+                kind: MessageKind::Thinking,
+                index: 0,
+            });
+        }
+    }
+    if let Some(text) = extract_text_content(&msg.content) {
+        messages.push(ConversationMessage {
+            role,
+            content: text,
+            timestamp,
+            timestamp_local,
+            kind: MessageKind::Text,
+            index: 0,
+        });
+    }
+
+    messages
+}
+
+/// Summarize one session file in a single pass (None for sidechain sessions)
+///
+/// Lines are fully parsed only until the head metadata is found; the rest are parsed
+/// as `LogEntryMarker` just for counting and the trailing error check.
+pub(crate) fn summarize_session_file(path: &Path, project_path: &str) -> Result<Option<SessionSummary>, String> {
+    let session_id = path.file_stem()
+        .and_then(|n| n.to_str())
+        .unwrap_or("")
+        .to_string();
+
+    let content = read_session_content(path)?;
+    let file_size_bytes = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+
+    let mut line_count = 0;
+    let mut message_count = 0;
+    let mut last_entry: Option<LogEntryMarker> = None;
+
+    let mut first_message = None;
+    let mut timestamp = None;
+    let mut git_branch = None;
+    let mut cwd = None;
+    // Fallbacks for sessions without a meaningful user message
+    let mut summary = None;
+    let mut first_assistant_message = None;
+    let mut first_timestamp = None;
+    let mut head_done = false;
 
     for line in content.lines() {
-        if let Ok(entry) = serde_json::from_str::<ClaudeLogEntry>(line) {
-            let entry_type = entry.entry_type.as_deref();
-
-            if entry_type == Some("user") || entry_type == Some("assistant") {
-                if let Some(msg) = &entry.message {
-                    if let Some(text) = extract_text_content(&msg.content) {
-                        messages.push(ConversationMessage {
-                            role: msg.role.clone().unwrap_or_else(|| entry_type.unwrap_or("unknown").to_string()),
-                            content: text,
-                            timestamp: entry.timestamp.clone().unwrap_or_default(),
-                        });
+        line_count += 1;
+
+        // Read until the first meaningful user message instead of a fixed number of lines,
+        // since leading meta/summary entries can push it further down
+        if !head_done {
+            let entry = serde_json::from_str::<ClaudeLogEntry>(line).ok();
+
+            // Skip sidechain sessions (created by subagents, not resumable)
+            if line_count == 1 && entry.as_ref().and_then(|e| e.is_sidechain) == Some(true) {
+                return Ok(None);
+            }
+
+            if let Some(entry) = entry {
+                // Get cwd from the first entry that has it
+                if cwd.is_none() {
+                    cwd = entry.cwd.clone();
+                }
+                if first_timestamp.is_none() {
+                    first_timestamp = entry.timestamp.clone();
+                }
+                match entry.entry_type.as_deref() {
+                    Some("user") if first_message.is_none() && entry.is_meta != Some(true) => {
+                        let text = entry.message.as_ref().and_then(|msg| extract_text_content(&msg.content));
+                        if let Some(text) = text.filter(|t| is_meaningful_user_text(t)) {
+                            first_message = Some(text);
+                            timestamp = entry.timestamp.clone();
+                            git_branch = entry.git_branch.clone();
+                        }
+                    }
+                    Some("assistant") if first_assistant_message.is_none() => {
+                        first_assistant_message = entry.message.as_ref().and_then(|msg| extract_text_content(&msg.content));
                     }
+                    Some("summary") if summary.is_none() => {
+                        summary = entry.summary.clone();
+                    }
+                    _ => {}
                 }
+                head_done = first_message.is_some() && cwd.is_some();
             }
         }
+
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Ok(marker) = serde_json::from_str::<LogEntryMarker>(line) else {
+            continue;
+        };
+        if matches!(marker.entry_type.as_deref(), Some("user") | Some("assistant")) {
+            message_count += 1;
+        }
+        last_entry = Some(marker);
+    }
+
+    if first_message.is_none() {
+        first_message = summary.or(first_assistant_message);
+    }
+    if timestamp.is_none() {
+        timestamp = first_timestamp;
     }
 
-    Ok(messages)
+    Ok(Some(SessionSummary {
+        session_id,
+        project_path: project_path.to_string(),
+        cwd,
+        git_branch,
+        cwd_estimated: false,
+        git_branch_estimated: false,
+        first_message,
+        timestamp_local: timestamp.as_deref().and_then(format_local_timestamp),
+        timestamp,
+        message_count,
+        file_size_bytes,
+        line_count,
+        ended_with_error: last_entry.as_ref().map(ended_with_error).unwrap_or(false),
+    }))
+}
+
+/// Format an RFC3339 timestamp in the system's local time zone, with the UTC offset
+/// (e.g. "2025-01-15 18:30:00 +09:00"). Logs recorded with any offset are converted.
+pub(crate) fn format_local_timestamp(timestamp: &str) -> Option<String> {
+    chrono::DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S %:z").to_string())
+}
+
+/// Whether the last entry of a session is an explicit error
+///
+/// Only explicit markers are trusted (API error messages and `system` entries with
+/// level "error"), so sessions that merely stopped mid-way are not flagged.
+fn ended_with_error(last_entry: &LogEntryMarker) -> bool {
+    last_entry.is_api_error_message == Some(true)
+        || (last_entry.entry_type.as_deref() == Some("system")
+            && last_entry.level.as_deref() == Some("error"))
+}
+
+/// Whether a user message was actually typed by the user (not a command wrapper or reminder)
+pub(crate) fn is_meaningful_user_text(text: &str) -> bool {
+    let text = text.trim();
+    !text.is_empty()
+        && !text.starts_with("<command-")
+        && !text.starts_with("<local-command-")
+        && !text.starts_with("<system-reminder>")
+        && !text.starts_with("Caveat:")
 }
 
 /// Extract text content from message content (handles both string and array formats)
@@ -310,6 +1256,72 @@ fn extract_text_content(content: &Option<serde_json::Value>) -> Option<String> {
     }
 }
 
+/// Image attached to a message (base64 encoded)
+#[derive(Debug, Clone)]
+pub(crate) struct ImageBlock {
+    pub media_type: String,
+    pub data: String,
+}
+
+/// Extract base64 image blocks from message content
+pub(crate) fn extract_images(content: &Option<serde_json::Value>) -> Vec<ImageBlock> {
+    let Some(serde_json::Value::Array(arr)) = content else {
+        return Vec::new();
+    };
+
+    arr.iter()
+        .filter_map(|item| {
+            let obj = item.as_object()?;
+            if obj.get("type").and_then(|t| t.as_str()) != Some("image") {
+                return None;
+            }
+            let source = obj.get("source")?.as_object()?;
+            if source.get("type").and_then(|t| t.as_str()) != Some("base64") {
+                return None;
+            }
+            Some(ImageBlock {
+                media_type: source.get("media_type")?.as_str()?.to_string(),
+                data: source.get("data")?.as_str()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Extract thinking blocks from message content, truncating long ones
+fn extract_thinking_content(content: &Option<serde_json::Value>) -> Option<String> {
+    let Some(serde_json::Value::Array(arr)) = content else {
+        return None;
+    };
+
+    let thoughts: Vec<String> = arr.iter()
+        .filter_map(|item| {
+            let obj = item.as_object()?;
+            if obj.get("type").and_then(|t| t.as_str()) != Some("thinking") {
+                return None;
+            }
+            let thinking = obj.get("thinking").and_then(|t| t.as_str())?;
+            if thinking.trim().is_empty() {
+                return None;
+            }
+            Some(truncate_chars(thinking, MAX_THINKING_CHARS))
+        })
+        .collect();
+
+    if thoughts.is_empty() {
+        None
+    } else {
+        Some(thoughts.join("\n"))
+    }
+}
+
+/// Truncate a string to at most `max_chars` characters, marking the cut with an ellipsis
+pub(crate) fn truncate_chars(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
 /// Launch Claude Code in interactive mode in a new terminal window
 #[tauri::command]
 pub fn launch_claude_code(
@@ -414,3 +1426,201 @@ pub fn resume_claude_code(
 
     Ok(())
 }
+
+/// Build the shell command that resumes a session: `cd '<cwd>' && claude --resume '<id>'`
+///
+/// The session must be found in the project of `cwd`, so sidechain sessions (which cannot
+/// be resumed) are always rejected rather than skipped when the project is missing.
+pub(crate) fn claude_resume_command(session_id: &str, cwd: &str) -> Result<String, String> {
+    // Session ids are UUIDs; anything else is not something we want to type into a shell
+    if session_id.is_empty()
+        || !session_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!("Invalid session id: {}", session_id));
+    }
+
+    let project_dir = get_claude_project_dir(cwd)?;
+    let session_file = project_dir.join(format!("{}.jsonl", session_id));
+    if !session_file.exists() {
+        return Err(format!(
+            "Session {} not found in {}",
+            session_id,
+            project_dir.display()
+        ));
+    }
+    if is_sidechain_session(&session_file) {
+        return Err(format!(
+            "Session {} is a sidechain session and cannot be resumed",
+            session_id
+        ));
+    }
+
+    Ok(format!(
+        "cd {} && claude --resume {}",
+        shell_single_quote(cwd),
+        shell_single_quote(session_id)
+    ))
+}
+
+/// Quote a string for POSIX shells (single quotes, with embedded quotes as '\'')
+fn shell_single_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Result of archiving or deleting a project's sessions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionCleanupResult {
+    /// Number of session files written to the zip
+    pub archived_count: usize,
+    /// Number of session files moved to the Trash
+    pub deleted_count: usize,
+    /// Path of the created zip (None if none was written, e.g. there were no sessions)
+    pub output_zip: Option<String>,
+}
+
+/// Archive all sessions of a project into a zip, optionally moving the originals to the Trash
+#[tauri::command]
+pub fn archive_project_sessions(
+    project_path: String,
+    output_zip: String,
+    include_sidechain: bool,
+    delete_after: bool,
+) -> Result<SessionCleanupResult, String> {
+    let project_dir = get_claude_project_dir(&project_path)?;
+    let files = collect_session_files(&project_dir, include_sidechain)?;
+
+    let archived_count = zip_files(&files, Path::new(&output_zip))?;
+    let deleted_count = if delete_after { move_to_trash(&files)? } else { 0 };
+
+    Ok(SessionCleanupResult {
+        archived_count,
+        deleted_count,
+        output_zip: (archived_count > 0).then_some(output_zip),
+    })
+}
+
+/// Move all sessions of a project to the Trash
+///
+/// With `require_backup`, a `backup_zip` path is mandatory and the zip is written
+/// before anything is deleted.
+#[tauri::command]
+pub fn delete_project_sessions(
+    project_path: String,
+    include_sidechain: bool,
+    require_backup: bool,
+    backup_zip: Option<String>,
+) -> Result<SessionCleanupResult, String> {
+    if require_backup && backup_zip.is_none() {
+        return Err("A backup zip path is required before deleting sessions".to_string());
+    }
+
+    let project_dir = get_claude_project_dir(&project_path)?;
+    let files = collect_session_files(&project_dir, include_sidechain)?;
+
+    let archived_count = match backup_zip {
+        Some(ref zip_path) => zip_files(&files, Path::new(zip_path))?,
+        None => 0,
+    };
+    let deleted_count = move_to_trash(&files)?;
+
+    Ok(SessionCleanupResult {
+        archived_count,
+        deleted_count,
+        output_zip: backup_zip.filter(|_| archived_count > 0),
+    })
+}
+
+/// Session files (*.jsonl) of a project directory, optionally including sidechain sessions
+fn collect_session_files(project_dir: &Path, include_sidechain: bool) -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
+
+    for entry in fs::read_dir(project_dir).map_err(|e| e.to_string())? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.extension().map(|ext| ext != "jsonl").unwrap_or(true) {
+            continue;
+        }
+        if !include_sidechain && is_sidechain_session(&path) {
+            continue;
+        }
+        files.push(path);
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Whether a session file was created by a subagent (checked on its first entry)
+pub(crate) fn is_sidechain_session(path: &Path) -> bool {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| {
+            content
+                .lines()
+                .next()
+                .and_then(|line| serde_json::from_str::<ClaudeLogEntry>(line).ok())
+        })
+        .map(|entry| entry.is_sidechain == Some(true))
+        .unwrap_or(false)
+}
+
+/// Write files into a zip with the system `zip` command (flat, without directories)
+fn zip_files(files: &[PathBuf], output_zip: &Path) -> Result<usize, String> {
+    if files.is_empty() {
+        return Ok(0);
+    }
+    if output_zip.exists() {
+        return Err(format!("Output file already exists: {}", output_zip.display()));
+    }
+    if let Some(parent) = output_zip.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    let output = Command::new("zip")
+        .arg("-q")
+        .arg("-j")
+        .arg(output_zip)
+        .args(files)
+        .output()
+        .map_err(|e| format!("Failed to execute zip: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to create zip: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(files.len())
+}
+
+/// Move files to the Trash via Finder so they can be restored
+fn move_to_trash(files: &[PathBuf]) -> Result<usize, String> {
+    if files.is_empty() {
+        return Ok(0);
+    }
+
+    let items = files
+        .iter()
+        .map(|path| {
+            let escaped = path.to_string_lossy().replace('\\', "\\\\").replace('"', "\\\"");
+            format!("POSIX file \"{}\"", escaped)
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let apple_script = format!(r#"tell application "Finder" to delete {{{}}}"#, items);
+
+    let output = Command::new("osascript")
+        .arg("-e")
+        .arg(&apple_script)
+        .output()
+        .map_err(|e| format!("Failed to execute osascript: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to move sessions to Trash: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(files.len())
+}
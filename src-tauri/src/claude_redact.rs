@@ -0,0 +1,102 @@
+use crate::claude_logs::{self, ConversationMessage};
+use regex::{Captures, Regex};
+
+/// Text that replaces each redacted match
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Strings that look like API keys or access tokens
+const API_KEY_PATTERNS: &[&str] = &[
+    // Anthropic, OpenAI
+    r"\bsk-(?:ant-|proj-)?[A-Za-z0-9_-]{20,}",
+    // GitHub
+    r"\bgh[pousr]_[A-Za-z0-9]{36,}",
+    r"\bgithub_pat_[A-Za-z0-9_]{22,}",
+    // AWS access key IDs
+    r"\b(?:AKIA|ASIA)[0-9A-Z]{16}\b",
+    // Slack
+    r"\bxox[abprs]-[A-Za-z0-9-]{10,}",
+    // Google
+    r"\bAIza[0-9A-Za-z_-]{35}",
+    // Authorization headers
+    r"(?i)(?P<keep>\bbearer\s+)[A-Za-z0-9._~+/=-]{20,}",
+    // Environment variables and settings whose name suggests a secret (the name is kept)
+    r#"(?i)(?P<keep>\b[A-Z0-9_]*(?:API_?KEY|TOKEN|SECRET|PASSWORD|PASSWD)[A-Z0-9_]*["']?\s*[=:]\s*)["']?[^\s"',;]{8,}"#,
+];
+
+/// Replaces secrets in exported text with `[REDACTED]` and counts the replacements
+///
+/// A named group `keep` in a pattern is left in place (e.g. the variable name of
+/// `API_KEY=...`), so only the rest of the match is replaced.
+pub struct Redactor {
+    patterns: Vec<Regex>,
+    count: usize,
+}
+
+impl Redactor {
+    /// Build from user patterns (regular expressions), optionally adding the built-in
+    /// API key and secret patterns
+    pub fn new(patterns: &[String], detect_api_keys: bool) -> Result<Self, String> {
+        let builtin = API_KEY_PATTERNS.iter().filter(|_| detect_api_keys);
+        let patterns = patterns
+            .iter()
+            .map(String::as_str)
+            .chain(builtin.copied())
+            .map(|pattern| {
+                Regex::new(pattern)
+                    .map_err(|e| format!("Invalid redact pattern {}: {}", pattern, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { patterns, count: 0 })
+    }
+
+    /// Replace every match of every pattern
+    pub fn redact(&mut self, text: &str) -> String {
+        let mut text = text.to_string();
+        for pattern in &self.patterns {
+            let count = &mut self.count;
+            text = pattern
+                .replace_all(&text, |captures: &Captures| {
+                    *count += 1;
+                    let keep = captures.name("keep").map_or("", |m| m.as_str());
+                    format!("{}{}", keep, REDACTED)
+                })
+                .into_owned();
+        }
+        text
+    }
+
+    /// How many matches have been replaced so far
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+/// A message without the text Claude Code injects into the conversation
+///
+/// `<system-reminder>` blocks are cut out, and user messages that are command wrappers
+/// or caveats (or nothing but reminders) are dropped entirely (None).
+pub fn without_system_text(message: &ConversationMessage) -> Option<ConversationMessage> {
+    let content = strip_blocks(&message.content, "<system-reminder>", "</system-reminder>");
+    if message.role == "user" && !claude_logs::is_meaningful_user_text(&content) {
+        return None;
+    }
+    Some(ConversationMessage {
+        content,
+        ..message.clone()
+    })
+}
+
+/// Remove every `open ... close` block (an unclosed block runs to the end)
+fn strip_blocks(text: &str, open: &str, close: &str) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(open) {
+        result.push_str(&rest[..start]);
+        rest = match rest[start..].find(close) {
+            Some(end) => &rest[start + end + close.len()..],
+            None => "",
+        };
+    }
+    result.push_str(rest);
+    result.trim().to_string()
+}
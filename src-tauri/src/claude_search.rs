@@ -0,0 +1,375 @@
+use crate::claude_logs::{self, ConversationMessage, SessionSummary};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Number of hits returned when no limit is given
+const DEFAULT_SEARCH_LIMIT: usize = 100;
+
+/// Characters shown before and after the match in a hit's snippet
+const SNIPPET_CONTEXT_CHARS: usize = 40;
+
+/// One message matching a search query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHit {
+    /// Claude project directory of the session
+    pub project_path: String,
+    pub session_id: String,
+    pub role: String,
+    /// The matching part of the message with some surrounding context
+    pub snippet: String,
+    pub timestamp: String,
+    pub timestamp_local: Option<String>,
+}
+
+/// A session located by its ID alone
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FoundSession {
+    /// Claude project directory containing the session
+    pub project_path: String,
+    pub session: SessionSummary,
+}
+
+/// Inverted index from character bigrams to the session files containing them
+///
+/// Bigrams work for both space-separated languages and Japanese, and any substring of
+/// two or more characters can be looked up. The index only narrows down the files to
+/// scan; hits are always confirmed against the actual messages.
+#[derive(Default)]
+struct InvertedIndex {
+    postings: HashMap<String, HashSet<PathBuf>>,
+    /// mtime and bigrams of every indexed file (to detect changes and remove stale postings)
+    files: HashMap<PathBuf, (SystemTime, Vec<String>)>,
+}
+
+impl InvertedIndex {
+    fn insert(&mut self, path: PathBuf, modified: SystemTime) {
+        self.remove(&path);
+
+        let tokens: Vec<String> = session_tokens(&path).into_iter().collect();
+        for token in &tokens {
+            self.postings.entry(token.clone()).or_default().insert(path.clone());
+        }
+        self.files.insert(path, (modified, tokens));
+    }
+
+    fn remove(&mut self, path: &Path) {
+        let Some((_, tokens)) = self.files.remove(path) else {
+            return;
+        };
+        for token in tokens {
+            if let Some(paths) = self.postings.get_mut(&token) {
+                paths.remove(path);
+                if paths.is_empty() {
+                    self.postings.remove(&token);
+                }
+            }
+        }
+    }
+
+    /// Files that may contain the query (all files if the query is too short to look up)
+    fn candidates(&self, query: &str) -> Vec<PathBuf> {
+        let tokens = bigrams(&query.to_lowercase());
+        if tokens.is_empty() {
+            return self.files.keys().cloned().collect();
+        }
+
+        let mut postings: Vec<&HashSet<PathBuf>> = Vec::with_capacity(tokens.len());
+        for token in &tokens {
+            match self.postings.get(token) {
+                Some(paths) => postings.push(paths),
+                None => return Vec::new(),
+            }
+        }
+        // Intersect starting from the rarest bigram
+        postings.sort_by_key(|paths| paths.len());
+        let Some((first, rest)) = postings.split_first() else {
+            return Vec::new();
+        };
+        first
+            .iter()
+            .filter(|path| rest.iter().all(|paths| paths.contains(*path)))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Full-text search over Claude session logs
+///
+/// Until `build` has been called every search scans all session files. Once built,
+/// each search first applies the changes since the last search (new, modified and
+/// deleted files) and then only scans the files the index points to.
+pub struct SearchIndex {
+    index: Mutex<Option<InvertedIndex>>,
+}
+
+impl SearchIndex {
+    pub fn new() -> Self {
+        Self {
+            index: Mutex::new(None),
+        }
+    }
+
+    /// (Re)build the index from scratch and return the number of indexed sessions
+    pub fn build(&self) -> Result<usize, String> {
+        let projects_dir = claude_logs::get_claude_logs_dir()?;
+
+        // Build without holding the lock so searches keep working meanwhile
+        let mut index = InvertedIndex::default();
+        for (path, modified) in claude_logs::snapshot_session_mtimes(&projects_dir) {
+            index.insert(path, modified);
+        }
+        let count = index.files.len();
+
+        *self.index.lock().map_err(|e| e.to_string())? = Some(index);
+        Ok(count)
+    }
+
+    /// Search messages containing `query` (case-insensitive), newest sessions first
+    /// `project_path` limits the search to one project (a cwd or a project directory)
+    pub fn search(
+        &self,
+        query: &str,
+        project_path: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<Vec<SearchHit>, String> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let projects_dir = claude_logs::get_claude_logs_dir()?;
+        let project_dir = match project_path {
+            Some(path) => Some(resolve_project_dir(path)?),
+            None => None,
+        };
+        let snapshot = claude_logs::snapshot_session_mtimes(&projects_dir);
+
+        let candidates: Vec<PathBuf> = {
+            let mut index = self.index.lock().map_err(|e| e.to_string())?;
+            match index.as_mut() {
+                Some(index) => {
+                    refresh(index, &snapshot);
+                    index.candidates(query)
+                }
+                // Not built yet: fall back to scanning every session
+                None => snapshot.keys().cloned().collect(),
+            }
+        };
+
+        let mut candidates: Vec<(PathBuf, SystemTime)> = candidates
+            .into_iter()
+            .filter(|path| match project_dir {
+                Some(ref dir) => path.parent() == Some(dir.as_path()),
+                None => true,
+            })
+            .filter_map(|path| snapshot.get(&path).map(|modified| (path, *modified)))
+            .collect();
+        candidates.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+
+        let limit = limit.unwrap_or(DEFAULT_SEARCH_LIMIT);
+        let mut hits = Vec::new();
+
+        for (path, _) in candidates {
+            for message in session_messages(&path) {
+                if let Some(snippet) = find_snippet(&message.content, query) {
+                    hits.push(SearchHit {
+                        project_path: path
+                            .parent()
+                            .map(|p| p.to_string_lossy().to_string())
+                            .unwrap_or_default(),
+                        session_id: path
+                            .file_stem()
+                            .and_then(|n| n.to_str())
+                            .unwrap_or("")
+                            .to_string(),
+                        role: message.role,
+                        snippet,
+                        timestamp: message.timestamp,
+                        timestamp_local: message.timestamp_local,
+                    });
+                    if hits.len() >= limit {
+                        return Ok(hits);
+                    }
+                }
+            }
+        }
+
+        Ok(hits)
+    }
+
+    /// Find the project a session belongs to from its ID alone
+    ///
+    /// Fails if no project has the session, or if more than one does (the error lists
+    /// them), rather than guessing.
+    pub fn find_session(&self, session_id: &str) -> Result<FoundSession, String> {
+        let session_id = session_id.trim();
+        // The ID becomes a file name, so it must not reach outside the project directories
+        let invalid = session_id.is_empty() || session_id.starts_with('.');
+        if invalid || session_id.contains(['/', '\\']) {
+            return Err(format!("Invalid session ID: {:?}", session_id));
+        }
+
+        let mut found = Vec::new();
+        for path in self.session_files(session_id)? {
+            let Some(project_dir) = path.parent() else {
+                continue;
+            };
+            let project_path = project_dir.to_string_lossy().to_string();
+            // Sidechain sessions are not listed anywhere, so they are not found either
+            if let Some(session) = claude_logs::summarize_session_file(&path, &project_path)? {
+                found.push(FoundSession {
+                    project_path,
+                    session,
+                });
+            }
+        }
+
+        match found.len() {
+            0 => Err(format!(
+                "Session {} not found in any Claude project",
+                session_id
+            )),
+            1 => Ok(found.remove(0)),
+            n => Err(format!(
+                "Session {} found in {} projects: {}",
+                session_id,
+                n,
+                found
+                    .iter()
+                    .map(|found| found.project_path.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        }
+    }
+
+    /// Session files named after `session_id`, in any project
+    ///
+    /// The built index already knows every session file, so the project directories are
+    /// only listed when it has not been built or does not know the session yet.
+    fn session_files(&self, session_id: &str) -> Result<Vec<PathBuf>, String> {
+        let file_name = format!("{}.jsonl", session_id);
+
+        let indexed: Vec<PathBuf> = {
+            let index = self.index.lock().map_err(|e| e.to_string())?;
+            index
+                .as_ref()
+                .map(|index| {
+                    index
+                        .files
+                        .keys()
+                        .filter(|path| {
+                            path.file_name().and_then(|n| n.to_str()) == Some(file_name.as_str())
+                        })
+                        .filter(|path| path.is_file())
+                        .cloned()
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        if !indexed.is_empty() {
+            return Ok(indexed);
+        }
+
+        let projects_dir = claude_logs::get_claude_logs_dir()?;
+        let projects = fs::read_dir(&projects_dir).map_err(|e| e.to_string())?;
+        Ok(projects
+            .flatten()
+            .map(|project| project.path().join(&file_name))
+            .filter(|path| path.is_file())
+            .collect())
+    }
+}
+
+impl Default for SearchIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Apply the differences between the index and the current session files
+fn refresh(index: &mut InvertedIndex, snapshot: &HashMap<PathBuf, SystemTime>) {
+    let removed: Vec<PathBuf> = index
+        .files
+        .keys()
+        .filter(|path| !snapshot.contains_key(*path))
+        .cloned()
+        .collect();
+    for path in removed {
+        index.remove(&path);
+    }
+
+    for (path, modified) in snapshot {
+        let unchanged = index
+            .files
+            .get(path)
+            .map(|(indexed, _)| indexed == modified)
+            .unwrap_or(false);
+        if !unchanged {
+            index.insert(path.clone(), *modified);
+        }
+    }
+}
+
+/// Accept either a Claude project directory or the cwd it belongs to
+fn resolve_project_dir(project_path: &str) -> Result<PathBuf, String> {
+    let path = Path::new(project_path);
+    if path.is_dir() && path.parent() == claude_logs::get_claude_logs_dir().ok().as_deref() {
+        return Ok(path.to_path_buf());
+    }
+    claude_logs::get_claude_project_dir(project_path)
+}
+
+/// Text messages of a session (empty for sidechain sessions and unreadable files)
+fn session_messages(path: &Path) -> Vec<ConversationMessage> {
+    if claude_logs::is_sidechain_session(path) {
+        return Vec::new();
+    }
+    claude_logs::read_session_content(path)
+        .map(|content| claude_logs::parse_session_messages(&content, false))
+        .unwrap_or_default()
+}
+
+fn session_tokens(path: &Path) -> HashSet<String> {
+    let mut tokens = HashSet::new();
+    for message in session_messages(path) {
+        tokens.extend(bigrams(&message.content.to_lowercase()));
+    }
+    tokens
+}
+
+/// Pairs of adjacent characters, skipping pairs that contain whitespace
+fn bigrams(text: &str) -> HashSet<String> {
+    let chars: Vec<char> = text.chars().collect();
+    chars
+        .windows(2)
+        .filter(|pair| !pair[0].is_whitespace() && !pair[1].is_whitespace())
+        .map(|pair| pair.iter().collect())
+        .collect()
+}
+
+/// The first case-insensitive match of `query` with surrounding context
+fn find_snippet(content: &str, query: &str) -> Option<String> {
+    let lowercase = |c: &char| c.to_lowercase().next().unwrap_or(*c);
+    let chars: Vec<char> = content.chars().collect();
+    let lower: Vec<char> = chars.iter().map(lowercase).collect();
+    let needle: Vec<char> = query.chars().map(|c| lowercase(&c)).collect();
+
+    let start = lower.windows(needle.len()).position(|window| window == needle.as_slice())?;
+    let from = start.saturating_sub(SNIPPET_CONTEXT_CHARS);
+    let to = (start + needle.len() + SNIPPET_CONTEXT_CHARS).min(chars.len());
+
+    let mut snippet: String = chars[from..to].iter().collect();
+    snippet = snippet.split_whitespace().collect::<Vec<_>>().join(" ");
+    if from > 0 {
+        snippet.insert(0, '…');
+    }
+    if to < chars.len() {
+        snippet.push('…');
+    }
+    Some(snippet)
+}
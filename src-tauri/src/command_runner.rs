@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Default timeout for a one-shot command
+const DEFAULT_TIMEOUT_MS: u64 = 30_000;
+
+/// Default cap for each of stdout/stderr
+const DEFAULT_MAX_OUTPUT_BYTES: usize = 1024 * 1024;
+
+/// How often the child is polled for exit while waiting
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// How long to wait for the output readers after the child exits
+/// (background grandchildren may keep the pipes open forever)
+const READER_GRACE_PERIOD: Duration = Duration::from_secs(1);
+
+/// Result of a one-shot command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandOutput {
+    pub stdout: String,
+    pub stderr: String,
+    /// Exit code (None if killed by a signal or on timeout)
+    pub exit_code: Option<i32>,
+    /// The command was killed because it exceeded the timeout
+    pub timed_out: bool,
+    /// stdout or stderr exceeded the size limit and was cut off
+    pub truncated: bool,
+}
+
+/// Run a command once and collect its output, without a PTY or shell
+///
+/// Unlike the interactive terminal sessions, the command is executed directly with
+/// `std::process::Command`. It is killed after `timeout_ms`, and each output stream is
+/// capped at `max_output_bytes` (the rest is drained and discarded).
+#[tauri::command(async)]
+pub fn run_command(
+    cwd: String,
+    cmd: String,
+    args: Vec<String>,
+    env: Option<HashMap<String, String>>,
+    timeout_ms: Option<u64>,
+    max_output_bytes: Option<usize>,
+) -> Result<CommandOutput, String> {
+    let timeout = Duration::from_millis(timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS));
+    let max_output_bytes = max_output_bytes.unwrap_or(DEFAULT_MAX_OUTPUT_BYTES);
+
+    let mut command = Command::new(&cmd);
+    command
+        .args(&args)
+        .current_dir(&cwd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(env) = env {
+        command.envs(env);
+    }
+
+    let mut child = command
+        .spawn()
+        .map_err(|e| format!("Failed to run {}: {}", cmd, e))?;
+
+    // Read both streams on their own threads so a full pipe never blocks the child
+    let stdout = child.stdout.take().ok_or("Failed to capture stdout")?;
+    let stderr = child.stderr.take().ok_or("Failed to capture stderr")?;
+    let stdout_buf = Arc::new(Mutex::new(CappedBuffer::new(max_output_bytes)));
+    let stderr_buf = Arc::new(Mutex::new(CappedBuffer::new(max_output_bytes)));
+    let readers = [
+        spawn_reader(stdout, stdout_buf.clone()),
+        spawn_reader(stderr, stderr_buf.clone()),
+    ];
+
+    let started_at = Instant::now();
+    let mut timed_out = false;
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(|e| e.to_string())? {
+            break Some(status);
+        }
+        if started_at.elapsed() >= timeout {
+            timed_out = true;
+            let _ = child.kill();
+            let _ = child.wait();
+            break None;
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    let exited_at = Instant::now();
+    while readers.iter().any(|r| !r.is_finished()) && exited_at.elapsed() < READER_GRACE_PERIOD {
+        thread::sleep(POLL_INTERVAL);
+    }
+
+    let (stdout, stdout_truncated) = stdout_buf.lock().map_err(|e| e.to_string())?.to_output();
+    let (stderr, stderr_truncated) = stderr_buf.lock().map_err(|e| e.to_string())?.to_output();
+
+    Ok(CommandOutput {
+        stdout,
+        stderr,
+        exit_code: status.and_then(|s| s.code()),
+        timed_out,
+        truncated: stdout_truncated || stderr_truncated,
+    })
+}
+
+/// Output buffer that keeps at most `max_bytes` and remembers whether anything was dropped
+struct CappedBuffer {
+    bytes: Vec<u8>,
+    max_bytes: usize,
+    truncated: bool,
+}
+
+impl CappedBuffer {
+    fn new(max_bytes: usize) -> Self {
+        Self {
+            bytes: Vec::new(),
+            max_bytes,
+            truncated: false,
+        }
+    }
+
+    fn push(&mut self, chunk: &[u8]) {
+        let room = self.max_bytes.saturating_sub(self.bytes.len());
+        if chunk.len() > room {
+            self.truncated = true;
+        }
+        self.bytes.extend_from_slice(&chunk[..chunk.len().min(room)]);
+    }
+
+    /// Returns the (lossily decoded) text and whether anything was discarded
+    fn to_output(&self) -> (String, bool) {
+        (String::from_utf8_lossy(&self.bytes).into_owned(), self.truncated)
+    }
+}
+
+/// Drain a stream into a capped buffer on a background thread
+fn spawn_reader<R: Read + Send + 'static>(
+    mut reader: R,
+    buffer: Arc<Mutex<CappedBuffer>>,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        let mut chunk = [0u8; 8192];
+        loop {
+            match reader.read(&mut chunk) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => {
+                    if let Ok(mut buffer) = buffer.lock() {
+                        buffer.push(&chunk[..n]);
+                    }
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,87 @@
+use serde::Serialize;
+use tauri::{Emitter, Runtime};
+
+// Events sent to the frontend, named `domain:event`
+// (the payload structs live in each domain's module)
+
+pub const WINDOW_VISIBILITY_CHANGED: &str = "window:visibility-changed";
+pub const SIDEBAR_VISIBILITY_CHANGED: &str = "sidebar:visibility-changed";
+pub const SIDEBAR_EDGE_CHANGED: &str = "sidebar:edge-changed";
+pub const WINDOW_REOPENED: &str = "window:reopened";
+
+pub const SPEECH_STATE_CHANGED: &str = "speech:state-changed";
+pub const SPEECH_RESULT: &str = "speech:result";
+pub const SPEECH_SENTENCE: &str = "speech:sentence";
+pub const SPEECH_SEGMENT_BOUNDARY: &str = "speech:segment-boundary";
+pub const SPEECH_AUDIO_LEVEL: &str = "speech:audio-level";
+pub const SPEECH_HANDS_FREE_STATE_CHANGED: &str = "speech:hands-free-state-changed";
+pub const SPEECH_DISABLED: &str = "speech:disabled";
+
+pub const CLAUDE_SESSION_UPDATED: &str = "claude:session-updated";
+pub const CLAUDE_MESSAGE: &str = "claude:message";
+pub const CLAUDE_READ_COMPLETE: &str = "claude:read-complete";
+
+pub const SYSTEM_APPEARANCE_CHANGED: &str = "system:appearance-changed";
+
+pub const PTY_INPUT: &str = "pty:input";
+pub const PTY_ACTIVE_CHANGED: &str = "pty:active-changed";
+
+/// Names used before the `domain:event` scheme (new name, legacy name)
+///
+/// The legacy names are still emitted alongside the new ones so listeners that have
+/// not moved over keep working; remove an entry once nothing listens to it anymore.
+pub const LEGACY_EVENT_NAMES: &[(&str, &str)] = &[
+    (WINDOW_VISIBILITY_CHANGED, "window-visibility-changed"),
+    (SPEECH_STATE_CHANGED, "speech-state-changed"),
+    (SPEECH_RESULT, "speech-recognition-result"),
+    (SPEECH_SENTENCE, "speech-sentence"),
+    (SPEECH_AUDIO_LEVEL, "speech-audio-level"),
+    (
+        SPEECH_HANDS_FREE_STATE_CHANGED,
+        "speech-hands-free-state-changed",
+    ),
+    (SPEECH_DISABLED, "speech-disabled"),
+    (CLAUDE_SESSION_UPDATED, "claude-session-updated"),
+    (CLAUDE_MESSAGE, "claude-message"),
+    (CLAUDE_READ_COMPLETE, "claude-read-complete"),
+    (SYSTEM_APPEARANCE_CHANGED, "system-appearance-changed"),
+];
+
+/// The legacy name of `event`, if it had one
+pub fn legacy_name(event: &str) -> Option<&'static str> {
+    LEGACY_EVENT_NAMES
+        .iter()
+        .find(|(name, _)| *name == event)
+        .map(|(_, legacy)| *legacy)
+}
+
+/// Emit `event`, and its legacy name with the same payload
+pub fn emit<R, E, S>(emitter: &E, event: &str, payload: S) -> tauri::Result<()>
+where
+    R: Runtime,
+    E: Emitter<R>,
+    S: Serialize + Clone,
+{
+    emit_with_legacy_payload(emitter, event, payload.clone(), payload)
+}
+
+/// Emit `event`, and its legacy name with the payload it used to carry
+/// (for events whose bare-value payload is now wrapped in a struct)
+pub fn emit_with_legacy_payload<R, E, S, L>(
+    emitter: &E,
+    event: &str,
+    payload: S,
+    legacy_payload: L,
+) -> tauri::Result<()>
+where
+    R: Runtime,
+    E: Emitter<R>,
+    S: Serialize + Clone,
+    L: Serialize + Clone,
+{
+    emitter.emit(event, payload)?;
+    if let Some(legacy) = legacy_name(event) {
+        emitter.emit(legacy, legacy_payload)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use std::fs;
+use std::path::Path;
+
+/// Create the directory if needed and make sure files can be written into it
+///
+/// `what` names the directory in error messages (e.g. "export dir").
+pub fn ensure_writable(dir: &Path, what: &str) -> Result<(), String> {
+    fs::create_dir_all(dir)
+        .map_err(|e| format!("Cannot create {} {}: {}", what, dir.display(), e))?;
+
+    let probe = dir.join(".funhou-write-test");
+    fs::write(&probe, b"")
+        .map_err(|e| format!("Cannot write to {} {}: {}", what, dir.display(), e))?;
+    let _ = fs::remove_file(&probe);
+
+    Ok(())
+}
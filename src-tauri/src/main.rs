@@ -1,22 +1,53 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod app_status;
 mod autohide;
+mod claude_edits;
+mod claude_export;
 mod claude_logs;
+mod claude_redact;
+mod claude_search;
+mod command_runner;
+mod events;
+mod fs_util;
 mod paste_to_app;
+mod pty;
 mod speech;
+mod system_appearance;
+mod tray;
 
-use autohide::{AutohideConfig, AutohideManager, ScreenEdge};
-use speech::{SpeechManager, SpeechRecognitionState};
+use autohide::config::WindowVisibilityPayload;
+use autohide::event_tap::TapEvent;
+use autohide::{
+    AutohideConfig, AutohideConfigResponse, AutohideManager, AutohideWorker, DisplayInfo, EasingKind,
+    ScreenEdge, VerticalAnchor, VisibilityReason, CONFIG_FILE_NAME,
+};
+use claude_logs::{ClaudePoller, SessionStreamer};
+use claude_search::{FoundSession, SearchHit, SearchIndex};
+use pty::{PtyActivePayload, PtyInputPayload, PtySessions};
+use speech::{
+    DataDirSettings, DecorationRule, HandsFreeAction, ResultFormat, SpeechManager, SpeechProfile,
+    SpeechProfiles, SpeechRecognitionState, SpeechSession, SleepPrevention,
+    DATA_DIR_SETTINGS_FILE_NAME, PROFILES_FILE_NAME,
+};
 use font_kit::source::SystemSource;
 use std::collections::HashSet;
+use std::str::FromStr;
 use std::sync::Mutex;
-use tauri::{Manager, State, WebviewUrl, WebviewWindowBuilder};
+use tauri::{Manager, RunEvent, State, WebviewUrl, WebviewWindowBuilder};
 
 /// Application state
 pub struct AppState {
+    /// Never locked on the main thread: the manager calls window getters, which wait for
+    /// the main thread (commands that use it are `async`, other work goes through the worker)
     autohide_manager: Mutex<AutohideManager>,
+    autohide_worker: AutohideWorker,
     speech_manager: Mutex<SpeechManager>,
+    claude_poller: Mutex<ClaudePoller>,
+    claude_streamer: SessionStreamer,
+    claude_search: SearchIndex,
+    pty_sessions: PtySessions,
 }
 
 #[tauri::command]
@@ -38,10 +69,115 @@ fn get_system_fonts() -> Result<Vec<String>, String> {
     Ok(fonts)
 }
 
-/// Enable or disable autohide mode
+/// The macOS appearance ("dark" or "light")
+/// Changes are sent as `system:appearance-changed`
 #[tauri::command]
+fn get_system_appearance() -> Result<system_appearance::SystemAppearance, String> {
+    system_appearance::current()
+}
+
+/// Start the global mouse tap that reveals the sidebar when its handle is clicked
+/// (or the docked edge is hovered, with hover-reveal on)
+///
+/// Hovering only reveals the window once the cursor has stayed in the trigger for
+/// `reveal_delay_ms`, so grazing the edge does nothing. A window revealed by hovering
+/// is hidden again `auto_hide_delay_ms` after the cursor leaves it; coming back within
+/// the delay keeps it shown.
+fn start_autohide_event_tap(manager: &AutohideManager, app: &tauri::AppHandle, window: &tauri::Window) {
+    let app_handle = app.clone();
+    let window = window.clone();
+    // The tap thread only queues the event; the manager is locked on the worker
+    let result = manager.start_event_tap(move |event| {
+        let app = app_handle.clone();
+        let window = window.clone();
+        let state = app_handle.state::<AppState>();
+        state
+            .autohide_worker
+            .post(move || handle_autohide_tap_event(&app, &window, event));
+    });
+
+    // Manual toggling still works without the tap, so this is not fatal
+    if let Err(e) = result {
+        eprintln!("[autohide] {}", e);
+    }
+}
+
+/// Act on what the event tap observed (runs on the autohide worker)
+fn handle_autohide_tap_event(app: &tauri::AppHandle, window: &tauri::Window, event: TapEvent) {
+    let state = app.state::<AppState>();
+    let manager = match state.autohide_manager.lock() {
+        Ok(manager) => manager,
+        Err(_) => return,
+    };
+    let result = match event {
+        TapEvent::HandleClicked if !manager.is_visible() => {
+            manager.show(window, VisibilityReason::Toggle)
+        }
+        TapEvent::TriggerEntered if !manager.is_visible() => {
+            manager.begin_reveal().and_then(|(token, delay)| {
+                if delay.is_zero() {
+                    return manager.finish_reveal(window, token);
+                }
+                // Wait without the lock; the reveal itself runs on the worker again
+                let app_handle = app.clone();
+                let window = window.clone();
+                state.autohide_worker.post_after(delay, move || {
+                    let state = app_handle.state::<AppState>();
+                    let Ok(manager) = state.autohide_manager.lock() else {
+                        return;
+                    };
+                    if let Err(e) = manager.finish_reveal(&window, token) {
+                        eprintln!("[autohide] Failed to reveal window: {}", e);
+                    }
+                });
+                Ok(())
+            })
+        }
+        TapEvent::TriggerLeft => manager.cancel_reveal(),
+        TapEvent::WindowEntered => manager.cancel_auto_hide(),
+        TapEvent::WindowLeft => manager.begin_auto_hide().map(|(token, delay)| {
+            let app_handle = app.clone();
+            let window = window.clone();
+            state.autohide_worker.post_after(delay, move || {
+                let state = app_handle.state::<AppState>();
+                let Ok(manager) = state.autohide_manager.lock() else {
+                    return;
+                };
+                if let Err(e) = manager.finish_auto_hide(&window, token) {
+                    eprintln!("[autohide] Failed to hide window: {}", e);
+                }
+            });
+        }),
+        _ => Ok(()),
+    };
+    if let Err(e) = result {
+        eprintln!("[autohide] Failed to handle {:?}: {}", event, e);
+    }
+}
+
+/// Run `job` with the autohide manager on the autohide worker
+///
+/// For work started on the main thread (window events), which must not wait for the
+/// manager lock.
+fn post_autohide_job<F>(app: &tauri::AppHandle, job: F)
+where
+    F: FnOnce(&AutohideManager) + Send + 'static,
+{
+    let app_handle = app.clone();
+    app.state::<AppState>().autohide_worker.post(move || {
+        let state = app_handle.state::<AppState>();
+        match state.autohide_manager.lock() {
+            Ok(manager) => job(&manager),
+            Err(e) => eprintln!("[autohide] Failed to lock the manager: {}", e),
+        };
+    });
+}
+
+/// Enable or disable autohide mode
+#[tauri::command(async)]
 fn set_autohide_enabled(
     enabled: bool,
+    app: tauri::AppHandle,
     window: tauri::Window,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
@@ -52,6 +188,7 @@ fn set_autohide_enabled(
 
     if enabled {
         manager.enable(&window)?;
+        start_autohide_event_tap(&manager, &app, &window);
     } else {
         manager.disable(&window)?;
     }
@@ -60,9 +197,12 @@ fn set_autohide_enabled(
 }
 
 /// Toggle sidebar visibility (Sidenotes-style)
+/// While autohide is disabled this enables it and hides the window at the edge;
+/// use `set_autohide_enabled(false)` to turn autohide off again
 /// Returns true if now visible, false if now hidden
-#[tauri::command]
+#[tauri::command(async)]
 fn toggle_sidebar(
+    app: tauri::AppHandle,
     window: tauri::Window,
     state: State<'_, AppState>,
 ) -> Result<bool, String> {
@@ -71,36 +211,309 @@ fn toggle_sidebar(
         .lock()
         .map_err(|e| e.to_string())?;
 
-    manager.toggle(&window)
+    let was_enabled = manager.get_config()?.enabled;
+    let visible = manager.toggle(&window)?;
+    if !was_enabled {
+        start_autohide_event_tap(&manager, &app, &window);
+    }
+
+    Ok(visible)
 }
 
-/// Set autohide edge (left or right)
-#[tauri::command]
-fn set_autohide_edge(edge: String, state: State<'_, AppState>) -> Result<(), String> {
+/// Show the sidebar (no-op if it is already visible)
+/// Returns true if now visible
+#[tauri::command(async)]
+fn show_sidebar(window: tauri::Window, state: State<'_, AppState>) -> Result<bool, String> {
     let manager = state
         .autohide_manager
         .lock()
         .map_err(|e| e.to_string())?;
 
-    let screen_edge = ScreenEdge::from_str(&edge);
-    manager.set_edge(screen_edge)?;
+    manager.show_if_hidden(&window)
+}
+
+/// Hide the sidebar at the edge (no-op if it is already hidden)
+/// Returns true if it is still visible (autohide is disabled or the sidebar is pinned)
+#[tauri::command(async)]
+fn hide_sidebar(window: tauri::Window, state: State<'_, AppState>) -> Result<bool, String> {
+    let manager = state
+        .autohide_manager
+        .lock()
+        .map_err(|e| e.to_string())?;
+
+    manager.hide_if_visible(&window)
+}
+
+/// Set autohide edge (left, right, top or bottom; anything else is an error)
+/// A change is reported through `sidebar:edge-changed`
+#[tauri::command(async)]
+fn set_autohide_edge(
+    edge: String,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let screen_edge = ScreenEdge::from_str(&edge)?;
+    let manager = state
+        .autohide_manager
+        .lock()
+        .map_err(|e| e.to_string())?;
+
+    manager.set_edge(&window, screen_edge)?;
 
     Ok(())
 }
 
-/// Get current autohide config
+/// Choose the autohide edge from the display layout and keep it automatic
+/// Returns the chosen edge; setting an edge manually turns this off again
+#[tauri::command(async)]
+fn set_autohide_edge_auto(window: tauri::Window, state: State<'_, AppState>) -> Result<ScreenEdge, String> {
+    let manager = state
+        .autohide_manager
+        .lock()
+        .map_err(|e| e.to_string())?;
+
+    manager.set_edge_auto(&window)
+}
+
+/// List active displays (ids can be passed to `move_autohide_to_display`)
 #[tauri::command]
-fn get_autohide_config(state: State<'_, AppState>) -> Result<AutohideConfig, String> {
+fn list_displays() -> Result<Vec<DisplayInfo>, String> {
+    autohide::window_controller::list_displays()
+}
+
+/// Move the sidebar to another display and autohide it on that display's edge
+#[tauri::command(async)]
+fn move_autohide_to_display(
+    display_id: u32,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let manager = state
+        .autohide_manager
+        .lock()
+        .map_err(|e| e.to_string())?;
+
+    manager.move_to_display(&window, display_id)
+}
+
+/// Set whether the sidebar takes focus when shown
+#[tauri::command(async)]
+fn set_autohide_steal_focus(steal_focus: bool, state: State<'_, AppState>) -> Result<(), String> {
+    let manager = state
+        .autohide_manager
+        .lock()
+        .map_err(|e| e.to_string())?;
+
+    manager.set_steal_focus_on_show(steal_focus)
+}
+
+/// Enable hover-reveal, optionally limited to the handle's vertical range
+/// Turning it off is an error while the hidden sidebar leaves no handle on screen
+#[tauri::command(async)]
+fn set_autohide_hover_reveal(
+    enabled: bool,
+    trigger_on_handle_only: bool,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let manager = state
+        .autohide_manager
+        .lock()
+        .map_err(|e| e.to_string())?;
+
+    manager.set_hover_reveal(&window, enabled, trigger_on_handle_only)
+}
+
+/// Set the thickness (points) of the strip along the docked edge that reveals the sidebar
+#[tauri::command(async)]
+fn set_autohide_trigger_zone_pixels(
+    pixels: f64,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
     let manager = state
         .autohide_manager
         .lock()
         .map_err(|e| e.to_string())?;
 
-    manager.get_config()
+    manager.set_trigger_zone_pixels(&window, pixels)
+}
+
+/// Set the easing curve of the slide animation (linear, ease_in, ease_out or ease_in_out)
+#[tauri::command(async)]
+fn set_autohide_easing(name: String, state: State<'_, AppState>) -> Result<(), String> {
+    let easing = EasingKind::from_name(&name).ok_or_else(|| format!("Unknown easing: {}", name))?;
+    let manager = state
+        .autohide_manager
+        .lock()
+        .map_err(|e| e.to_string())?;
+
+    manager.set_easing(easing)
+}
+
+/// Set how many pixels of the hidden sidebar stay on screen as the handle
+/// (0 hides it completely, which is an error while hover-reveal is off)
+#[tauri::command(async)]
+fn set_autohide_visible_pixels(
+    pixels: f64,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let manager = state
+        .autohide_manager
+        .lock()
+        .map_err(|e| e.to_string())?;
+
+    manager.set_visible_pixels(&window, pixels)
+}
+
+/// Make the sidebar occupy only a fraction of the display height on the left or right
+/// edge, anchored to the top or center (1.0 brings back the window's own height;
+/// an unknown anchor is an error)
+#[tauri::command(async)]
+fn set_autohide_height(
+    fraction: f64,
+    anchor: String,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let anchor = VerticalAnchor::from_str(&anchor)?;
+    let manager = state
+        .autohide_manager
+        .lock()
+        .map_err(|e| e.to_string())?;
+
+    manager.set_height(&window, fraction, anchor)
+}
+
+/// Resize the sidebar to `width` points (dragging its inner edge); the width is
+/// remembered with the autohide config
+/// Pass `persist: false` while dragging and save once at the end (default true)
+#[tauri::command(async)]
+fn set_sidebar_width(
+    width: u32,
+    persist: Option<bool>,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let manager = state
+        .autohide_manager
+        .lock()
+        .map_err(|e| e.to_string())?;
+
+    manager.set_sidebar_width(&window, width, persist.unwrap_or(true))
+}
+
+/// Set the space (points) left above and below the sidebar, which decides its height
+/// The window is resized and moved right away
+#[tauri::command(async)]
+fn set_sidebar_margins(
+    top: f64,
+    bottom: f64,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let manager = state
+        .autohide_manager
+        .lock()
+        .map_err(|e| e.to_string())?;
+
+    manager.set_margins(&window, top, bottom)
+}
+
+/// Set whether the docked sidebar sits flush against the edge (square corners on that
+/// side) and whether it keeps its window shadow
+#[tauri::command(async)]
+fn set_autohide_dock_appearance(
+    enabled: bool,
+    shadow: bool,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let manager = state
+        .autohide_manager
+        .lock()
+        .map_err(|e| e.to_string())?;
+
+    manager.set_dock_appearance(&window, enabled, shadow)
+}
+
+/// Keep the main window above other apps (remembered across restarts)
+#[tauri::command(async)]
+fn set_always_on_top(
+    enabled: bool,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let manager = state
+        .autohide_manager
+        .lock()
+        .map_err(|e| e.to_string())?;
+
+    manager.set_always_on_top(&window, enabled)
+}
+
+/// Pin the sidebar open so autohide stops hiding it, or unpin it
+#[tauri::command(async)]
+fn set_sidebar_pinned(
+    pinned: bool,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let manager = state
+        .autohide_manager
+        .lock()
+        .map_err(|e| e.to_string())?;
+
+    manager.set_pinned(&window, pinned)
+}
+
+/// Set whether the sidebar hides itself when the app loses focus
+#[tauri::command(async)]
+fn set_autohide_hide_on_blur(hide_on_blur: bool, state: State<'_, AppState>) -> Result<(), String> {
+    let manager = state
+        .autohide_manager
+        .lock()
+        .map_err(|e| e.to_string())?;
+
+    manager.set_hide_on_blur(hide_on_blur)
+}
+
+/// Set how long a hover-revealed sidebar stays after the cursor leaves it
+#[tauri::command(async)]
+fn set_autohide_delay(delay_ms: u64, state: State<'_, AppState>) -> Result<(), String> {
+    let manager = state
+        .autohide_manager
+        .lock()
+        .map_err(|e| e.to_string())?;
+
+    manager.set_auto_hide_delay(delay_ms)
+}
+
+/// Set how long the cursor has to rest on the docked edge before the sidebar is revealed
+#[tauri::command(async)]
+fn set_reveal_delay(delay_ms: u64, state: State<'_, AppState>) -> Result<(), String> {
+    let manager = state
+        .autohide_manager
+        .lock()
+        .map_err(|e| e.to_string())?;
+
+    manager.set_reveal_delay(delay_ms)
+}
+
+/// Get current autohide config (with whether the sidebar is pinned)
+#[tauri::command(async)]
+fn get_autohide_config(state: State<'_, AppState>) -> Result<AutohideConfigResponse, String> {
+    let manager = state
+        .autohide_manager
+        .lock()
+        .map_err(|e| e.to_string())?;
+
+    manager.get_config_response()
 }
 
 /// Check if sidebar is currently visible
-#[tauri::command]
+#[tauri::command(async)]
 fn is_sidebar_visible(state: State<'_, AppState>) -> Result<bool, String> {
     let manager = state
         .autohide_manager
@@ -196,15 +609,274 @@ fn start_speech_recognition(
 }
 
 /// Stop speech recognition
+/// Returns the path of the recorded audio file, if recording was enabled
 #[tauri::command]
 fn stop_speech_recognition(
     app: tauri::AppHandle,
     state: State<'_, AppState>,
-) -> Result<(), String> {
+) -> Result<Option<String>, String> {
     let manager = state.speech_manager.lock().map_err(|e| e.to_string())?;
     manager.stop_recognition(&app)
 }
 
+/// Start speech recognition if idle, stop it if listening
+/// Returns true if recognition was started, false if it was stopped
+#[tauri::command]
+fn toggle_speech_recognition(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<bool, String> {
+    // The manager lock is held from the state check through the transition
+    let manager = state.speech_manager.lock().map_err(|e| e.to_string())?;
+    manager.toggle_recognition(&app)
+}
+
+/// Set the file path to record audio to while recognizing (None to disable)
+/// Relative paths are saved under the `recordings` dir of the speech data dir
+#[tauri::command]
+fn set_speech_record_audio_path(
+    path: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let manager = state.speech_manager.lock().map_err(|e| e.to_string())?;
+    manager.set_record_audio_path(path)
+}
+
+/// Set the base directory for speech data such as recordings (None for app_data_dir)
+/// The setting is saved and restored on the next launch.
+#[tauri::command]
+fn set_speech_data_dir(
+    path: Option<String>,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let manager = state.speech_manager.lock().map_err(|e| e.to_string())?;
+    manager.set_data_dir(path.clone())?;
+    DataDirSettings { data_dir: path }.save(&speech_data_dir_settings_path(&app)?)
+}
+
+/// Path of the speech data dir settings file in the app config dir
+fn speech_data_dir_settings_path(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    let config_dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    Ok(config_dir.join(DATA_DIR_SETTINGS_FILE_NAME))
+}
+
+/// Use the speech data dir saved by `set_speech_data_dir`, if any
+fn restore_speech_data_dir(app: &tauri::AppHandle) -> Result<(), String> {
+    let settings = DataDirSettings::load(&speech_data_dir_settings_path(app)?)?;
+    let Some(path) = settings.data_dir else {
+        return Ok(());
+    };
+
+    let state = app.state::<AppState>();
+    let manager = state.speech_manager.lock().map_err(|e| e.to_string())?;
+    manager.set_data_dir(Some(path))
+}
+
+/// Enable or disable hands-free mode (recognition toggled by input level)
+#[tauri::command]
+fn set_speech_hands_free(
+    enabled: bool,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let manager = state.speech_manager.lock().map_err(|e| e.to_string())?;
+
+    if !enabled {
+        return manager.disable_hands_free(&app);
+    }
+
+    let app_handle = app.clone();
+    manager.enable_hands_free(&app, move |action: HandsFreeAction| {
+        let state = app_handle.state::<AppState>();
+        let manager = state.speech_manager.lock().map_err(|e| e.to_string())?;
+        manager.apply_hands_free_action(&app_handle, action)
+    })
+}
+
+/// Set the payload format of `speech:result` ("simple" or "web_speech")
+/// Unknown format names are rejected
+#[tauri::command]
+fn set_speech_result_format(format: String, state: State<'_, AppState>) -> Result<(), String> {
+    let format = ResultFormat::from_str(&format)?;
+    let manager = state.speech_manager.lock().map_err(|e| e.to_string())?;
+    manager.set_result_format(format)
+}
+
+/// Set which sleep to prevent while recognizing ("off", "system" or "display")
+/// Unknown kinds are rejected
+#[tauri::command]
+fn set_speech_sleep_prevention(kind: String, state: State<'_, AppState>) -> Result<(), String> {
+    let kind = SleepPrevention::from_str(&kind)?;
+    let manager = state.speech_manager.lock().map_err(|e| e.to_string())?;
+    manager.set_sleep_prevention(kind)
+}
+
+/// Enable or disable splitting final speech results into `speech:sentence` events
+/// `delimiters` overrides the language's default sentence delimiters
+#[tauri::command]
+fn set_speech_sentence_split(
+    enabled: bool,
+    delimiters: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let manager = state.speech_manager.lock().map_err(|e| e.to_string())?;
+    manager.set_sentence_split(enabled, delimiters)
+}
+
+/// Set the thresholds below which final speech results are discarded as noise
+/// (0 for both lets every final result through)
+#[tauri::command]
+fn set_speech_min_utterance(
+    min_chars: usize,
+    min_ms: u64,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let manager = state.speech_manager.lock().map_err(|e| e.to_string())?;
+    manager.set_min_utterance(min_chars, min_ms)
+}
+
+/// Set how long a pause between speech results starts a new speaker segment
+/// (0 turns segmenting off)
+#[tauri::command]
+fn set_speech_segment_pause(pause_ms: u64, state: State<'_, AppState>) -> Result<(), String> {
+    let manager = state.speech_manager.lock().map_err(|e| e.to_string())?;
+    manager.set_segment_pause(pause_ms)
+}
+
+/// Set the minimum interval between partial speech results sent to the frontend
+/// (only the latest partial in each interval is sent; final results are never delayed;
+/// 0 sends every partial)
+#[tauri::command]
+fn set_speech_partial_debounce(debounce_ms: u64, state: State<'_, AppState>) -> Result<(), String> {
+    let manager = state.speech_manager.lock().map_err(|e| e.to_string())?;
+    manager.set_partial_debounce(debounce_ms)
+}
+
+/// Enable or disable Markdown decoration of final speech results by trigger words
+/// `rules` replaces the trigger word table (None keeps the current one)
+#[tauri::command]
+fn set_speech_decoration(
+    enabled: bool,
+    rules: Option<Vec<DecorationRule>>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let manager = state.speech_manager.lock().map_err(|e| e.to_string())?;
+    manager.set_decoration(enabled, rules)
+}
+
+/// Enable or disable normalization of final speech results
+#[tauri::command]
+fn set_speech_normalize(enabled: bool, state: State<'_, AppState>) -> Result<(), String> {
+    let manager = state.speech_manager.lock().map_err(|e| e.to_string())?;
+    manager.set_normalize(enabled)
+}
+
+/// Change the recognition language
+///
+/// If listening, the in-progress text is committed as a final result before switching,
+/// and recognition restarts in the new language unless `resume` is false.
+#[tauri::command]
+fn set_speech_language(
+    language: String,
+    resume: Option<bool>,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let manager = state.speech_manager.lock().map_err(|e| e.to_string())?;
+    manager.set_language(&app, language, resume.unwrap_or(true))
+}
+
+/// Notify the speech manager of the app's display locale
+/// Returns the new speech language when it followed the locale
+#[tauri::command]
+fn set_speech_app_locale(
+    locale: String,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Option<String>, String> {
+    let manager = state.speech_manager.lock().map_err(|e| e.to_string())?;
+    manager.set_app_locale(&app, locale)
+}
+
+/// Enable or disable following the app locale with the speech language
+/// While disabled, the language set with `set_speech_language` is kept
+#[tauri::command]
+fn set_speech_follow_app_locale(
+    enabled: bool,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Option<String>, String> {
+    let manager = state.speech_manager.lock().map_err(|e| e.to_string())?;
+    manager.set_follow_app_locale(&app, enabled)
+}
+
+/// Reset the speech error counter and re-enable speech recognition
+#[tauri::command]
+fn reset_speech_errors(state: State<'_, AppState>) -> Result<(), String> {
+    let manager = state.speech_manager.lock().map_err(|e| e.to_string())?;
+    manager.reset_errors()
+}
+
+/// Path of the speech profiles file in the app config dir
+fn speech_profiles_path(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    let config_dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    Ok(config_dir.join(PROFILES_FILE_NAME))
+}
+
+/// List the speech profiles defined in the profiles file
+#[tauri::command]
+fn list_speech_profiles(app: tauri::AppHandle) -> Result<Vec<SpeechProfile>, String> {
+    let profiles = SpeechProfiles::load(&speech_profiles_path(&app)?)?;
+    Ok(profiles.profiles)
+}
+
+/// Apply a speech profile by name (the recognizer is recreated with its settings)
+#[tauri::command]
+fn apply_speech_profile(
+    name: String,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let profiles = SpeechProfiles::load(&speech_profiles_path(&app)?)?;
+    let profile = profiles
+        .find(&name)
+        .ok_or_else(|| format!("Speech profile not found: {}", name))?;
+
+    let manager = state.speech_manager.lock().map_err(|e| e.to_string())?;
+    manager.apply_profile(profile)
+}
+
+/// Set the profile applied on startup (None to clear)
+#[tauri::command]
+fn set_default_speech_profile(name: Option<String>, app: tauri::AppHandle) -> Result<(), String> {
+    let path = speech_profiles_path(&app)?;
+    let mut profiles = SpeechProfiles::load(&path)?;
+
+    if let Some(ref name) = name {
+        if profiles.find(name).is_none() {
+            return Err(format!("Speech profile not found: {}", name));
+        }
+    }
+    profiles.default_profile = name;
+    profiles.save(&path)
+}
+
+/// Apply the default speech profile, if one is configured
+fn restore_speech_profile(app: &tauri::AppHandle) -> Result<(), String> {
+    let profiles = SpeechProfiles::load(&speech_profiles_path(app)?)?;
+    let Some(ref name) = profiles.default_profile else {
+        return Ok(());
+    };
+    let profile = profiles
+        .find(name)
+        .ok_or_else(|| format!("Default speech profile not found: {}", name))?;
+
+    let state = app.state::<AppState>();
+    let manager = state.speech_manager.lock().map_err(|e| e.to_string())?;
+    manager.apply_profile(profile)
+}
+
 /// Get current speech recognition state
 #[tauri::command]
 fn get_speech_state(state: State<'_, AppState>) -> Result<SpeechRecognitionState, String> {
@@ -212,6 +884,274 @@ fn get_speech_state(state: State<'_, AppState>) -> Result<SpeechRecognitionState
     manager.get_state()
 }
 
+/// Check whether the microphone is really in use (audio engine running)
+/// A state that disagrees with the engine is corrected and re-emitted
+#[tauri::command]
+fn is_speech_actually_listening(app: tauri::AppHandle, state: State<'_, AppState>) -> Result<bool, String> {
+    let manager = state.speech_manager.lock().map_err(|e| e.to_string())?;
+    manager.is_actually_listening(&app)
+}
+
+/// List past speech recognition sessions, newest first
+/// `language` matches by prefix ("ja" includes "ja-JP"); `from` / `to` are inclusive
+/// start dates in YYYY-MM-DD (local time)
+#[tauri::command]
+fn get_speech_history(
+    language: Option<String>,
+    from: Option<String>,
+    to: Option<String>,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<SpeechSession>, String> {
+    let manager = state.speech_manager.lock().map_err(|e| e.to_string())?;
+    manager.get_history(&app, language, from, to)
+}
+
+/// Add a word to the persistent speech dictionary (passed to the recognizer as a
+/// contextual string from the next start). Returns the updated dictionary
+#[tauri::command]
+fn add_speech_dictionary_word(
+    word: String,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    let manager = state.speech_manager.lock().map_err(|e| e.to_string())?;
+    manager.add_dictionary_word(&app, &word)
+}
+
+/// Remove a word from the speech dictionary. Returns the updated dictionary
+#[tauri::command]
+fn remove_speech_dictionary_word(
+    word: String,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    let manager = state.speech_manager.lock().map_err(|e| e.to_string())?;
+    manager.remove_dictionary_word(&app, &word)
+}
+
+/// List the words in the speech dictionary (in the order they were added)
+#[tauri::command]
+fn list_speech_dictionary(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    let manager = state.speech_manager.lock().map_err(|e| e.to_string())?;
+    manager.list_dictionary(&app)
+}
+
+/// Start polling Claude projects for session updates
+/// Emits `claude:session-updated` when a session file changes
+#[tauri::command]
+fn start_claude_polling(
+    interval_ms: u64,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let mut poller = state.claude_poller.lock().map_err(|e| e.to_string())?;
+    poller.start(&app, interval_ms)
+}
+
+/// Stop polling Claude projects
+#[tauri::command]
+fn stop_claude_polling(state: State<'_, AppState>) -> Result<(), String> {
+    let mut poller = state.claude_poller.lock().map_err(|e| e.to_string())?;
+    poller.stop();
+    Ok(())
+}
+
+/// Stream a session's messages as `claude-message` events, then `claude-read-complete`
+/// The events carry `stream_id`, chosen by the caller so it can listen before starting
+/// (pass it to `cancel_claude_session_stream` to stop early)
+#[tauri::command]
+fn stream_claude_session(
+    stream_id: String,
+    project_path: String,
+    session_id: String,
+    include_thinking: Option<bool>,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state.claude_streamer.start(
+        &app,
+        &stream_id,
+        &project_path,
+        &session_id,
+        include_thinking.unwrap_or(false),
+    )
+}
+
+/// Cancel a running session stream
+/// Returns false if the stream had already finished
+#[tauri::command]
+fn cancel_claude_session_stream(stream_id: String, state: State<'_, AppState>) -> Result<bool, String> {
+    state.claude_streamer.cancel(&stream_id)
+}
+
+/// Rebuild the Claude log search index from scratch
+/// Returns the number of indexed sessions
+#[tauri::command]
+fn build_claude_search_index(state: State<'_, AppState>) -> Result<usize, String> {
+    state.claude_search.build()
+}
+
+/// Search Claude session messages (case-insensitive substring match)
+/// Uses the search index when it has been built, otherwise scans every session
+#[tauri::command]
+fn search_claude_logs(
+    query: String,
+    project_path: Option<String>,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<SearchHit>, String> {
+    state
+        .claude_search
+        .search(&query, project_path.as_deref(), limit)
+}
+
+/// Find which project a session belongs to from its session ID
+/// Uses the search index when it has been built, otherwise checks every project directory
+#[tauri::command]
+fn find_session(session_id: String, state: State<'_, AppState>) -> Result<FoundSession, String> {
+    state.claude_search.find_session(&session_id)
+}
+
+/// Keep a PTY shell alive while the main window is closed (until `kill_pty` or app exit)
+#[tauri::command]
+fn register_pty(pid: u32, state: State<'_, AppState>) -> Result<(), String> {
+    state.pty_sessions.register(pid)
+}
+
+/// End a PTY session explicitly (the only way a registered shell is killed before exit)
+#[tauri::command]
+fn kill_pty(pid: u32, state: State<'_, AppState>) -> Result<(), String> {
+    state.pty_sessions.kill(pid)
+}
+
+/// Resume a Claude session in one of the app's PTY tabs (`cd <cwd> && claude --resume <id>`)
+///
+/// The PTYs are written to by the frontend, so the command line is sent as `pty:input`
+/// to the tab running the PTY process `pty_id`
+#[tauri::command]
+fn resume_claude_in_pty(
+    pty_id: u32,
+    session_id: String,
+    cwd: String,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state.pty_sessions.ensure_registered(pty_id)?;
+    let command = claude_logs::claude_resume_command(&session_id, &cwd)?;
+
+    // Clear whatever is typed on the line first (Ctrl-U) so the command is not appended to it
+    let payload = PtyInputPayload {
+        pid: pty_id,
+        data: format!("\x15{}\n", command),
+    };
+    events::emit(&app, events::PTY_INPUT, payload).map_err(|e| e.to_string())
+}
+
+/// Stream output only for the PTY process `pty_id` (None to stream every PTY again)
+///
+/// Output is streamed by the frontend, so the switch is sent as `pty:active-changed`
+/// and applied like switching tabs (output held back meanwhile is redrawn)
+#[tauri::command]
+fn set_active_pty(
+    pty_id: Option<u32>,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    if let Some(pid) = pty_id {
+        state.pty_sessions.ensure_registered(pid)?;
+    }
+    let payload = PtyActivePayload { pid: pty_id };
+    events::emit(&app, events::PTY_ACTIVE_CHANGED, payload).map_err(|e| e.to_string())
+}
+
+/// Show or hide the main window and tell the frontend, which pauses rendering while hidden
+fn set_main_window_visible(window: &tauri::Window, visible: bool) {
+    let result = if visible {
+        window.show().and_then(|_| window.set_focus())
+    } else {
+        window.hide()
+    };
+    if let Err(e) = result {
+        eprintln!("[window] Failed to change visibility: {}", e);
+        return;
+    }
+    let payload = WindowVisibilityPayload { visible };
+    if let Err(e) = events::emit(window, events::WINDOW_VISIBILITY_CHANGED, payload) {
+        eprintln!("[window] Failed to emit visibility event: {}", e);
+    }
+}
+
+/// Load the persisted autohide config (defaults if it is missing or unreadable)
+fn load_autohide_config(app: &tauri::AppHandle) -> Result<AutohideConfig, String> {
+    let config_dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    let state = app.state::<AppState>();
+    let manager = state
+        .autohide_manager
+        .lock()
+        .map_err(|e| e.to_string())?;
+
+    manager.load_config(config_dir.join(CONFIG_FILE_NAME))
+}
+
+/// Restore the persisted autohide state on startup
+/// If autohide was enabled last time, start with the window hidden at the edge
+fn restore_autohide_state(
+    app: &tauri::AppHandle,
+    window: &tauri::Window,
+    config: &AutohideConfig,
+) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let manager = state
+        .autohide_manager
+        .lock()
+        .map_err(|e| e.to_string())?;
+
+    if let Err(e) = manager.restore_sidebar_width(window) {
+        eprintln!("[autohide] Failed to restore the sidebar width: {}", e);
+    }
+    if let Err(e) = window.set_always_on_top(config.always_on_top) {
+        eprintln!("[autohide] Failed to restore always-on-top: {}", e);
+    }
+    if !config.enabled {
+        return Ok(());
+    }
+
+    // If the window state can't be read yet, fall back to a normal visible window
+    // rather than leaving it stranded off-screen. The saved config is left as it is,
+    // so autohide is restored again on the next launch.
+    if let Err(e) = manager.enable(window) {
+        eprintln!("[autohide] Failed to restore autohide state: {}", e);
+        if let Err(e) = window.show() {
+            eprintln!("[autohide] Failed to show the window: {}", e);
+        }
+        return Ok(());
+    }
+    start_autohide_event_tap(&manager, app, window);
+
+    Ok(())
+}
+
+/// Keep the sidebar on a connected display when displays are plugged in or out
+/// (the change is reported on the main thread, so the sidebar is re-placed on the worker)
+fn watch_autohide_displays(app: &tauri::AppHandle, window: tauri::Window) {
+    let app_handle = app.clone();
+    let watched = autohide::display_watch::watch_display_changes(move || {
+        let target = window.clone();
+        post_autohide_job(&app_handle, move |manager| {
+            if let Err(e) = manager.handle_display_change(&target) {
+                eprintln!("[autohide] Failed to re-place after a display change: {}", e);
+            }
+        });
+    });
+    if let Err(e) = watched {
+        eprintln!("[autohide] {}", e);
+    }
+}
+
 fn main() {
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
@@ -220,23 +1160,67 @@ fn main() {
         .plugin(tauri_plugin_dialog::init())
         .manage(AppState {
             autohide_manager: Mutex::new(AutohideManager::new()),
+            autohide_worker: AutohideWorker::new(),
             speech_manager: Mutex::new(SpeechManager::new()),
+            claude_poller: Mutex::new(ClaudePoller::new()),
+            claude_streamer: SessionStreamer::new(),
+            claude_search: SearchIndex::new(),
+            pty_sessions: PtySessions::new(),
+        })
+        .on_window_event(|window, event| {
+            if window.label() == "main" {
+                match event {
+                    // A scale factor change means the window moved to another display or the
+                    // display configuration changed, so the automatic edge may no longer fit
+                    tauri::WindowEvent::ScaleFactorChanged { .. } => {
+                        let target = window.clone();
+                        post_autohide_job(window.app_handle(), move |manager| {
+                            if let Err(e) = manager.reevaluate_auto_edge(&target) {
+                                eprintln!("[autohide] Failed to re-evaluate edge: {}", e);
+                            }
+                        });
+                    }
+                    // Closing the window while terminals are running only hides it, so the
+                    // PTY sessions (held by the webview) keep running until it is reopened
+                    tauri::WindowEvent::CloseRequested { api, .. }
+                        if window.state::<AppState>().pty_sessions.has_live_sessions() =>
+                    {
+                        api.prevent_close();
+                        set_main_window_visible(window, false);
+                    }
+                    tauri::WindowEvent::Focused(false) => {
+                        let target = window.clone();
+                        post_autohide_job(window.app_handle(), move |manager| {
+                            if let Err(e) = manager.hide_on_blur(&target) {
+                                eprintln!("[autohide] Failed to hide on blur: {}", e);
+                            }
+                        });
+                    }
+                    _ => {}
+                }
+            }
         })
         .setup(|app| {
             // Get primary monitor size and adjust window heights
             if let Some(main_window) = app.get_webview_window("main") {
+                // The config is loaded first because its margins decide the window height
+                let config = load_autohide_config(app.handle()).unwrap_or_else(|e| {
+                    eprintln!("[autohide] Failed to load config: {}", e);
+                    AutohideConfig::default()
+                });
+
                 if let Some(monitor) = main_window.primary_monitor().ok().flatten() {
                     let screen_size = monitor.size();
                     let scale_factor = monitor.scale_factor();
 
-                    // Calculate height (leave 100px margin top and bottom)
-                    let vertical_margin = 100.0;
-                    let window_height = (screen_size.height as f64 - vertical_margin * 2.0 * scale_factor) as u32;
+                    // Calculate height (leave the configured margins at the top and bottom)
+                    let vertical_margin = config.top_margin + config.bottom_margin;
+                    let window_height = (screen_size.height as f64 - vertical_margin * scale_factor) as u32;
 
                     // Set main window size and position
                     let main_width = 430;
                     let main_x = 10; // タブの横に寄せる
-                    let main_y = 100; // 100px from top
+                    let main_y = config.top_margin;
 
                     let _ = main_window.set_size(tauri::PhysicalSize::new(
                         (main_width as f64 * scale_factor) as u32,
@@ -244,19 +1228,66 @@ fn main() {
                     ));
                     let _ = main_window.set_position(tauri::PhysicalPosition::new(
                         (main_x as f64 * scale_factor) as i32,
-                        (main_y as f64 * scale_factor) as i32,
+                        (main_y * scale_factor) as i32,
                     ));
 
                 }
+
+                // Restore autohide after the window has been sized and positioned,
+                // so the cached position is the final one
+                if let Err(e) = restore_autohide_state(app.handle(), &main_window.as_ref().window(), &config) {
+                    eprintln!("[autohide] {}", e);
+                }
+                watch_autohide_displays(app.handle(), main_window.as_ref().window());
+            }
+
+            if let Err(e) = restore_speech_data_dir(app.handle()) {
+                eprintln!("[speech] {}", e);
             }
+            if let Err(e) = restore_speech_profile(app.handle()) {
+                eprintln!("[speech] {}", e);
+            }
+            system_appearance::watch_changes(app.handle());
+            if let Err(e) = tray::setup(app.handle()) {
+                eprintln!("[tray] {}", e);
+            }
+
+            // Build the search index in the background; searches scan files until it is ready
+            let app_handle = app.handle().clone();
+            std::thread::spawn(move || {
+                if let Err(e) = app_handle.state::<AppState>().claude_search.build() {
+                    eprintln!("[claude_search] Failed to build index: {}", e);
+                }
+            });
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             get_system_fonts,
+            get_system_appearance,
             set_autohide_enabled,
             toggle_sidebar,
+            show_sidebar,
+            hide_sidebar,
             set_autohide_edge,
+            set_autohide_edge_auto,
             get_autohide_config,
+            app_status::get_app_status,
+            set_autohide_steal_focus,
+            set_autohide_hover_reveal,
+            set_autohide_delay,
+            set_reveal_delay,
+            set_autohide_trigger_zone_pixels,
+            set_autohide_hide_on_blur,
+            set_sidebar_pinned,
+            set_autohide_visible_pixels,
+            set_autohide_height,
+            set_sidebar_width,
+            set_sidebar_margins,
+            set_autohide_dock_appearance,
+            set_always_on_top,
+            set_autohide_easing,
+            list_displays,
+            move_autohide_to_display,
             is_sidebar_visible,
             toggle_main_window,
             create_claude_terminal_window,
@@ -264,19 +1295,83 @@ fn main() {
             focus_claude_terminal_window,
             start_speech_recognition,
             stop_speech_recognition,
+            toggle_speech_recognition,
             get_speech_state,
+            is_speech_actually_listening,
+            get_speech_history,
+            add_speech_dictionary_word,
+            remove_speech_dictionary_word,
+            list_speech_dictionary,
+            set_speech_record_audio_path,
+            set_speech_data_dir,
+            set_speech_normalize,
+            set_speech_sentence_split,
+            set_speech_min_utterance,
+            set_speech_segment_pause,
+            set_speech_partial_debounce,
+            set_speech_decoration,
+            set_speech_language,
+            set_speech_app_locale,
+            set_speech_follow_app_locale,
+            set_speech_result_format,
+            set_speech_sleep_prevention,
+            set_speech_hands_free,
+            list_speech_profiles,
+            apply_speech_profile,
+            set_default_speech_profile,
+            reset_speech_errors,
+            start_claude_polling,
+            stop_claude_polling,
+            stream_claude_session,
+            cancel_claude_session_stream,
+            build_claude_search_index,
+            search_claude_logs,
+            find_session,
             claude_logs::list_claude_projects,
             claude_logs::list_claude_sessions,
+            claude_logs::find_claude_project_candidates,
+            claude_logs::list_claude_sessions_grouped,
             claude_logs::read_claude_session,
             claude_logs::launch_claude_code,
             claude_logs::resume_claude_code,
+            claude_edits::get_session_edited_files,
+            claude_export::export_claude_session,
+            claude_export::export_claude_session_html,
+            claude_export::get_claude_session_text,
+            claude_export::set_export_dir,
+            claude_export::get_export_dir,
             claude_logs::get_current_working_directory,
             claude_logs::get_project_path_for_cwd,
             claude_logs::list_sessions_for_cwd,
             claude_logs::get_latest_session_for_cwd,
+            claude_logs::archive_project_sessions,
+            claude_logs::delete_project_sessions,
             paste_to_app::paste_text_to_app,
             paste_to_app::get_running_apps,
+            pty::signal_pty_resize,
+            pty::is_pty_echo_enabled,
+            register_pty,
+            kill_pty,
+            resume_claude_in_pty,
+            set_active_pty,
+            command_runner::run_command,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| match event {
+            // Clicking the Dock icon brings back a main window hidden by closing it
+            RunEvent::Reopen { .. } => {
+                if let Some(window) = app.get_webview_window("main") {
+                    let window = window.as_ref().window();
+                    if !window.is_visible().unwrap_or(true) {
+                        set_main_window_visible(&window, true);
+                        if let Err(e) = events::emit(&window, events::WINDOW_REOPENED, ()) {
+                            eprintln!("[window] Failed to emit reopen event: {}", e);
+                        }
+                    }
+                }
+            }
+            RunEvent::Exit => app.state::<AppState>().pty_sessions.kill_all(),
+            _ => {}
+        });
 }
@@ -1,10 +1,23 @@
 use serde::{Deserialize, Serialize};
 use std::process::Command;
 
+/// ペーストするテキストの上限（バイト数）のデフォルト値
+const DEFAULT_MAX_PASTE_BYTES: usize = 100 * 1024;
+
+/// 対象アプリが前面になるまで待つ時間（ミリ秒）のデフォルト値
+const DEFAULT_ACTIVATION_TIMEOUT_MS: u64 = 2000;
+
+/// 前面アプリを確認する間隔（秒）
+const FRONTMOST_POLL_INTERVAL_SECS: f64 = 0.05;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PasteResult {
     pub success: bool,
     pub error: Option<String>,
+    /// 上限を超えたためテキストを切り詰めたかどうか
+    pub truncated: bool,
+    /// 切り詰め前のテキスト長（UTF-8 のバイト数。文字数や JS の length とは異なる）
+    pub original_length: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -65,12 +78,45 @@ pub fn get_running_apps() -> Result<Vec<AppInfo>, String> {
     Ok(apps)
 }
 
+/// 上限バイト数を超えないよう、文字境界で切り詰める
+fn truncate_to_bytes(text: &str, max_bytes: usize) -> &str {
+    if text.len() <= max_bytes {
+        return text;
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
 /// 指定したアプリにテキストをペースト
+/// `max_bytes` を超えるテキストは切り詰めて送り、結果の `truncated` で知らせる
+/// 対象アプリが前面になったことを確認してからキー入力を送り、
+/// `activation_timeout_ms` 以内に前面にならなければ失敗を返す
 #[tauri::command]
-pub fn paste_text_to_app(text: String, target_app: String, bundle_id: Option<String>) -> Result<PasteResult, String> {
+pub fn paste_text_to_app(
+    text: String,
+    target_app: String,
+    bundle_id: Option<String>,
+    max_bytes: Option<usize>,
+    activation_timeout_ms: Option<u64>,
+) -> Result<PasteResult, String> {
     let preview: String = text.chars().take(20).collect();
     println!("[paste_to_app] Called with text: {}, target: {}, bundle_id: {:?}", preview, target_app, bundle_id);
 
+    let original_length = text.len();
+    let max_bytes = max_bytes.unwrap_or(DEFAULT_MAX_PASTE_BYTES);
+    let text = truncate_to_bytes(&text, max_bytes);
+    let truncated = text.len() < original_length;
+    if truncated {
+        println!(
+            "[paste_to_app] Text too large ({} bytes), truncated to {} bytes",
+            original_length,
+            text.len()
+        );
+    }
+
     // エスケープ処理
     let escaped_text = text
         .replace('\\', "\\\\")
@@ -87,11 +133,38 @@ pub fn paste_text_to_app(text: String, target_app: String, bundle_id: Option<Str
         None => format!(r#"tell application "{escaped_app}" to activate"#),
     };
 
+    // 前面プロセスが対象アプリかどうかの判定（Bundle IDがあればそれでも判定）
+    let frontmost_condition = match &bundle_id {
+        Some(id) => format!(
+            r#"frontName is "{escaped_app}" or frontId is "{}""#,
+            id.replace('"', "\\\"")
+        ),
+        None => format!(r#"frontName is "{escaped_app}""#),
+    };
+    let timeout_secs =
+        activation_timeout_ms.unwrap_or(DEFAULT_ACTIVATION_TIMEOUT_MS) as f64 / 1000.0;
+
+    // 固定時間待つ代わりに、前面になるまで短い間隔でポーリングする
     let script = format!(
         r#"
         set the clipboard to "{escaped_text}"
         {activate_command}
-        delay 0.3
+        set waited to 0
+        repeat
+            tell application "System Events"
+                set frontProc to first application process whose frontmost is true
+                set frontName to name of frontProc
+                try
+                    set frontId to bundle identifier of frontProc
+                on error
+                    set frontId to ""
+                end try
+            end tell
+            if {frontmost_condition} then exit repeat
+            if waited >= {timeout_secs} then error "Timed out waiting for {escaped_app} to become frontmost"
+            delay {FRONTMOST_POLL_INTERVAL_SECS}
+            set waited to waited + {FRONTMOST_POLL_INTERVAL_SECS}
+        end repeat
         tell application "System Events"
             keystroke "v" using command down
         end tell
@@ -112,6 +185,8 @@ pub fn paste_text_to_app(text: String, target_app: String, bundle_id: Option<Str
         Ok(PasteResult {
             success: true,
             error: None,
+            truncated,
+            original_length,
         })
     } else {
         let error = String::from_utf8_lossy(&output.stderr).to_string();
@@ -119,6 +194,8 @@ pub fn paste_text_to_app(text: String, target_app: String, bundle_id: Option<Str
         Ok(PasteResult {
             success: false,
             error: Some(error),
+            truncated,
+            original_length,
         })
     }
 }
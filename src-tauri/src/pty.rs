@@ -0,0 +1,198 @@
+use crate::AppState;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io;
+use std::process::Command;
+use std::sync::Mutex;
+use tauri::State;
+
+/// `pty:input` event payload: input for the frontend to write to the PTY of `pid`
+#[derive(Debug, Clone, Serialize)]
+pub struct PtyInputPayload {
+    pub pid: u32,
+    pub data: String,
+}
+
+/// `pty:active-changed` event payload: the PTY whose output is streamed (None for all)
+#[derive(Debug, Clone, Serialize)]
+pub struct PtyActivePayload {
+    pub pid: Option<u32>,
+}
+
+/// PTY shells that outlive the main window
+///
+/// The shells are spawned by the frontend (tauri-plugin-pty) and registered here.
+/// Closing the main window only hides it while any of them is alive, so commands keep
+/// running; only `kill` ends a session, and whatever is left is hung up on exit.
+pub struct PtySessions {
+    pids: Mutex<HashSet<u32>>,
+}
+
+impl PtySessions {
+    pub fn new() -> Self {
+        Self {
+            pids: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Keep track of a PTY shell
+    pub fn register(&self, pid: u32) -> Result<(), String> {
+        let mut pids = self.pids.lock().map_err(|e| e.to_string())?;
+        pids.insert(pid);
+        Ok(())
+    }
+
+    /// Fail unless `pid` is a registered PTY shell
+    pub fn ensure_registered(&self, pid: u32) -> Result<(), String> {
+        let pids = self.pids.lock().map_err(|e| e.to_string())?;
+        if !pids.contains(&pid) {
+            return Err(format!("PTY process {} is not registered", pid));
+        }
+        Ok(())
+    }
+
+    /// Whether any registered shell is still running (exited ones are forgotten)
+    pub fn has_live_sessions(&self) -> bool {
+        !self.live_pids().is_empty()
+    }
+
+    /// PIDs of the registered shells that are still running (exited ones are forgotten)
+    pub fn live_pids(&self) -> Vec<u32> {
+        let Ok(mut pids) = self.pids.lock() else {
+            return Vec::new();
+        };
+        pids.retain(|pid| is_alive(*pid));
+        let mut live: Vec<u32> = pids.iter().copied().collect();
+        live.sort_unstable();
+        live
+    }
+
+    /// End a session: hang up its shell and the program running in the foreground
+    pub fn kill(&self, pid: u32) -> Result<(), String> {
+        let registered = {
+            let mut pids = self.pids.lock().map_err(|e| e.to_string())?;
+            pids.remove(&pid)
+        };
+        if !registered {
+            return Err(format!("PTY process {} is not registered", pid));
+        }
+        // The shell may have exited on its own, and its pid may since have been reused
+        if !is_alive(pid) {
+            return Ok(());
+        }
+        hang_up(pid)
+    }
+
+    /// Hang up every session still running (called when the app exits)
+    pub fn kill_all(&self) {
+        let pids: Vec<u32> = match self.pids.lock() {
+            Ok(mut pids) => pids.drain().collect(),
+            Err(_) => return,
+        };
+        for pid in pids.into_iter().filter(|pid| is_alive(*pid)) {
+            if let Err(e) = hang_up(pid) {
+                eprintln!("[pty] Failed to end PTY process {}: {}", pid, e);
+            }
+        }
+    }
+}
+
+/// Make sure the foreground process of a PTY receives SIGWINCH after a resize
+///
+/// Resizing the PTY master should make the kernel signal the foreground process
+/// group, but in some environments the signal never arrives and TUIs keep drawing
+/// with the old size. This sends it explicitly to the terminal's foreground group.
+///
+/// Manual check:
+/// 1. Open a terminal tab and run `vim` (or `less` on a long file)
+/// 2. Resize the window
+/// 3. The screen should redraw at the new size immediately, without a key press
+#[tauri::command]
+pub fn signal_pty_resize(pid: u32, state: State<'_, AppState>) -> Result<(), String> {
+    state.pty_sessions.ensure_registered(pid)?;
+
+    // Foreground process group of the terminal the shell is attached to
+    let tpgid = foreground_process_group(pid)?;
+    send_signal(-tpgid, libc::SIGWINCH)
+        .map_err(|e| format!("Failed to send SIGWINCH to process group {}: {}", tpgid, e))
+}
+
+/// Whether the terminal of a PTY process currently echoes input
+///
+/// Programs that read passwords (sudo, ssh, ...) turn echo off on the terminal while
+/// the password is typed, so input typed during that time should not be recorded.
+#[tauri::command]
+pub fn is_pty_echo_enabled(pid: u32, state: State<'_, AppState>) -> Result<bool, String> {
+    // Only PTY shells of this app, not arbitrary processes
+    state.pty_sessions.ensure_registered(pid)?;
+
+    // Terminal the shell is attached to (e.g. "ttys003")
+    let output = Command::new("ps")
+        .args(["-o", "tty=", "-p", &pid.to_string()])
+        .output()
+        .map_err(|e| format!("Failed to execute ps: {}", e))?;
+
+    let tty = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if tty.is_empty() || tty.starts_with('?') {
+        return Err(format!("Process {} has no controlling terminal", pid));
+    }
+
+    // stty reports the settings of the terminal on its stdin
+    let device = File::open(format!("/dev/{}", tty))
+        .map_err(|e| format!("Failed to open /dev/{}: {}", tty, e))?;
+    let output = Command::new("stty")
+        .arg("-a")
+        .stdin(device)
+        .output()
+        .map_err(|e| format!("Failed to execute stty: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("Failed to read terminal settings of /dev/{}", tty));
+    }
+
+    // Local flags are listed as "echo" or "-echo"
+    let settings = String::from_utf8_lossy(&output.stdout);
+    Ok(!settings.split_whitespace().any(|flag| flag == "-echo"))
+}
+
+/// Send SIGHUP to the foreground process group and the shell, as closing a terminal does
+fn hang_up(pid: u32) -> Result<(), String> {
+    if let Ok(tpgid) = foreground_process_group(pid) {
+        // The foreground program may already have exited along with the shell
+        let _ = send_signal(-tpgid, libc::SIGHUP);
+    }
+    match send_signal(pid as i32, libc::SIGHUP) {
+        Err(e) if is_alive(pid) => Err(format!("Failed to send SIGHUP to process {}: {}", pid, e)),
+        _ => Ok(()),
+    }
+}
+
+fn is_alive(pid: u32) -> bool {
+    send_signal(pid as i32, 0).is_ok()
+}
+
+/// kill(2): a negative `pid` signals the process group `-pid`, signal 0 only checks
+fn send_signal(pid: i32, signal: i32) -> io::Result<()> {
+    // SAFETY: kill has no memory-safety preconditions
+    if unsafe { libc::kill(pid, signal) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+/// Foreground process group of the terminal a process is attached to
+fn foreground_process_group(pid: u32) -> Result<i32, String> {
+    let output = Command::new("ps")
+        .args(["-o", "tpgid=", "-p", &pid.to_string()])
+        .output()
+        .map_err(|e| format!("Failed to execute ps: {}", e))?;
+
+    String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse::<i32>()
+        .ok()
+        .filter(|pgid| *pgid > 0)
+        .ok_or_else(|| format!("Process {} has no controlling terminal", pid))
+}
@@ -1,4 +1,7 @@
+use crate::speech::decorator::{self, DecorationRule};
+use crate::speech::sleep_guard::SleepPrevention;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
 /// 音声認識の状態
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -14,6 +17,33 @@ pub enum SpeechRecognitionState {
     Error,
 }
 
+/// `speech:result` で送る結果の形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ResultFormat {
+    /// `RecognitionResult` をそのまま送る
+    #[default]
+    Simple,
+    /// Web Speech API の `SpeechRecognitionResult` に近い形（`WebSpeechResult`）で送る
+    WebSpeech,
+}
+
+impl FromStr for ResultFormat {
+    type Err = String;
+
+    /// 形式名を解釈する（大文字小文字は区別しない）。不明な名前は Simple にせずエラーにする
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s.to_lowercase().as_str() {
+            "simple" => Ok(ResultFormat::Simple),
+            "web_speech" | "webspeech" => Ok(ResultFormat::WebSpeech),
+            _ => Err(format!(
+                "Unknown result format: {} (expected simple or web_speech)",
+                s
+            )),
+        }
+    }
+}
+
 /// 音声認識設定
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SpeechConfig {
@@ -21,6 +51,57 @@ pub struct SpeechConfig {
     pub enabled: bool,
     /// 認識言語（ja-JP等）
     pub language: String,
+    /// アプリの表示言語（アプリロケール）の変更に認識言語を追従させる
+    pub follow_app_locale: bool,
+    /// 最後に通知されたアプリロケール（ja、en-GB等）
+    pub app_locale: Option<String>,
+    /// 句読点を自動で付与する
+    pub adds_punctuation: bool,
+    /// 認識されやすくする語彙
+    pub vocabulary: Vec<String>,
+    /// 適用中のプロファイル名
+    pub profile: Option<String>,
+    /// 部分結果の先頭がこの時間（ms）変化しなければ準確定とする（0 で無効）
+    pub commit_stable_ms: u64,
+    /// この文字数未満の確定結果は誤認識として捨てる（0 で無効）
+    pub min_utterance_chars: usize,
+    /// 発話がこの時間（ms）未満の確定結果は誤認識として捨てる（0 で無効）
+    pub min_utterance_ms: u64,
+    /// 認識結果の間がこの時間（ms）以上空いたら別の発話（話者セグメント）とみなす（0 で区切らない）
+    pub segment_pause_ms: u64,
+    /// 部分結果を送る最小間隔（ms）。間隔内の部分結果は最新のものだけを送る（0 で間引かない）
+    pub partial_debounce_ms: u64,
+    /// 認識中の音声を保存するファイルパス（.caf/.wav、None なら保存しない）
+    /// 相対パスはデータディレクトリの recordings 配下に保存する
+    pub record_audio_path: Option<String>,
+    /// 録音などを保存するベースディレクトリ（None なら app_data_dir）
+    pub data_dir: Option<String>,
+    /// 連続エラーがこの回数に達したら機能を無効化する（0 で無効化しない）
+    pub max_consecutive_errors: u32,
+    /// 確定テキストを正規化する（全角→半角、漢数字→算用数字など）
+    pub normalize: bool,
+    /// 認識結果イベントの形式
+    pub result_format: ResultFormat,
+    /// 確定テキストを文に分割して `speech:sentence` でも送る
+    pub split_sentences: bool,
+    /// 文の区切り文字（None なら言語ごとの既定）
+    pub sentence_delimiters: Option<String>,
+    /// 確定テキスト先頭のトリガー語（「コードブロック」「引用」など）で Markdown 装飾を付ける
+    pub decorate: bool,
+    /// トリガー語と装飾の対応
+    pub decoration_rules: Vec<DecorationRule>,
+    /// 認識中に抑止するスリープの種類
+    pub prevent_sleep: SleepPrevention,
+    /// 音量に応じて認識を自動で開始/停止するハンズフリーモード
+    pub hands_free: bool,
+    /// この音量（dBFS）を超えた状態が続いたら認識を開始
+    pub hands_free_start_db: f32,
+    /// 開始レベルを超え続ける必要がある時間（ms）
+    pub hands_free_start_ms: u64,
+    /// この音量（dBFS）を下回った状態が続いたら認識を停止（開始レベルより低くする）
+    pub hands_free_stop_db: f32,
+    /// 停止レベルを下回り続ける必要がある時間（ms）
+    pub hands_free_stop_ms: u64,
 }
 
 impl Default for SpeechConfig {
@@ -28,10 +109,77 @@ impl Default for SpeechConfig {
         Self {
             enabled: true,
             language: "ja-JP".to_string(),
+            follow_app_locale: false,
+            app_locale: None,
+            adds_punctuation: false,
+            vocabulary: Vec::new(),
+            profile: None,
+            commit_stable_ms: 1500,
+            min_utterance_chars: 0,
+            min_utterance_ms: 0,
+            segment_pause_ms: 2000,
+            partial_debounce_ms: 0,
+            record_audio_path: None,
+            data_dir: None,
+            max_consecutive_errors: 5,
+            normalize: true,
+            result_format: ResultFormat::Simple,
+            split_sentences: false,
+            sentence_delimiters: None,
+            decorate: false,
+            decoration_rules: decorator::default_rules(),
+            prevent_sleep: SleepPrevention::System,
+            hands_free: false,
+            hands_free_start_db: -35.0,
+            hands_free_start_ms: 300,
+            hands_free_stop_db: -50.0,
+            hands_free_stop_ms: 1500,
         }
     }
 }
 
+/// `speech:state-changed` イベントのペイロード
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SpeechStatePayload {
+    pub state: SpeechRecognitionState,
+}
+
+/// `speech:audio-level` イベントのペイロード
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SpeechAudioLevelPayload {
+    /// 入力音量（dBFS）
+    pub level_db: f32,
+}
+
+/// `speech:disabled` イベントのペイロード
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeechDisabledPayload {
+    /// 連続エラー回数
+    pub error_count: u32,
+    /// 最後に発生したエラー
+    pub last_error: String,
+}
+
+/// `speech:sentence` イベントのペイロード
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeechSentencePayload {
+    /// 文のテキスト（区切り文字を含む）
+    pub text: String,
+    /// 確定テキスト内での文の位置（0始まり）
+    pub index: usize,
+    /// 確定テキストの最後の文かどうか
+    pub is_last: bool,
+}
+
+/// `speech:segment-boundary` イベントのペイロード
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SpeechSegmentBoundaryPayload {
+    /// 新しく始まったセグメントの番号
+    pub segment_index: usize,
+    /// 境界になったポーズの長さ（ms）
+    pub pause_ms: u64,
+}
+
 /// 認識結果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecognitionResult {
@@ -39,4 +187,87 @@ pub struct RecognitionResult {
     pub text: String,
     /// 確定かどうか
     pub is_final: bool,
+    /// 準確定（安定して変化しなくなった）先頭部分
+    #[serde(default)]
+    pub committed_prefix: String,
+    /// 準確定部分を除いた未確定の残り
+    #[serde(default)]
+    pub pending: String,
+    /// Markdown 装飾を付ける前のテキスト（装飾した場合のみ。誤爆時に元へ戻す用）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plain_text: Option<String>,
+    /// 話者セグメントの番号（長いポーズのたびに増える。認識の開始ごとに 0 から）
+    #[serde(default)]
+    pub segment_index: usize,
+    /// 認識器が返した候補（先頭が最有力）。Web Speech 形式でのみ送る
+    #[serde(skip)]
+    pub alternatives: Vec<RecognitionAlternative>,
+}
+
+/// 認識中に発生したエラー
+#[derive(Debug, Clone)]
+pub struct RecognitionError {
+    /// エラーの説明
+    pub message: String,
+    /// 音声が検出されなかった等で認識タスクが終わっただけのエラー（連続エラーには数えない）
+    pub transient: bool,
+}
+
+/// 認識候補
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecognitionAlternative {
+    pub transcript: String,
+    /// 信頼度（0.0〜1.0、部分結果では 0）
+    pub confidence: f32,
+}
+
+/// Web Speech API の `SpeechRecognitionResult` 互換の認識結果
+///
+/// `RecognitionResult` からの対応:
+/// - `is_final` → `isFinal`
+/// - `text` → `alternatives[0].transcript`（正規化後のテキスト）
+/// - 認識器の候補の信頼度 → `alternatives[].confidence`
+/// - 2番目以降の候補 → `alternatives[1..]`
+/// - `committed_prefix` / `pending` は含まない
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebSpeechResult {
+    pub is_final: bool,
+    pub alternatives: Vec<RecognitionAlternative>,
+}
+
+impl From<&RecognitionResult> for WebSpeechResult {
+    fn from(result: &RecognitionResult) -> Self {
+        let best_confidence = result
+            .alternatives
+            .first()
+            .map(|alt| alt.confidence)
+            .unwrap_or(0.0);
+
+        let mut alternatives = vec![RecognitionAlternative {
+            transcript: result.text.clone(),
+            confidence: best_confidence,
+        }];
+        alternatives.extend(result.alternatives.iter().skip(1).cloned());
+
+        Self {
+            is_final: result.is_final,
+            alternatives,
+        }
+    }
+}
+
+impl RecognitionResult {
+    /// 認識器から得たテキストで結果を作成（準確定部分は空）
+    pub fn new(text: String, is_final: bool) -> Self {
+        Self {
+            pending: text.clone(),
+            text,
+            is_final,
+            committed_prefix: String::new(),
+            plain_text: None,
+            segment_index: 0,
+            alternatives: Vec::new(),
+        }
+    }
 }
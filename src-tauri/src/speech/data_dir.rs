@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// データ保存先の設定ファイル名（app_config_dir 内）
+pub const DATA_DIR_SETTINGS_FILE_NAME: &str = "speech_data_dir.json";
+
+/// 再起動後も使うデータ保存先の設定
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DataDirSettings {
+    /// ベースディレクトリ（None なら app_data_dir）
+    pub data_dir: Option<String>,
+}
+
+impl DataDirSettings {
+    /// JSONファイルから読み込む（ファイルが無ければ既定値）
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid speech data dir settings {}: {}", path.display(), e))
+    }
+
+    /// JSONファイルへ保存
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, content).map_err(|e| e.to_string())
+    }
+}
+
+/// 音声認識のデータ保存先のサブディレクトリ
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpeechDataKind {
+    /// 認識中の録音ファイル
+    Recordings,
+    /// 認識セッションの履歴
+    History,
+}
+
+impl SpeechDataKind {
+    fn dir_name(self) -> &'static str {
+        match self {
+            SpeechDataKind::Recordings => "recordings",
+            SpeechDataKind::History => "history",
+        }
+    }
+}
+
+/// ベースディレクトリ配下の種類ごとのディレクトリを返す（無ければ作成）
+pub fn subdir(base: &Path, kind: SpeechDataKind) -> Result<PathBuf, String> {
+    let dir = base.join(kind.dir_name());
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    Ok(dir)
+}
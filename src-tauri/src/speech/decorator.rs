@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+
+/// トリガー語と本文の間に置かれうる区切り
+const TRIGGER_SEPARATORS: &[char] = &[' ', '　', '、', '，', ',', ':', '：'];
+
+/// 付与する Markdown 装飾
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecorationKind {
+    /// フェンス付きコードブロック
+    CodeBlock,
+    /// インラインコード
+    InlineCode,
+    /// 引用（各行の先頭に `> `）
+    Quote,
+    /// 箇条書き（各行の先頭に `- `）
+    Bullet,
+}
+
+impl DecorationKind {
+    fn apply(self, body: &str) -> String {
+        match self {
+            DecorationKind::CodeBlock => format!("```\n{}\n```", body),
+            DecorationKind::InlineCode => format!("`{}`", body),
+            DecorationKind::Quote => prefix_lines(body, "> "),
+            DecorationKind::Bullet => prefix_lines(body, "- "),
+        }
+    }
+}
+
+/// トリガー語と装飾の対応
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecorationRule {
+    /// 確定テキストの先頭でこの語が話されたら装飾する（英字は大文字小文字を区別しない）
+    pub trigger: String,
+    pub kind: DecorationKind,
+    /// 対象の認識言語（前方一致。"ja" で "ja-JP" も含む、None なら全言語）
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+impl DecorationRule {
+    fn new(trigger: &str, kind: DecorationKind, language: &str) -> Self {
+        Self {
+            trigger: trigger.to_string(),
+            kind,
+            language: Some(language.to_string()),
+        }
+    }
+
+    fn applies_to(&self, language: &str) -> bool {
+        match self.language {
+            Some(ref prefix) => language.to_lowercase().starts_with(&prefix.to_lowercase()),
+            None => true,
+        }
+    }
+}
+
+/// 既定のトリガー語（日本語と英語）
+pub fn default_rules() -> Vec<DecorationRule> {
+    vec![
+        DecorationRule::new("コードブロック", DecorationKind::CodeBlock, "ja"),
+        DecorationRule::new("インラインコード", DecorationKind::InlineCode, "ja"),
+        DecorationRule::new("コマンド", DecorationKind::InlineCode, "ja"),
+        DecorationRule::new("引用", DecorationKind::Quote, "ja"),
+        DecorationRule::new("箇条書き", DecorationKind::Bullet, "ja"),
+        DecorationRule::new("code block", DecorationKind::CodeBlock, "en"),
+        DecorationRule::new("inline code", DecorationKind::InlineCode, "en"),
+        DecorationRule::new("command", DecorationKind::InlineCode, "en"),
+        DecorationRule::new("quote", DecorationKind::Quote, "en"),
+        DecorationRule::new("bullet", DecorationKind::Bullet, "en"),
+    ]
+}
+
+/// テキストの先頭のトリガー語に応じて Markdown 装飾を付ける
+///
+/// トリガー語は取り除き、残りの本文を装飾する。該当するトリガーが無い場合や
+/// 本文が空の場合は None を返す。複数のトリガーに一致する場合は長い方を優先する。
+pub fn decorate(text: &str, language: &str, rules: &[DecorationRule]) -> Option<String> {
+    let text = text.trim_start();
+
+    let mut rules: Vec<&DecorationRule> = rules
+        .iter()
+        .filter(|rule| !rule.trigger.is_empty() && rule.applies_to(language))
+        .collect();
+    rules.sort_by_key(|rule| std::cmp::Reverse(rule.trigger.len()));
+
+    rules.into_iter().find_map(|rule| {
+        let rest = strip_trigger(text, &rule.trigger)?;
+        let body = rest.trim_start_matches(TRIGGER_SEPARATORS).trim();
+        if body.is_empty() {
+            return None;
+        }
+        Some(rule.kind.apply(body))
+    })
+}
+
+/// 先頭のトリガー語を取り除いた残りを返す
+/// 英数字で終わるトリガーは単語の途中（"quote" に対する "quoted" など）では一致させない
+fn strip_trigger<'a>(text: &'a str, trigger: &str) -> Option<&'a str> {
+    let head = text.get(..trigger.len())?;
+    if !head.eq_ignore_ascii_case(trigger) {
+        return None;
+    }
+    let rest = &text[trigger.len()..];
+
+    let ends_with_word = trigger.chars().last().is_some_and(|c| c.is_ascii_alphanumeric());
+    let continues_word = rest.chars().next().is_some_and(|c| c.is_alphanumeric());
+    if ends_with_word && continues_word {
+        return None;
+    }
+    Some(rest)
+}
+
+fn prefix_lines(body: &str, prefix: &str) -> String {
+    body.lines()
+        .map(|line| format!("{}{}", prefix, line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+/// ユーザー辞書のファイル名（データ保存先のベースディレクトリ内）
+pub const DICTIONARY_FILE_NAME: &str = "speech_dictionary.json";
+
+/// 認識器に渡す contextual strings の上限（Apple の推奨は 100 語まで）
+pub const MAX_CONTEXTUAL_STRINGS: usize = 100;
+
+/// 認識されにくい固有名詞などを登録しておくユーザー辞書
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct UserDictionary {
+    /// 登録順の単語
+    pub words: Vec<String>,
+}
+
+impl UserDictionary {
+    /// JSONファイルから読み込む（ファイルが無ければ空）
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid speech dictionary file {}: {}", path.display(), e))
+    }
+
+    /// JSONファイルへ保存
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, content).map_err(|e| e.to_string())
+    }
+
+    /// 単語を追加する（前後の空白は除く）
+    ///
+    /// 大文字小文字だけが違う単語は登録済みとみなして追加しない（false）。
+    /// 空の単語や、上限に達している場合はエラー
+    pub fn add(&mut self, word: &str) -> Result<bool, String> {
+        let word = word.trim();
+        if word.is_empty() {
+            return Err("Dictionary word is empty".to_string());
+        }
+        if self.position(word).is_some() {
+            return Ok(false);
+        }
+        if self.words.len() >= MAX_CONTEXTUAL_STRINGS {
+            return Err(format!(
+                "The speech dictionary is full ({} words)",
+                MAX_CONTEXTUAL_STRINGS
+            ));
+        }
+        self.words.push(word.to_string());
+        Ok(true)
+    }
+
+    /// 単語を削除する（大文字小文字は区別しない）。登録されていなければ false
+    pub fn remove(&mut self, word: &str) -> bool {
+        match self.position(word.trim()) {
+            Some(index) => {
+                self.words.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn position(&self, word: &str) -> Option<usize> {
+        let key = word.to_lowercase();
+        self.words.iter().position(|w| w.to_lowercase() == key)
+    }
+}
+
+/// プロファイルの語彙とユーザー辞書をまとめて contextual strings にする
+///
+/// 語彙を先に並べ、重複（大文字小文字の違いのみを含む）と空の語を除いて上限で切る
+pub fn contextual_strings(vocabulary: &[String], dictionary: &[String]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    vocabulary
+        .iter()
+        .chain(dictionary)
+        .map(|word| word.trim())
+        .filter(|word| !word.is_empty() && seen.insert(word.to_lowercase()))
+        .take(MAX_CONTEXTUAL_STRINGS)
+        .map(str::to_string)
+        .collect()
+}
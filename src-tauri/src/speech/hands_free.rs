@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+use crate::speech::config::SpeechConfig;
+
+/// ハンズフリーモードの状態（`speech:hands-free-state-changed` で通知）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum HandsFreeState {
+    /// ハンズフリーモードではない
+    #[default]
+    Off,
+    /// 音量を監視して発話を待っている
+    Waiting,
+    /// 発話を検出して認識中
+    Listening,
+}
+
+/// `speech:hands-free-state-changed` イベントのペイロード
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct HandsFreeStatePayload {
+    pub state: HandsFreeState,
+}
+
+/// 検出器が要求する操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandsFreeAction {
+    Start,
+    Stop,
+}
+
+/// 音量レベルから認識の開始/停止を判定する
+///
+/// 開始レベルを停止レベルより高くしてヒステリシスを持たせ、
+/// さらに一定時間続いた場合のみ切り替えることで、瞬間的なノイズでの誤起動を防ぐ
+pub struct HandsFreeDetector {
+    start_db: f32,
+    start_after: Duration,
+    stop_db: f32,
+    stop_after: Duration,
+    listening: bool,
+    /// 切り替え条件を満たし始めた時刻
+    crossing_since: Option<Instant>,
+}
+
+impl HandsFreeDetector {
+    /// 設定のしきい値から検出器を作成
+    pub fn new(config: &SpeechConfig) -> Self {
+        Self {
+            start_db: config.hands_free_start_db,
+            start_after: Duration::from_millis(config.hands_free_start_ms),
+            // 停止レベルが開始レベルを上回るとバタつくため、開始レベル以下に抑える
+            stop_db: config.hands_free_stop_db.min(config.hands_free_start_db),
+            stop_after: Duration::from_millis(config.hands_free_stop_ms),
+            listening: false,
+            crossing_since: None,
+        }
+    }
+
+    /// 音量レベル（dBFS）を処理し、切り替えが必要なら操作を返す
+    pub fn process(&mut self, level_db: f32, now: Instant) -> Option<HandsFreeAction> {
+        let (crossing, required) = if self.listening {
+            (level_db < self.stop_db, self.stop_after)
+        } else {
+            (level_db > self.start_db, self.start_after)
+        };
+
+        if !crossing {
+            self.crossing_since = None;
+            return None;
+        }
+
+        let since = *self.crossing_since.get_or_insert(now);
+        if now.duration_since(since) < required {
+            return None;
+        }
+
+        self.crossing_since = None;
+        self.listening = !self.listening;
+        Some(if self.listening {
+            HandsFreeAction::Start
+        } else {
+            HandsFreeAction::Stop
+        })
+    }
+
+    /// 操作に失敗したときなどに、認識中かどうかを実際の状態に合わせる
+    pub fn set_listening(&mut self, listening: bool) {
+        self.listening = listening;
+        self.crossing_since = None;
+    }
+}
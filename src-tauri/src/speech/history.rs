@@ -0,0 +1,147 @@
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+/// 履歴ファイル名（データディレクトリの history 配下、1行1セッションの JSONL）
+pub const HISTORY_FILE_NAME: &str = "sessions.jsonl";
+
+/// 1回の認識セッション（開始から停止まで）の記録
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeechSession {
+    /// 開始時刻（RFC 3339、ローカルタイムゾーン）
+    pub started_at: String,
+    /// 終了時刻（RFC 3339、ローカルタイムゾーン）
+    pub ended_at: Option<String>,
+    /// 認識言語（ja-JP等）
+    pub language: String,
+    /// 確定テキスト（複数回確定した場合は改行で連結）
+    pub text: String,
+    /// セッション中にエラーが発生したかどうか
+    pub had_error: bool,
+    /// 最後に発生したエラー
+    pub last_error: Option<String>,
+}
+
+impl SpeechSession {
+    /// 現在時刻で新しいセッションを開始
+    pub fn start(language: &str) -> Self {
+        Self {
+            started_at: Local::now().to_rfc3339(),
+            ended_at: None,
+            language: language.to_string(),
+            text: String::new(),
+            had_error: false,
+            last_error: None,
+        }
+    }
+
+    /// 確定テキストを追加
+    pub fn push_text(&mut self, text: &str) {
+        let text = text.trim();
+        if text.is_empty() {
+            return;
+        }
+        if !self.text.is_empty() {
+            self.text.push('\n');
+        }
+        self.text.push_str(text);
+    }
+
+    /// エラーを記録
+    pub fn record_error(&mut self, error: &str) {
+        self.had_error = true;
+        self.last_error = Some(error.to_string());
+    }
+
+    /// 現在時刻で終了
+    pub fn finish(&mut self) {
+        self.ended_at = Some(Local::now().to_rfc3339());
+    }
+
+    /// 開始日（ローカル）
+    fn start_date(&self) -> Option<NaiveDate> {
+        DateTime::parse_from_rfc3339(&self.started_at)
+            .ok()
+            .map(|t| t.with_timezone(&Local).date_naive())
+    }
+}
+
+/// 履歴の絞り込み条件
+#[derive(Debug, Clone, Default)]
+pub struct HistoryFilter {
+    /// 言語（前方一致・大文字小文字を区別しない。"ja" で "ja-JP" も含む）
+    pub language: Option<String>,
+    /// この日以降に開始したセッション（YYYY-MM-DD、ローカル）
+    pub from: Option<NaiveDate>,
+    /// この日以前に開始したセッション（YYYY-MM-DD、ローカル）
+    pub to: Option<NaiveDate>,
+}
+
+impl HistoryFilter {
+    /// 文字列の条件から作成（日付が不正ならエラー）
+    pub fn parse(
+        language: Option<String>,
+        from: Option<&str>,
+        to: Option<&str>,
+    ) -> Result<Self, String> {
+        let parse_date = |date: &str| {
+            NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|e| format!("Invalid date '{}': {}", date, e))
+        };
+        Ok(Self {
+            language: language.filter(|l| !l.is_empty()),
+            from: from.map(parse_date).transpose()?,
+            to: to.map(parse_date).transpose()?,
+        })
+    }
+
+    fn matches(&self, session: &SpeechSession) -> bool {
+        if let Some(ref language) = self.language {
+            if !session
+                .language
+                .to_lowercase()
+                .starts_with(&language.to_lowercase())
+            {
+                return false;
+            }
+        }
+        if self.from.is_none() && self.to.is_none() {
+            return true;
+        }
+        let Some(date) = session.start_date() else {
+            return false;
+        };
+        self.from.is_none_or(|from| date >= from) && self.to.is_none_or(|to| date <= to)
+    }
+}
+
+/// 終了したセッションを履歴ファイルに追記
+pub fn append(path: &Path, session: &SpeechSession) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let line = serde_json::to_string(session).map_err(|e| e.to_string())?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    writeln!(file, "{}", line).map_err(|e| e.to_string())
+}
+
+/// 条件に合うセッションを新しい順に読み込む（ファイルが無ければ空、壊れた行は読み飛ばす）
+pub fn load(path: &Path, filter: &HistoryFilter) -> Result<Vec<SpeechSession>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut sessions: Vec<SpeechSession> = content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .filter(|session| filter.matches(session))
+        .collect();
+    sessions.reverse();
+    Ok(sessions)
+}
@@ -0,0 +1,124 @@
+use block2::RcBlock;
+use objc2::rc::Retained;
+use objc2_avf_audio::{AVAudioEngine, AVAudioPCMBuffer, AVAudioTime};
+use std::ptr::NonNull;
+
+/// 無音とみなす下限レベル（dBFS）
+const SILENCE_DB: f32 = -160.0;
+
+/// 入力ノードのオーディオタップに渡すブロック
+type TapBlock = RcBlock<dyn Fn(NonNull<AVAudioPCMBuffer>, NonNull<AVAudioTime>)>;
+
+/// マイク入力の音量レベルを監視する（認識とは別の AVAudioEngine を使う）
+pub struct LevelMonitor {
+    audio_engine: Retained<AVAudioEngine>,
+    is_running: bool,
+    /// 設置中のオーディオタップのブロック（監視中のみ Some）
+    /// タップを外すまで確実に生きているよう、ここで所有して停止時に破棄する
+    tap_block: Option<TapBlock>,
+}
+
+// 明示的にSend+Syncを実装（Objective-Cオブジェクトはスレッドセーフ）
+unsafe impl Send for LevelMonitor {}
+unsafe impl Sync for LevelMonitor {}
+
+impl LevelMonitor {
+    /// 新しいLevelMonitorを作成
+    pub fn new() -> Self {
+        unsafe {
+            Self {
+                audio_engine: AVAudioEngine::new(),
+                is_running: false,
+                tap_block: None,
+            }
+        }
+    }
+
+    /// 監視を開始し、バッファごとの音量（dBFS）をコールバックに渡す
+    pub fn start<F>(&mut self, callback: F) -> Result<(), String>
+    where
+        F: Fn(f32) + Send + 'static,
+    {
+        if self.is_running {
+            return Ok(());
+        }
+
+        unsafe {
+            let input_node = self.audio_engine.inputNode();
+            let format = input_node.outputFormatForBus(0);
+
+            let tap_block =
+                RcBlock::new(move |buffer: NonNull<AVAudioPCMBuffer>, _when: NonNull<AVAudioTime>| {
+                    callback(buffer_level_db(buffer.as_ref()));
+                });
+
+            input_node.installTapOnBus_bufferSize_format_block(
+                0,
+                1024,
+                Some(&format),
+                &*tap_block as *const _ as *mut _,
+            );
+
+            self.audio_engine.prepare();
+            if let Err(e) = self.audio_engine.startAndReturnError() {
+                input_node.removeTapOnBus(0);
+                return Err(format!(
+                    "Failed to start level monitor: {}",
+                    e.localizedDescription()
+                ));
+            }
+            self.tap_block = Some(tap_block);
+        }
+
+        self.is_running = true;
+        Ok(())
+    }
+
+    /// 監視を停止
+    pub fn stop(&mut self) {
+        if !self.is_running {
+            return;
+        }
+
+        unsafe {
+            self.audio_engine.stop();
+            self.audio_engine.inputNode().removeTapOnBus(0);
+        }
+        // タップを外してからブロックを破棄する
+        self.tap_block = None;
+        self.is_running = false;
+    }
+}
+
+impl Drop for LevelMonitor {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// バッファ（先頭チャンネル）の RMS を dBFS で返す
+pub(crate) fn buffer_level_db(buffer: &AVAudioPCMBuffer) -> f32 {
+    unsafe {
+        let channels = buffer.floatChannelData();
+        let frames = buffer.frameLength() as usize;
+        if channels.is_null() || frames == 0 {
+            return SILENCE_DB;
+        }
+
+        let stride = buffer.stride().max(1);
+        let samples = (*channels).as_ptr();
+        let sum_squares: f32 = (0..frames)
+            .map(|i| {
+                let sample = *samples.add(i * stride);
+                sample * sample
+            })
+            .sum();
+        let rms = (sum_squares / frames as f32).sqrt();
+
+        if rms > 0.0 {
+            (20.0 * rms.log10()).max(SILENCE_DB)
+        } else {
+            SILENCE_DB
+        }
+    }
+}
@@ -0,0 +1,58 @@
+/// 言語コードだけのアプリロケールに対して優先する音声ロケール
+const PREFERRED_REGIONS: &[(&str, &str)] = &[
+    ("ja", "ja-JP"),
+    ("en", "en-US"),
+    ("zh", "zh-CN"),
+    ("ko", "ko-KR"),
+    ("fr", "fr-FR"),
+    ("de", "de-DE"),
+    ("es", "es-ES"),
+    ("pt", "pt-BR"),
+];
+
+/// アプリロケール（"ja"、"en_GB"、"zh-Hant-TW" 等）に対応する音声ロケールを探す
+///
+/// 完全一致 → 言語コードごとの優先ロケール → 同じ言語コードの最初のロケールの順に探し、
+/// 対応するものが無ければ None を返す。
+pub fn speech_locale_for(app_locale: &str, supported: &[String]) -> Option<String> {
+    let app_locale = app_locale.trim().replace('_', "-");
+    let mut parts = app_locale.split('-').filter(|part| !part.is_empty());
+    let language = parts.next()?.to_lowercase();
+    // スクリプト（Hant 等）を除いた言語-地域
+    let region = parts.find(|part| part.len() == 2 || part.len() == 3);
+
+    let find = |tag: &str| {
+        supported
+            .iter()
+            .find(|locale| locale.eq_ignore_ascii_case(tag))
+            .cloned()
+    };
+
+    if let Some(found) = find(&app_locale) {
+        return Some(found);
+    }
+    if let Some(found) = region.and_then(|region| find(&format!("{}-{}", language, region))) {
+        return Some(found);
+    }
+    if language == "zh" && app_locale.to_lowercase().contains("hant") {
+        if let Some(found) = find("zh-TW") {
+            return Some(found);
+        }
+    }
+    if let Some(found) = PREFERRED_REGIONS
+        .iter()
+        .find(|(code, _)| *code == language)
+        .and_then(|(_, tag)| find(tag))
+    {
+        return Some(found);
+    }
+    supported
+        .iter()
+        .find(|locale| {
+            locale
+                .split('-')
+                .next()
+                .is_some_and(|code| code.eq_ignore_ascii_case(&language))
+        })
+        .cloned()
+}
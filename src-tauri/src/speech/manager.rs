@@ -1,23 +1,83 @@
-use std::sync::Mutex;
-use tauri::Emitter;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::Manager;
 
-use crate::speech::config::{RecognitionResult, SpeechConfig, SpeechRecognitionState};
-use crate::speech::recognizer::SpeechRecognizerWrapper;
+use crate::events;
+use crate::fs_util;
+use crate::speech::config::{
+    RecognitionError, RecognitionResult, ResultFormat, SpeechAudioLevelPayload, SpeechConfig,
+    SpeechDisabledPayload, SpeechRecognitionState, SpeechSegmentBoundaryPayload,
+    SpeechSentencePayload, SpeechStatePayload, WebSpeechResult,
+};
+use crate::speech::data_dir::{self, SpeechDataKind};
+use crate::speech::decorator::{self, DecorationRule};
+use crate::speech::dictionary::{self, UserDictionary, DICTIONARY_FILE_NAME};
+use crate::speech::hands_free::{
+    HandsFreeAction, HandsFreeDetector, HandsFreeState, HandsFreeStatePayload,
+};
+use crate::speech::history::{self, HistoryFilter, SpeechSession, HISTORY_FILE_NAME};
+use crate::speech::level_monitor::LevelMonitor;
+use crate::speech::locale;
+use crate::speech::normalizer;
+use crate::speech::profile::SpeechProfile;
+use crate::speech::recognizer::{LevelCallback, ListenOptions, SpeechRecognizerWrapper};
+use crate::speech::segmenter::PauseSegmenter;
+use crate::speech::sentence;
+use crate::speech::sleep_guard::{SleepGuard, SleepPrevention};
+use crate::speech::stabilizer::PartialStabilizer;
+use crate::speech::throttle::PartialThrottle;
+use crate::speech::utterance_filter::UtteranceFilter;
 
 /// 音声認識マネージャー
 pub struct SpeechManager {
-    config: Mutex<SpeechConfig>,
-    state: Mutex<SpeechRecognitionState>,
+    config: Arc<Mutex<SpeechConfig>>,
+    /// 認識の状態（エラーで認識が終わったときは認識スレッドから更新する）
+    state: Arc<Mutex<SpeechRecognitionState>>,
     recognizer: Mutex<Option<SpeechRecognizerWrapper>>,
+    /// 連続エラー回数（正常な認識でリセット）
+    consecutive_errors: Arc<AtomicU32>,
+    /// 連続エラーで自動的に無効化されたか（`reset_errors` で解除）
+    disabled_by_errors: Arc<AtomicBool>,
+    /// ハンズフリーモードの監視（モード中のみ Some）
+    hands_free: Mutex<Option<HandsFreeSession>>,
+    /// 最後に送った部分結果（final を受け取ると None）。言語切替時に確定させる
+    last_partial: Arc<Mutex<Option<RecognitionResult>>>,
+    /// 進行中の認識セッション（停止時に履歴へ保存）
+    current_session: Arc<Mutex<Option<SpeechSession>>>,
+    /// 認識中のスリープ抑止（停止時やエラーで認識が終わったときに解除）
+    sleep_guard: Arc<Mutex<Option<SleepGuard>>>,
+    /// 読み込み済みのユーザー辞書（初回アクセスでファイルから読む。保存先の変更で破棄）
+    dictionary: Mutex<Option<UserDictionary>>,
+    /// 認識中の部分結果の間引き（停止時に送られていない部分結果を捨てる）
+    partial_throttle: Mutex<Option<Arc<PartialThrottle>>>,
+}
+
+/// ハンズフリーモード中の音量監視
+struct HandsFreeSession {
+    monitor: LevelMonitor,
+    /// 判定スレッドの停止フラグ
+    stop_flag: Arc<AtomicBool>,
 }
 
 impl SpeechManager {
     /// 新しいSpeechManagerを作成
     pub fn new() -> Self {
         Self {
-            config: Mutex::new(SpeechConfig::default()),
-            state: Mutex::new(SpeechRecognitionState::Idle),
+            config: Arc::new(Mutex::new(SpeechConfig::default())),
+            state: Arc::new(Mutex::new(SpeechRecognitionState::Idle)),
             recognizer: Mutex::new(None),
+            consecutive_errors: Arc::new(AtomicU32::new(0)),
+            disabled_by_errors: Arc::new(AtomicBool::new(false)),
+            hands_free: Mutex::new(None),
+            last_partial: Arc::new(Mutex::new(None)),
+            current_session: Arc::new(Mutex::new(None)),
+            sleep_guard: Arc::new(Mutex::new(None)),
+            dictionary: Mutex::new(None),
+            partial_throttle: Mutex::new(None),
         }
     }
 
@@ -34,28 +94,200 @@ impl SpeechManager {
             }
         }
 
+        if self.disabled_by_errors.load(Ordering::Relaxed) {
+            return Err("Speech recognition is disabled after repeated errors".to_string());
+        }
+
         // 設定を取得
-        let language = {
+        let config_snapshot = {
             let config = self.config.lock().map_err(|e| e.to_string())?;
-            config.language.clone()
+            config.clone()
+        };
+        let record_audio_path = match config_snapshot.record_audio_path {
+            Some(ref path) => Some(self.resolve_record_path(app, path)?),
+            None => None,
         };
+        let language = config_snapshot.language.clone();
+        let normalize = config_snapshot.normalize;
+        let result_format = config_snapshot.result_format;
+        let sentence_delimiters = sentence_delimiters(&config_snapshot);
+        let decoration_rules = decoration_rules(&config_snapshot);
+        // 辞書が読めなくても認識はプロファイルの語彙だけで続ける
+        let dictionary_words = self.list_dictionary(app).unwrap_or_else(|e| {
+            eprintln!("[SpeechManager] Failed to load speech dictionary: {}", e);
+            Vec::new()
+        });
+        let contextual_strings =
+            dictionary::contextual_strings(&config_snapshot.vocabulary, &dictionary_words);
 
         // 認識器を初期化
-        let mut recognizer_guard = self.recognizer.lock().map_err(|e| e.to_string())?;
-        if recognizer_guard.is_none() {
-            *recognizer_guard = Some(SpeechRecognizerWrapper::new(&language)?);
+        let ended_by_error = {
+            let mut recognizer_guard = self.recognizer.lock().map_err(|e| e.to_string())?;
+            match *recognizer_guard {
+                // エラーで終わった前回の認識が停止されないまま残っていれば片付ける
+                Some(ref recognizer) if recognizer.is_listening() => {
+                    recognizer.stop_listening()?;
+                    true
+                }
+                Some(_) => false,
+                None => match SpeechRecognizerWrapper::new(&language) {
+                    Ok(recognizer) => {
+                        *recognizer_guard = Some(recognizer);
+                        false
+                    }
+                    Err(e) => {
+                        self.record_start_error(app, &e);
+                        return Err(e);
+                    }
+                },
+            }
+        };
+        if ended_by_error {
+            self.finish_session(app);
+        }
+
+        // エラー時の後始末は、結果を加工するコールバックとは別のリスナーで行う
+        {
+            let app_handle = app.clone();
+            let config = self.config.clone();
+            let state = self.state.clone();
+            let consecutive_errors = self.consecutive_errors.clone();
+            let disabled_by_errors = self.disabled_by_errors.clone();
+            let sleep_guard = self.sleep_guard.clone();
+            let session = self.current_session.clone();
+            self.add_result_listener(move |result| {
+                let Err(e) = result else {
+                    return;
+                };
+                // エラーで認識タスクは終わっているので、スリープ抑止を残さない
+                if let Ok(mut guard) = sleep_guard.lock() {
+                    guard.take();
+                }
+                if e.transient {
+                    // 無音などで終わっただけなので、連続エラーには数えずに停止した扱いにする
+                    end_listening(&app_handle, &state, SpeechRecognitionState::Idle);
+                    return;
+                }
+                record_error(
+                    &app_handle,
+                    &config,
+                    &consecutive_errors,
+                    &disabled_by_errors,
+                    &e.message,
+                );
+                if let Ok(mut session) = session.lock() {
+                    if let Some(ref mut session) = *session {
+                        session.record_error(&e.message);
+                    }
+                }
+                end_listening(&app_handle, &state, SpeechRecognitionState::Error);
+            })?;
         }
 
         // コールバックを設定して開始
         let app_handle = app.clone();
+        let stabilizer = Mutex::new(PartialStabilizer::new(config_snapshot.commit_stable_ms));
+        let utterance_filter = Mutex::new(UtteranceFilter::new(
+            config_snapshot.min_utterance_chars,
+            config_snapshot.min_utterance_ms,
+        ));
+        let segmenter = Mutex::new(PauseSegmenter::new(config_snapshot.segment_pause_ms));
+        let throttle = {
+            let app = app.clone();
+            Arc::new(PartialThrottle::new(
+                config_snapshot.partial_debounce_ms,
+                move |result| emit_result(&app, result_format, result),
+            ))
+        };
+        *self.partial_throttle.lock().map_err(|e| e.to_string())? = Some(throttle.clone());
+        let consecutive_errors = self.consecutive_errors.clone();
+        let normalize_language = language.clone();
+        let last_partial = self.last_partial.clone();
+        let session = self.current_session.clone();
+        if let Ok(mut partial) = last_partial.lock() {
+            *partial = None;
+        }
+        if let Ok(mut session) = session.lock() {
+            *session = Some(SpeechSession::start(&language));
+        }
+        let recognizer_guard = self.recognizer.lock().map_err(|e| e.to_string())?;
         if let Some(ref recognizer) = *recognizer_guard {
-            recognizer.start_listening(move |result: RecognitionResult| {
-                println!("[SpeechManager] Emitting result: '{}' (final: {})", result.text, result.is_final);
-                // フロントエンドにイベント送信
-                if let Err(e) = app_handle.emit("speech-recognition-result", &result) {
-                    eprintln!("[SpeechManager] Failed to emit event: {}", e);
+            let options = ListenOptions {
+                record_audio_path: record_audio_path.as_deref(),
+                adds_punctuation: config_snapshot.adds_punctuation,
+                contextual_strings: &contextual_strings,
+                on_level: Some(audio_level_emitter(app)),
+            };
+            let started = recognizer.start_listening(&options, move |result| {
+                let mut result = match result {
+                    Ok(result) => result,
+                    // エラーは開始前に登録したリスナーが処理する
+                    Err(_) => return,
+                };
+                // 正常に認識できたら連続エラーをリセット
+                consecutive_errors.store(0, Ordering::Relaxed);
+
+                // 確定テキストのみ正規化（部分結果は準確定の判定を乱さないようそのまま）
+                if normalize && result.is_final {
+                    let normalized = normalizer::normalize(&result.text, &normalize_language);
+                    if normalized != result.text {
+                        println!("[SpeechManager] Normalized: '{}' -> '{}'", result.text, normalized);
+                        result.text = normalized;
+                    }
                 }
-            })?;
+
+                if let (true, Some(rules)) = (result.is_final, decoration_rules.as_deref()) {
+                    apply_decoration(&mut result, &normalize_language, rules);
+                }
+
+                if let Ok(mut stabilizer) = stabilizer.lock() {
+                    stabilizer.apply(&mut result);
+                }
+                if let Ok(mut segmenter) = segmenter.lock() {
+                    let (segment_index, pause) = segmenter.segment(Instant::now());
+                    result.segment_index = segment_index;
+                    if let Some(pause) = pause {
+                        emit_segment_boundary(&app_handle, segment_index, pause);
+                    }
+                }
+                if let Ok(mut partial) = last_partial.lock() {
+                    *partial = if result.is_final { None } else { Some(result.clone()) };
+                }
+                let accepted = utterance_filter
+                    .lock()
+                    .map(|mut filter| filter.accept(&result, Instant::now()))
+                    .unwrap_or(true);
+                if !accepted {
+                    // 短すぎる final は送らず、表示中の部分結果を空の部分結果で消す
+                    println!("[SpeechManager] Discarded short utterance: '{}'", result.text);
+                    throttle.cancel();
+                    let cleared = RecognitionResult::new(String::new(), false);
+                    emit_result(&app_handle, result_format, &cleared);
+                    return;
+                }
+                if result.is_final {
+                    if let Ok(mut session) = session.lock() {
+                        if let Some(ref mut session) = *session {
+                            session.push_text(&result.text);
+                        }
+                    }
+                }
+                throttle.submit(result.clone());
+                if let (true, Some(delimiters)) = (result.is_final, sentence_delimiters.as_deref()) {
+                    emit_sentences(&app_handle, result.plain_text.as_ref().unwrap_or(&result.text), delimiters);
+                }
+            });
+            if let Err(e) = started {
+                self.record_start_error(app, &e);
+                self.current_session.lock().map_err(|e| e.to_string())?.take();
+                return Err(e);
+            }
+        }
+
+        // 認識を開始できた場合だけスリープを抑止する（失敗時に解除漏れが起きないように）
+        {
+            let mut guard = self.sleep_guard.lock().map_err(|e| e.to_string())?;
+            *guard = SleepGuard::begin(config_snapshot.prevent_sleep);
         }
 
         // 状態を更新
@@ -65,24 +297,31 @@ impl SpeechManager {
         }
 
         // 状態変更イベント送信
-        app.emit("speech-state-changed", SpeechRecognitionState::Listening)
-            .map_err(|e| e.to_string())?;
+        emit_state(app, SpeechRecognitionState::Listening)?;
 
         Ok(())
     }
 
     /// 音声認識を停止
+    /// 録音していた場合は保存したファイルのパスを返す
     pub fn stop_recognition<R: tauri::Runtime>(
         &self,
         app: &tauri::AppHandle<R>,
-    ) -> Result<(), String> {
+    ) -> Result<Option<String>, String> {
         // 認識器を停止
-        {
+        let recorded_path = {
             let recognizer_guard = self.recognizer.lock().map_err(|e| e.to_string())?;
-            if let Some(ref recognizer) = *recognizer_guard {
-                recognizer.stop_listening()?;
+            match *recognizer_guard {
+                Some(ref recognizer) => recognizer.stop_listening()?,
+                None => None,
             }
-        }
+        };
+
+        // 認識器のリスナーは停止時にすべて解除されている。送られていない部分結果を捨てる
+        self.cancel_partial_throttle()?;
+        self.sleep_guard.lock().map_err(|e| e.to_string())?.take();
+
+        self.finish_session(app);
 
         // 状態を更新
         {
@@ -91,9 +330,149 @@ impl SpeechManager {
         }
 
         // 状態変更イベント送信
-        app.emit("speech-state-changed", SpeechRecognitionState::Idle)
-            .map_err(|e| e.to_string())?;
+        emit_state(app, SpeechRecognitionState::Idle)?;
+
+        Ok(recorded_path)
+    }
+
+    /// 認識結果のリスナーを追加する（次の停止ですべて解除される）
+    ///
+    /// リスナーは認識器に登録され、認識スレッドから加工前の結果（またはエラー）が渡される。
+    /// 停止中に追加したリスナーは次の認識から結果を受け取る
+    pub fn add_result_listener<F>(&self, listener: F) -> Result<(), String>
+    where
+        F: Fn(Result<RecognitionResult, RecognitionError>) + Send + Sync + 'static,
+    {
+        let recognizer_guard = self.recognizer.lock().map_err(|e| e.to_string())?;
+        match *recognizer_guard {
+            Some(ref recognizer) => recognizer.add_result_listener(listener),
+            None => Err("Speech recognizer is not initialized".to_string()),
+        }
+    }
+
+    /// アイドルなら開始、リスニング中なら停止する
+    /// 開始した場合は true、停止した場合は false を返す。
+    /// 処理中・エラー状態では何もせずにエラーを返す（エラーからは start で再開する）
+    ///
+    /// 呼び出し側は SpeechManager のロックを保持したまま呼ぶので、判定から遷移までの間に
+    /// 他の開始/停止が割り込むことはない
+    pub fn toggle_recognition<R: tauri::Runtime>(
+        &self,
+        app: &tauri::AppHandle<R>,
+    ) -> Result<bool, String> {
+        match self.get_state()? {
+            SpeechRecognitionState::Idle => {
+                self.start_recognition(app)?;
+                Ok(true)
+            }
+            SpeechRecognitionState::Listening => {
+                self.stop_recognition(app)?;
+                Ok(false)
+            }
+            state => Err(format!("Cannot toggle speech recognition while {:?}", state)),
+        }
+    }
+
+    /// ハンズフリーモードを開始
+    ///
+    /// 音量レベルを監視し、しきい値の判定結果を `on_action` に渡す。
+    /// `on_action` は別スレッドから呼ばれるので、そこで `apply_hands_free_action` を呼ぶ。
+    pub fn enable_hands_free<R, F>(&self, app: &tauri::AppHandle<R>, on_action: F) -> Result<(), String>
+    where
+        R: tauri::Runtime,
+        F: Fn(HandsFreeAction) -> Result<(), String> + Send + 'static,
+    {
+        let mut hands_free = self.hands_free.lock().map_err(|e| e.to_string())?;
+        if hands_free.is_some() {
+            return Ok(());
+        }
+
+        let mut detector = {
+            let config = self.config.lock().map_err(|e| e.to_string())?;
+            HandsFreeDetector::new(&config)
+        };
+
+        // オーディオスレッドでは音量を送るだけにして、判定と開始/停止は別スレッドで行う
+        let (level_tx, level_rx) = mpsc::channel::<f32>();
+        let mut monitor = LevelMonitor::new();
+        monitor.start(move |level| {
+            let _ = level_tx.send(level);
+        })?;
+
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let worker_stop_flag = stop_flag.clone();
+        thread::spawn(move || {
+            while !worker_stop_flag.load(Ordering::Relaxed) {
+                let level = match level_rx.recv_timeout(Duration::from_millis(200)) {
+                    Ok(level) => level,
+                    Err(RecvTimeoutError::Timeout) => continue,
+                    Err(RecvTimeoutError::Disconnected) => break,
+                };
+                if let Some(action) = detector.process(level, Instant::now()) {
+                    if let Err(e) = on_action(action) {
+                        eprintln!("[SpeechManager] Hands-free {:?} failed: {}", action, e);
+                        // 失敗した場合は切り替え前の状態に戻す
+                        detector.set_listening(action == HandsFreeAction::Stop);
+                    }
+                }
+            }
+        });
+
+        *hands_free = Some(HandsFreeSession { monitor, stop_flag });
+        {
+            let mut config = self.config.lock().map_err(|e| e.to_string())?;
+            config.hands_free = true;
+        }
+
+        emit_hands_free_state(app, HandsFreeState::Waiting);
+        Ok(())
+    }
+
+    /// ハンズフリーモードを終了（認識中なら停止する）
+    pub fn disable_hands_free<R: tauri::Runtime>(&self, app: &tauri::AppHandle<R>) -> Result<(), String> {
+        let session = self.hands_free.lock().map_err(|e| e.to_string())?.take();
+        {
+            let mut config = self.config.lock().map_err(|e| e.to_string())?;
+            config.hands_free = false;
+        }
+
+        let Some(mut session) = session else {
+            return Ok(());
+        };
+        session.stop_flag.store(true, Ordering::Relaxed);
+        session.monitor.stop();
+
+        if self.get_state()? == SpeechRecognitionState::Listening {
+            self.stop_recognition(app)?;
+        }
+
+        emit_hands_free_state(app, HandsFreeState::Off);
+        Ok(())
+    }
+
+    /// ハンズフリーの判定結果に従って認識を開始/停止する
+    pub fn apply_hands_free_action<R: tauri::Runtime>(
+        &self,
+        app: &tauri::AppHandle<R>,
+        action: HandsFreeAction,
+    ) -> Result<(), String> {
+        // 判定スレッドがロック待ちの間にモードが終了していたら何もしない
+        if self.hands_free.lock().map_err(|e| e.to_string())?.is_none() {
+            return Ok(());
+        }
 
+        match action {
+            HandsFreeAction::Start => {
+                if self.get_state()? != SpeechRecognitionState::Listening {
+                    self.start_recognition(app)?;
+                }
+                emit_hands_free_state(app, HandsFreeState::Listening);
+            }
+            HandsFreeAction::Stop => {
+                self.stop_recognition(app)?;
+                emit_hands_free_state(app, HandsFreeState::Waiting);
+            }
+        }
         Ok(())
     }
 
@@ -103,6 +482,45 @@ impl SpeechManager {
         Ok(*state)
     }
 
+    /// オーディオエンジンが実際に動いているかを確認し、状態とずれていれば補正する
+    ///
+    /// - Listening なのにエンジンが止まっている（エラーで停止した等）→ 認識器を片付けて Idle にする
+    /// - Listening でないのにエンジンが動いている → 認識器を停止する
+    ///
+    /// 言語切替中（Processing）は一時的に止まっているだけなので補正しない
+    pub fn is_actually_listening<R: tauri::Runtime>(
+        &self,
+        app: &tauri::AppHandle<R>,
+    ) -> Result<bool, String> {
+        let engine_running = {
+            let recognizer_guard = self.recognizer.lock().map_err(|e| e.to_string())?;
+            recognizer_guard
+                .as_ref()
+                .map(|recognizer| recognizer.is_engine_running())
+                .unwrap_or(false)
+        };
+        let state = self.get_state()?;
+
+        match state {
+            SpeechRecognitionState::Listening if !engine_running => {
+                println!("[SpeechManager] Audio engine stopped while listening, resetting state");
+                self.stop_recognition(app)?;
+                Ok(false)
+            }
+            SpeechRecognitionState::Listening => Ok(true),
+            SpeechRecognitionState::Processing => Ok(false),
+            _ if engine_running => {
+                println!("[SpeechManager] Audio engine running while not listening, stopping it");
+                let recognizer_guard = self.recognizer.lock().map_err(|e| e.to_string())?;
+                if let Some(ref recognizer) = *recognizer_guard {
+                    recognizer.stop_listening()?;
+                }
+                Ok(false)
+            }
+            _ => Ok(false),
+        }
+    }
+
     /// 設定を取得
     #[allow(dead_code)]
     pub fn get_config(&self) -> Result<SpeechConfig, String> {
@@ -110,18 +528,630 @@ impl SpeechManager {
         Ok(config.clone())
     }
 
-    /// 言語を設定
-    #[allow(dead_code)]
-    pub fn set_language(&self, language: String) -> Result<(), String> {
+    /// 連続エラー回数をリセットし、無効化された機能を再度有効にする
+    pub fn reset_errors(&self) -> Result<(), String> {
+        self.consecutive_errors.store(0, Ordering::Relaxed);
+        self.disabled_by_errors.store(false, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// 連続エラーで自動的に無効化されているか
+    pub fn is_disabled_by_errors(&self) -> bool {
+        self.disabled_by_errors.load(Ordering::Relaxed)
+    }
+
+    /// 開始に失敗したエラーを連続エラーとして記録する
+    fn record_start_error<R: tauri::Runtime>(&self, app: &tauri::AppHandle<R>, error: &str) {
+        record_error(
+            app,
+            &self.config,
+            &self.consecutive_errors,
+            &self.disabled_by_errors,
+            error,
+        );
+    }
+
+    /// 認識結果イベントの形式を設定
+    pub fn set_result_format(&self, format: ResultFormat) -> Result<(), String> {
         let mut config = self.config.lock().map_err(|e| e.to_string())?;
-        config.language = language;
+        config.result_format = format;
+        Ok(())
+    }
+
+    /// 確定テキストの正規化を有効/無効にする
+    pub fn set_normalize(&self, enabled: bool) -> Result<(), String> {
+        let mut config = self.config.lock().map_err(|e| e.to_string())?;
+        config.normalize = enabled;
+        Ok(())
+    }
+
+    /// 短すぎる確定結果を捨てる基準を設定する（どちらも 0 なら全て通す）
+    /// 次に認識を開始したときから反映される
+    pub fn set_min_utterance(&self, min_chars: usize, min_ms: u64) -> Result<(), String> {
+        let mut config = self.config.lock().map_err(|e| e.to_string())?;
+        config.min_utterance_chars = min_chars;
+        config.min_utterance_ms = min_ms;
+        Ok(())
+    }
+
+    /// 話者セグメントを区切るポーズの長さ（ms）を設定する（0 なら区切らない）
+    /// 次に認識を開始したときから反映される
+    pub fn set_segment_pause(&self, pause_ms: u64) -> Result<(), String> {
+        let mut config = self.config.lock().map_err(|e| e.to_string())?;
+        config.segment_pause_ms = pause_ms;
+        Ok(())
+    }
+
+    /// 部分結果を送る最小間隔（ms）を設定する（0 なら部分結果をすべてすぐ送る）
+    /// 次に認識を開始したときから反映される
+    pub fn set_partial_debounce(&self, debounce_ms: u64) -> Result<(), String> {
+        let mut config = self.config.lock().map_err(|e| e.to_string())?;
+        config.partial_debounce_ms = debounce_ms;
+        Ok(())
+    }
+
+    /// 確定テキストの文分割を有効/無効にする
+    /// 区切り文字が None なら言語ごとの既定（日本語・中国語は「。！？」、それ以外は「.!?」）
+    pub fn set_sentence_split(&self, enabled: bool, delimiters: Option<String>) -> Result<(), String> {
+        let mut config = self.config.lock().map_err(|e| e.to_string())?;
+        config.split_sentences = enabled;
+        config.sentence_delimiters = delimiters.filter(|d| !d.is_empty());
+        Ok(())
+    }
+
+    /// 確定テキストの Markdown 装飾を有効/無効にする
+    /// ルールが None なら現在のルールのまま（既定は日本語・英語のトリガー語）
+    pub fn set_decoration(&self, enabled: bool, rules: Option<Vec<DecorationRule>>) -> Result<(), String> {
+        let mut config = self.config.lock().map_err(|e| e.to_string())?;
+        config.decorate = enabled;
+        if let Some(rules) = rules {
+            config.decoration_rules = rules;
+        }
+        Ok(())
+    }
+
+    /// 認識中に抑止するスリープの種類を設定
+    /// 認識中に変更した場合はすぐに新しい種類で抑止し直す
+    pub fn set_sleep_prevention(&self, kind: SleepPrevention) -> Result<(), String> {
+        {
+            let mut config = self.config.lock().map_err(|e| e.to_string())?;
+            config.prevent_sleep = kind;
+        }
+        let mut guard = self.sleep_guard.lock().map_err(|e| e.to_string())?;
+        if guard.is_some() || self.get_state()? == SpeechRecognitionState::Listening {
+            // 先に古いアサーションを解除してから新しく立てる
+            guard.take();
+            *guard = SleepGuard::begin(kind);
+        }
+        Ok(())
+    }
+
+    /// 録音ファイルの保存先を設定（None で録音しない）
+    pub fn set_record_audio_path(&self, path: Option<String>) -> Result<(), String> {
+        let mut config = self.config.lock().map_err(|e| e.to_string())?;
+        config.record_audio_path = path;
+        Ok(())
+    }
+
+    /// データ保存先のベースディレクトリを設定（None で app_data_dir に戻す）
+    /// 書き込めないディレクトリはエラーにする
+    pub fn set_data_dir(&self, path: Option<String>) -> Result<(), String> {
+        if let Some(ref path) = path {
+            fs_util::ensure_writable(Path::new(path), "speech data dir")?;
+        }
+        let mut config = self.config.lock().map_err(|e| e.to_string())?;
+        config.data_dir = path;
+        // 辞書は新しい保存先から読み直す
+        *self.dictionary.lock().map_err(|e| e.to_string())? = None;
+        Ok(())
+    }
+
+    /// データ保存先のベースディレクトリ
+    pub fn data_dir<R: tauri::Runtime>(&self, app: &tauri::AppHandle<R>) -> Result<PathBuf, String> {
+        let config = self.config.lock().map_err(|e| e.to_string())?;
+        match config.data_dir {
+            Some(ref dir) => Ok(PathBuf::from(dir)),
+            None => app.path().app_data_dir().map_err(|e| e.to_string()),
+        }
+    }
+
+    /// 録音ファイルのパスを解決（相対パスは recordings 配下）
+    fn resolve_record_path<R: tauri::Runtime>(
+        &self,
+        app: &tauri::AppHandle<R>,
+        path: &str,
+    ) -> Result<String, String> {
+        let path = Path::new(path);
+        if path.is_absolute() {
+            return Ok(path.to_string_lossy().to_string());
+        }
+        let dir = data_dir::subdir(&self.data_dir(app)?, SpeechDataKind::Recordings)?;
+        Ok(dir.join(path).to_string_lossy().to_string())
+    }
+
+    /// プロファイルの言語・句読点・語彙を適用する
+    /// 認識器はリセットされ、次回開始時に新しい設定で初期化される
+    pub fn apply_profile(&self, profile: &SpeechProfile) -> Result<(), String> {
+        if self.get_state()? == SpeechRecognitionState::Listening {
+            return Err("Stop speech recognition before switching profiles".to_string());
+        }
+
+        {
+            let mut config = self.config.lock().map_err(|e| e.to_string())?;
+            config.language = profile.language.clone();
+            config.adds_punctuation = profile.adds_punctuation;
+            config.vocabulary = profile.vocabulary.clone();
+            config.profile = Some(profile.name.clone());
+        }
 
-        // 認識器をリセット（次回開始時に新しい言語で初期化される）
         let mut recognizer = self.recognizer.lock().map_err(|e| e.to_string())?;
         *recognizer = None;
 
         Ok(())
     }
+
+    /// 言語を設定
+    ///
+    /// リスニング中の場合は認識を止めて進行中の部分結果を final として送ってから切り替える。
+    /// 切替中は `Processing` 状態になり、`resume` が true なら新しい言語で認識を再開する。
+    pub fn set_language<R: tauri::Runtime>(
+        &self,
+        app: &tauri::AppHandle<R>,
+        language: String,
+        resume: bool,
+    ) -> Result<(), String> {
+        let was_listening = self.get_state()? == SpeechRecognitionState::Listening;
+
+        if was_listening {
+            self.set_state(app, SpeechRecognitionState::Processing)?;
+            {
+                let recognizer_guard = self.recognizer.lock().map_err(|e| e.to_string())?;
+                if let Some(ref recognizer) = *recognizer_guard {
+                    recognizer.stop_listening()?;
+                }
+            }
+            self.commit_last_partial(app)?;
+            // 言語ごとにセッションを分ける（再開時は新しい言語で別のセッションになる）
+            self.finish_session(app);
+        }
+
+        {
+            let mut config = self.config.lock().map_err(|e| e.to_string())?;
+            config.language = language;
+        }
+
+        // 認識器をリセット（次回開始時に新しい言語で初期化される）
+        {
+            let mut recognizer = self.recognizer.lock().map_err(|e| e.to_string())?;
+            *recognizer = None;
+        }
+
+        if !was_listening {
+            return Ok(());
+        }
+        if resume {
+            if let Err(e) = self.start_recognition(app) {
+                self.sleep_guard.lock().map_err(|e| e.to_string())?.take();
+                self.set_state(app, SpeechRecognitionState::Idle)?;
+                return Err(e);
+            }
+            Ok(())
+        } else {
+            self.sleep_guard.lock().map_err(|e| e.to_string())?.take();
+            self.set_state(app, SpeechRecognitionState::Idle)
+        }
+    }
+
+    /// アプリロケールの変更を通知する
+    ///
+    /// 追従が有効なら対応する音声ロケールに切り替え、切り替えた言語を返す。
+    /// 対応する音声ロケールが無い場合や追従が無効な場合は現在の言語のまま（None）。
+    pub fn set_app_locale<R: tauri::Runtime>(
+        &self,
+        app: &tauri::AppHandle<R>,
+        locale: String,
+    ) -> Result<Option<String>, String> {
+        {
+            let mut config = self.config.lock().map_err(|e| e.to_string())?;
+            config.app_locale = Some(locale).filter(|l| !l.is_empty());
+        }
+        self.follow_app_locale(app)
+    }
+
+    /// アプリロケールへの追従を有効/無効にする
+    /// 有効にした場合は最後に通知されたアプリロケールにすぐ合わせ、切り替えた言語を返す
+    pub fn set_follow_app_locale<R: tauri::Runtime>(
+        &self,
+        app: &tauri::AppHandle<R>,
+        enabled: bool,
+    ) -> Result<Option<String>, String> {
+        {
+            let mut config = self.config.lock().map_err(|e| e.to_string())?;
+            config.follow_app_locale = enabled;
+        }
+        self.follow_app_locale(app)
+    }
+
+    /// 追従が有効ならアプリロケールに対応する音声ロケールへ切り替える
+    fn follow_app_locale<R: tauri::Runtime>(
+        &self,
+        app: &tauri::AppHandle<R>,
+    ) -> Result<Option<String>, String> {
+        let (app_locale, current) = {
+            let config = self.config.lock().map_err(|e| e.to_string())?;
+            if !config.follow_app_locale {
+                return Ok(None);
+            }
+            match config.app_locale {
+                Some(ref app_locale) => (app_locale.clone(), config.language.clone()),
+                None => return Ok(None),
+            }
+        };
+
+        let supported = SpeechRecognizerWrapper::supported_locales();
+        let Some(language) = locale::speech_locale_for(&app_locale, &supported) else {
+            return Ok(None);
+        };
+        if language.eq_ignore_ascii_case(&current) {
+            return Ok(None);
+        }
+        self.set_language(app, language.clone(), true)?;
+        Ok(Some(language))
+    }
+
+    /// 最後に送った部分結果を final として送り直す（停止済みの認識器に対して呼ぶ）
+    fn commit_last_partial<R: tauri::Runtime>(&self, app: &tauri::AppHandle<R>) -> Result<(), String> {
+        // 確定させる部分結果より古いものが後から届かないように
+        self.cancel_partial_throttle()?;
+        let Some(partial) = self.last_partial.lock().map_err(|e| e.to_string())?.take() else {
+            return Ok(());
+        };
+        if partial.text.is_empty() {
+            return Ok(());
+        }
+
+        let (language, normalize, result_format, sentence_delimiters, decoration_rules) = {
+            let config = self.config.lock().map_err(|e| e.to_string())?;
+            (
+                config.language.clone(),
+                config.normalize,
+                config.result_format,
+                sentence_delimiters(&config),
+                decoration_rules(&config),
+            )
+        };
+
+        let text = if normalize {
+            normalizer::normalize(&partial.text, &language)
+        } else {
+            partial.text
+        };
+        let mut result = RecognitionResult::new(text, true);
+        if let Some(rules) = decoration_rules {
+            apply_decoration(&mut result, &language, &rules);
+        }
+        result.committed_prefix = result.text.clone();
+        result.pending = String::new();
+        result.alternatives = partial.alternatives;
+        result.segment_index = partial.segment_index;
+
+        emit_result(app, result_format, &result);
+        if let Some(delimiters) = sentence_delimiters {
+            emit_sentences(app, result.plain_text.as_ref().unwrap_or(&result.text), &delimiters);
+        }
+        if let Some(ref mut session) = *self.current_session.lock().map_err(|e| e.to_string())? {
+            session.push_text(&result.text);
+        }
+        Ok(())
+    }
+
+    /// 進行中のセッションを終了して履歴ファイルに追記する
+    fn finish_session<R: tauri::Runtime>(&self, app: &tauri::AppHandle<R>) {
+        let session = match self.current_session.lock() {
+            Ok(mut session) => session.take(),
+            Err(_) => return,
+        };
+        let Some(mut session) = session else {
+            return;
+        };
+        session.finish();
+
+        let saved = self
+            .history_path(app)
+            .and_then(|path| history::append(&path, &session));
+        if let Err(e) = saved {
+            eprintln!("[SpeechManager] Failed to save speech history: {}", e);
+        }
+    }
+
+    /// 認識セッションの履歴を新しい順に取得
+    /// 言語は前方一致（"ja" で "ja-JP" も含む）、日付は開始日（YYYY-MM-DD、両端を含む）で絞り込む
+    pub fn get_history<R: tauri::Runtime>(
+        &self,
+        app: &tauri::AppHandle<R>,
+        language: Option<String>,
+        from: Option<String>,
+        to: Option<String>,
+    ) -> Result<Vec<SpeechSession>, String> {
+        let filter = HistoryFilter::parse(language, from.as_deref(), to.as_deref())?;
+        history::load(&self.history_path(app)?, &filter)
+    }
+
+    /// 認識中の部分結果の間引きを止め、送られていない部分結果を捨てる
+    fn cancel_partial_throttle(&self) -> Result<(), String> {
+        if let Some(throttle) = self.partial_throttle.lock().map_err(|e| e.to_string())?.take() {
+            throttle.cancel();
+        }
+        Ok(())
+    }
+
+    /// ユーザー辞書に単語を追加して保存し、追加後の一覧を返す
+    /// 次に認識を開始したときから contextual strings として渡される
+    pub fn add_dictionary_word<R: tauri::Runtime>(
+        &self,
+        app: &tauri::AppHandle<R>,
+        word: &str,
+    ) -> Result<Vec<String>, String> {
+        self.update_dictionary(app, |dictionary| dictionary.add(word).map(|_| ()))
+    }
+
+    /// ユーザー辞書から単語を削除して保存し、削除後の一覧を返す
+    pub fn remove_dictionary_word<R: tauri::Runtime>(
+        &self,
+        app: &tauri::AppHandle<R>,
+        word: &str,
+    ) -> Result<Vec<String>, String> {
+        self.update_dictionary(app, |dictionary| {
+            if dictionary.remove(word) {
+                Ok(())
+            } else {
+                Err(format!("Not in the speech dictionary: {}", word))
+            }
+        })
+    }
+
+    /// ユーザー辞書の単語（登録順）
+    pub fn list_dictionary<R: tauri::Runtime>(
+        &self,
+        app: &tauri::AppHandle<R>,
+    ) -> Result<Vec<String>, String> {
+        let mut cached = self.dictionary.lock().map_err(|e| e.to_string())?;
+        let dictionary = load_dictionary(&mut cached, &self.dictionary_path(app)?)?;
+        Ok(dictionary.words.clone())
+    }
+
+    /// 辞書のコピーを変更してファイルに保存し、保存できたら読み込み済みの辞書も差し替える
+    fn update_dictionary<R: tauri::Runtime>(
+        &self,
+        app: &tauri::AppHandle<R>,
+        update: impl FnOnce(&mut UserDictionary) -> Result<(), String>,
+    ) -> Result<Vec<String>, String> {
+        let path = self.dictionary_path(app)?;
+        let mut cached = self.dictionary.lock().map_err(|e| e.to_string())?;
+        let mut dictionary = load_dictionary(&mut cached, &path)?.clone();
+        update(&mut dictionary)?;
+        dictionary.save(&path)?;
+        let words = dictionary.words.clone();
+        *cached = Some(dictionary);
+        Ok(words)
+    }
+
+    /// ユーザー辞書のパス
+    fn dictionary_path<R: tauri::Runtime>(&self, app: &tauri::AppHandle<R>) -> Result<PathBuf, String> {
+        Ok(self.data_dir(app)?.join(DICTIONARY_FILE_NAME))
+    }
+
+    /// 履歴ファイルのパス
+    fn history_path<R: tauri::Runtime>(&self, app: &tauri::AppHandle<R>) -> Result<PathBuf, String> {
+        let dir = data_dir::subdir(&self.data_dir(app)?, SpeechDataKind::History)?;
+        Ok(dir.join(HISTORY_FILE_NAME))
+    }
+
+    /// 状態を更新して `speech:state-changed` を送信
+    fn set_state<R: tauri::Runtime>(
+        &self,
+        app: &tauri::AppHandle<R>,
+        new_state: SpeechRecognitionState,
+    ) -> Result<(), String> {
+        {
+            let mut state = self.state.lock().map_err(|e| e.to_string())?;
+            *state = new_state;
+        }
+        emit_state(app, new_state)
+    }
+}
+
+/// `speech:state-changed` を送信（旧イベント名には状態をそのまま送る）
+fn emit_state<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    state: SpeechRecognitionState,
+) -> Result<(), String> {
+    events::emit_with_legacy_payload(
+        app,
+        events::SPEECH_STATE_CHANGED,
+        SpeechStatePayload { state },
+        state,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// エラーで認識タスクが終わったときに、認識中の状態を `new_state` にして通知する
+///
+/// 停止や言語の切り替えで既に Listening でなくなっていれば何もしない
+fn end_listening<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    state: &Mutex<SpeechRecognitionState>,
+    new_state: SpeechRecognitionState,
+) {
+    {
+        let Ok(mut state) = state.lock() else {
+            return;
+        };
+        if *state != SpeechRecognitionState::Listening {
+            return;
+        }
+        *state = new_state;
+    }
+    if let Err(e) = emit_state(app, new_state) {
+        eprintln!("[SpeechManager] Failed to emit event: {}", e);
+    }
+}
+
+/// 認識結果を設定された形式でフロントエンドに送信
+fn emit_result<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    format: ResultFormat,
+    result: &RecognitionResult,
+) {
+    println!("[SpeechManager] Emitting result: '{}' (final: {})", result.text, result.is_final);
+    let emitted = match format {
+        ResultFormat::Simple => events::emit(app, events::SPEECH_RESULT, result),
+        ResultFormat::WebSpeech => events::emit(app, events::SPEECH_RESULT, WebSpeechResult::from(result)),
+    };
+    if let Err(e) = emitted {
+        eprintln!("[SpeechManager] Failed to emit event: {}", e);
+    }
+}
+
+/// 文分割が有効なら使用する区切り文字を返す
+fn sentence_delimiters(config: &SpeechConfig) -> Option<String> {
+    if !config.split_sentences {
+        return None;
+    }
+    Some(
+        config
+            .sentence_delimiters
+            .clone()
+            .unwrap_or_else(|| sentence::default_delimiters(&config.language).to_string()),
+    )
+}
+
+/// 装飾が有効なら使用するルールを返す
+fn decoration_rules(config: &SpeechConfig) -> Option<Vec<DecorationRule>> {
+    config.decorate.then(|| config.decoration_rules.clone())
+}
+
+/// 確定テキストにトリガー語があれば装飾し、元のテキストを `plain_text` に残す
+fn apply_decoration(result: &mut RecognitionResult, language: &str, rules: &[DecorationRule]) {
+    if let Some(decorated) = decorator::decorate(&result.text, language, rules) {
+        println!("[SpeechManager] Decorated: '{}' -> '{}'", result.text, decorated);
+        result.plain_text = Some(std::mem::replace(&mut result.text, decorated));
+    }
+}
+
+/// 読み込み済みの辞書を返す（まだなら読み込んでキャッシュする）
+fn load_dictionary<'a>(
+    cached: &'a mut Option<UserDictionary>,
+    path: &Path,
+) -> Result<&'a mut UserDictionary, String> {
+    if cached.is_none() {
+        *cached = Some(UserDictionary::load(path)?);
+    }
+    Ok(cached.get_or_insert_with(UserDictionary::default))
+}
+
+/// 確定テキストを文に分割して `speech:sentence` で1文ずつ送信
+fn emit_sentences<R: tauri::Runtime>(app: &tauri::AppHandle<R>, text: &str, delimiters: &str) {
+    let sentences = sentence::split_sentences(text, delimiters);
+    let count = sentences.len();
+    for (index, text) in sentences.into_iter().enumerate() {
+        let payload = SpeechSentencePayload {
+            text,
+            index,
+            is_last: index + 1 == count,
+        };
+        if let Err(e) = events::emit(app, events::SPEECH_SENTENCE, &payload) {
+            eprintln!("[SpeechManager] Failed to emit event: {}", e);
+        }
+    }
+}
+
+/// 話者セグメントの境界を `speech:segment-boundary` で送る
+fn emit_segment_boundary<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    segment_index: usize,
+    pause: Duration,
+) {
+    let payload = SpeechSegmentBoundaryPayload {
+        segment_index,
+        pause_ms: pause.as_millis() as u64,
+    };
+    if let Err(e) = events::emit(app, events::SPEECH_SEGMENT_BOUNDARY, &payload) {
+        eprintln!("[SpeechManager] Failed to emit event: {}", e);
+    }
+}
+
+/// `speech:audio-level` を送る最短の間隔（オーディオタップは 1 秒に数十回呼ばれる）
+const AUDIO_LEVEL_EVENT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 入力音量（dBFS）を間引いて `speech:audio-level` で送るコールバックを作る
+/// （旧イベント名には音量をそのまま送る）
+fn audio_level_emitter<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> LevelCallback {
+    let app = app.clone();
+    let last_emitted = Mutex::new(None::<Instant>);
+    Arc::new(move |level| {
+        let now = Instant::now();
+        if let Ok(mut last_emitted) = last_emitted.lock() {
+            if last_emitted.is_some_and(|last| now.duration_since(last) < AUDIO_LEVEL_EVENT_INTERVAL) {
+                return;
+            }
+            *last_emitted = Some(now);
+        }
+        if let Err(e) = events::emit_with_legacy_payload(
+            &app,
+            events::SPEECH_AUDIO_LEVEL,
+            SpeechAudioLevelPayload { level_db: level },
+            level,
+        ) {
+            eprintln!("[SpeechManager] Failed to emit event: {}", e);
+        }
+    })
+}
+
+/// ハンズフリーモードの状態変化を通知
+fn emit_hands_free_state<R: tauri::Runtime>(app: &tauri::AppHandle<R>, state: HandsFreeState) {
+    if let Err(e) = events::emit_with_legacy_payload(
+        app,
+        events::SPEECH_HANDS_FREE_STATE_CHANGED,
+        HandsFreeStatePayload { state },
+        state,
+    ) {
+        eprintln!("[SpeechManager] Failed to emit event: {}", e);
+    }
+}
+
+/// 連続エラーを記録し、閾値に達したら機能を無効化して `speech:disabled` を送信
+fn record_error<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    config: &Mutex<SpeechConfig>,
+    consecutive_errors: &AtomicU32,
+    disabled_by_errors: &AtomicBool,
+    error: &str,
+) {
+    let error_count = consecutive_errors.fetch_add(1, Ordering::Relaxed) + 1;
+
+    let Ok(max_consecutive_errors) = config.lock().map(|config| config.max_consecutive_errors)
+    else {
+        return;
+    };
+    if max_consecutive_errors == 0
+        || error_count < max_consecutive_errors
+        || disabled_by_errors.swap(true, Ordering::Relaxed)
+    {
+        return;
+    }
+
+    eprintln!(
+        "[SpeechManager] Disabled after {} consecutive errors: {}",
+        error_count, error
+    );
+    let payload = SpeechDisabledPayload {
+        error_count,
+        last_error: error.to_string(),
+    };
+    if let Err(e) = events::emit(app, events::SPEECH_DISABLED, &payload) {
+        eprintln!("[SpeechManager] Failed to emit event: {}", e);
+    }
 }
 
 impl Default for SpeechManager {
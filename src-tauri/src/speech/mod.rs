@@ -1,6 +1,30 @@
 mod config;
+mod data_dir;
+mod decorator;
+mod dictionary;
+mod hands_free;
+mod history;
+mod level_monitor;
+mod locale;
 mod manager;
+mod normalizer;
+mod profile;
 mod recognizer;
+mod run_loop;
+mod segmenter;
+mod sentence;
+mod sleep_guard;
+mod stabilizer;
+mod throttle;
+mod utterance_filter;
 
-pub use config::SpeechRecognitionState;
+pub use config::{
+    ResultFormat, SpeechAudioLevelPayload, SpeechConfig, SpeechRecognitionState, SpeechStatePayload,
+};
+pub use data_dir::{DataDirSettings, DATA_DIR_SETTINGS_FILE_NAME};
+pub use decorator::DecorationRule;
+pub use hands_free::HandsFreeAction;
+pub use history::SpeechSession;
 pub use manager::SpeechManager;
+pub use profile::{SpeechProfile, SpeechProfiles, PROFILES_FILE_NAME};
+pub use sleep_guard::SleepPrevention;
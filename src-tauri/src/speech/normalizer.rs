@@ -0,0 +1,207 @@
+/// 確定した認識結果のテキストを言語に応じて正規化する
+///
+/// - 日本語: 全角英数字→半角、漢数字（2文字以上の並びで、助数詞・単位が続くもの）→算用数字
+/// - 中国語: 全角英数字→半角（漢数字は語彙と区別しにくいため変換しない）
+/// - その他: 全角英数字・記号・スペース→半角
+pub fn normalize(text: &str, language: &str) -> String {
+    let language = language.to_lowercase();
+    if language.starts_with("ja") {
+        convert_kanji_numerals(&to_halfwidth(text, false))
+    } else if language.starts_with("zh") {
+        to_halfwidth(text, false)
+    } else {
+        to_halfwidth(text, true)
+    }
+}
+
+/// 全角英数字を半角に変換する（`include_symbols` なら記号とスペースも）
+fn to_halfwidth(text: &str, include_symbols: bool) -> String {
+    text.chars()
+        .map(|c| match c {
+            '０'..='９' | 'Ａ'..='Ｚ' | 'ａ'..='ｚ' => shift_to_ascii(c),
+            '！'..='～' if include_symbols => shift_to_ascii(c),
+            '\u{3000}' if include_symbols => ' ',
+            _ => c,
+        })
+        .collect()
+}
+
+/// 全角 ASCII 領域（U+FF01〜U+FF5E）の文字を対応する ASCII 文字にずらす
+fn shift_to_ascii(c: char) -> char {
+    char::from_u32(c as u32 - 0xFEE0).unwrap_or(c)
+}
+
+/// 漢数字の並びのあとに続くと数量とみなす助数詞・単位（1文字のもの）
+const COUNTER_CHARS: &str = "円個人名年月日時分秒週歳才回件本枚台匹冊階番度点倍割%％";
+
+/// 漢数字の並びのあとに続くと数量とみなす助数詞・単位（2文字以上のもの）
+const COUNTER_WORDS: &[&str] = &[
+    "ヶ月",
+    "か月",
+    "カ月",
+    "キロ",
+    "メートル",
+    "センチ",
+    "ミリ",
+    "グラム",
+    "ドル",
+    "パーセント",
+];
+
+/// 助数詞が続いていても数量ではない慣用句（そのまま残す）
+const SET_PHRASES: &[&str] = &["四六時中", "二六時中", "三三九度"];
+
+fn kanji_digit(c: char) -> Option<u64> {
+    match c {
+        '〇' | '零' => Some(0),
+        '一' => Some(1),
+        '二' => Some(2),
+        '三' => Some(3),
+        '四' => Some(4),
+        '五' => Some(5),
+        '六' => Some(6),
+        '七' => Some(7),
+        '八' => Some(8),
+        '九' => Some(9),
+        _ => None,
+    }
+}
+
+fn kanji_small_unit(c: char) -> Option<u64> {
+    match c {
+        '十' => Some(10),
+        '百' => Some(100),
+        '千' => Some(1_000),
+        _ => None,
+    }
+}
+
+fn kanji_large_unit(c: char) -> Option<u64> {
+    match c {
+        '万' => Some(10_000),
+        '億' => Some(100_000_000),
+        _ => None,
+    }
+}
+
+fn is_kanji_numeral(c: char) -> bool {
+    kanji_digit(c).is_some() || kanji_small_unit(c).is_some() || kanji_large_unit(c).is_some()
+}
+
+/// 数量を表す漢数字の並びを算用数字に置き換える
+///
+/// 後ろに助数詞・単位が続く並びだけを数量とみなす。「五十嵐」「九十九里」「七五三」の
+/// ような固有名詞や熟語の一部は変換しない。「一つ」「十分」のような1文字の漢数字も
+/// 熟語の一部であることが多いため変換しない。
+fn convert_kanji_numerals(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        if let Some(phrase) = SET_PHRASES.iter().find(|phrase| rest.starts_with(**phrase)) {
+            output.push_str(phrase);
+            rest = &rest[phrase.len()..];
+        } else if is_kanji_numeral(c) {
+            let end = rest.find(|c| !is_kanji_numeral(c)).unwrap_or(rest.len());
+            let (run, after) = rest.split_at(end);
+            output.push_str(&convert_numeral_run(run, after));
+            rest = after;
+        } else {
+            output.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    output
+}
+
+/// 漢数字の並び `run` を、2文字以上で後ろ（`after`）に助数詞・単位が続く場合だけ変換する
+fn convert_numeral_run(run: &str, after: &str) -> String {
+    let has_counter = after
+        .chars()
+        .next()
+        .is_some_and(|c| COUNTER_CHARS.contains(c))
+        || COUNTER_WORDS.iter().any(|word| after.starts_with(word));
+    let is_quantity = run.chars().count() >= 2 && has_counter;
+    match parse_kanji_number(run).filter(|_| is_quantity) {
+        Some(value) => value.to_string(),
+        None => run.to_string(),
+    }
+}
+
+/// 漢数字を数値として解釈する（解釈できない並びは None）
+///
+/// 位取りの単位を含まない場合は「二〇二四」のように1桁ずつ並べた表記として扱う。
+fn parse_kanji_number(run: &str) -> Option<u64> {
+    let has_unit = run
+        .chars()
+        .any(|c| kanji_small_unit(c).is_some() || kanji_large_unit(c).is_some());
+
+    if !has_unit {
+        return run
+            .chars()
+            .try_fold(0u64, |acc, c| acc.checked_mul(10)?.checked_add(kanji_digit(c)?));
+    }
+
+    // 「万一」のように大きな単位から始まるものは数値ではない
+    if run.chars().next().and_then(kanji_large_unit).is_some() {
+        return None;
+    }
+
+    let mut total = 0u64;
+    let mut section = 0u64;
+    let mut current: Option<u64> = None;
+
+    for c in run.chars() {
+        if let Some(digit) = kanji_digit(c) {
+            // 単位を挟まずに数字が続く並び（「二二十」など）は解釈しない
+            if current.is_some() {
+                return None;
+            }
+            current = Some(digit);
+        } else if let Some(unit) = kanji_small_unit(c) {
+            section = section.checked_add(current.take().unwrap_or(1).checked_mul(unit)?)?;
+        } else if let Some(unit) = kanji_large_unit(c) {
+            section = section.checked_add(current.take().unwrap_or(0))?;
+            total = total.checked_add(section.checked_mul(unit)?)?;
+            section = 0;
+        }
+    }
+
+    total.checked_add(section)?.checked_add(current.unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numerals_followed_by_a_counter_are_converted() {
+        assert_eq!(normalize("三十五人が参加", "ja-JP"), "35人が参加");
+        assert_eq!(normalize("二〇二四年十二月", "ja-JP"), "2024年12月");
+        assert_eq!(normalize("千五百円です", "ja-JP"), "1500円です");
+        assert_eq!(normalize("五十％", "ja-JP"), "50％");
+    }
+
+    #[test]
+    fn proper_nouns_keep_their_numerals() {
+        assert_eq!(normalize("五十嵐さん", "ja-JP"), "五十嵐さん");
+        assert_eq!(normalize("九十九里浜", "ja-JP"), "九十九里浜");
+    }
+
+    #[test]
+    fn idioms_keep_their_numerals() {
+        assert_eq!(normalize("七五三のお祝い", "ja-JP"), "七五三のお祝い");
+        assert_eq!(
+            normalize("四六時中考えている", "ja-JP"),
+            "四六時中考えている"
+        );
+        assert_eq!(normalize("三三九度", "ja-JP"), "三三九度");
+    }
+
+    #[test]
+    fn single_numerals_and_runs_at_the_end_are_kept() {
+        assert_eq!(normalize("十分です", "ja-JP"), "十分です");
+        assert_eq!(normalize("答えは四十二", "ja-JP"), "答えは四十二");
+    }
+}
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// プロファイル定義ファイル名（app_config_dir 内）
+pub const PROFILES_FILE_NAME: &str = "speech_profiles.json";
+
+/// 名前付きの認識設定（チームで共有する言語・句読点・語彙のセット）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeechProfile {
+    /// プロファイル名
+    pub name: String,
+    /// 認識言語（ja-JP等）
+    pub language: String,
+    /// 句読点を自動で付与する
+    #[serde(default)]
+    pub adds_punctuation: bool,
+    /// 認識されやすくする語彙（固有名詞・専門用語など）
+    #[serde(default)]
+    pub vocabulary: Vec<String>,
+}
+
+/// プロファイル定義ファイルの内容
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct SpeechProfiles {
+    /// 起動時に適用するプロファイル名
+    pub default_profile: Option<String>,
+    pub profiles: Vec<SpeechProfile>,
+}
+
+impl SpeechProfiles {
+    /// JSONファイルから読み込む（ファイルが無ければ空）
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid speech profiles file {}: {}", path.display(), e))
+    }
+
+    /// JSONファイルへ保存
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, content).map_err(|e| e.to_string())
+    }
+
+    /// 名前でプロファイルを検索
+    pub fn find(&self, name: &str) -> Option<&SpeechProfile> {
+        self.profiles.iter().find(|p| p.name == name)
+    }
+}
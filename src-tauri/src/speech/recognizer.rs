@@ -1,18 +1,59 @@
 use block2::RcBlock;
 use objc2::rc::Retained;
 use objc2::AllocAnyThread;
-use objc2_avf_audio::{AVAudioEngine, AVAudioPCMBuffer, AVAudioTime};
-use objc2_foundation::{NSError, NSLocale, NSString};
+use objc2_avf_audio::{AVAudioEngine, AVAudioFile, AVAudioPCMBuffer, AVAudioTime};
+use objc2_foundation::{NSArray, NSError, NSLocale, NSOperationQueue, NSString, NSURL};
 use objc2_speech::{
     SFSpeechAudioBufferRecognitionRequest, SFSpeechRecognitionResult, SFSpeechRecognitionTask,
-    SFSpeechRecognizer, SFSpeechRecognizerAuthorizationStatus,
+    SFSpeechRecognizer, SFTranscription,
 };
 use std::ptr::NonNull;
 use std::sync::{Arc, Mutex};
 
-use crate::speech::config::RecognitionResult;
-
-type ResultCallback = Arc<Mutex<Option<Box<dyn Fn(RecognitionResult) + Send + 'static>>>>;
+use crate::speech::config::{RecognitionAlternative, RecognitionError, RecognitionResult};
+use crate::speech::level_monitor::buffer_level_db;
+use crate::speech::run_loop::RunLoopThread;
+
+/// マイクが他のプロセスに占有されているときに返る OSStatus
+/// （FourCC: '!hog' / '!rec' / '!pri' / '!int' / 'nope'）
+const MIC_BUSY_ERROR_CODES: [isize; 5] = [560492391, 561145187, 561017449, 560557684, 1852797029];
+
+/// AudioUnit の初期化失敗（入力デバイスが他アプリに掴まれていると発生しやすい）
+const AUDIO_UNIT_INIT_ERROR_CODES: [isize; 2] = [-10875, -10868];
+
+/// 認識タスクが終わっただけで、続けて認識できるエラー（ドメインとコード）
+/// 1110: 音声が検出されなかった / 203: 再試行を求められた / 216, 301: リクエストがキャンセルされた
+const TRANSIENT_ERRORS: [(&str, isize); 4] = [
+    ("kAFAssistantErrorDomain", 1110),
+    ("kAFAssistantErrorDomain", 203),
+    ("kAFAssistantErrorDomain", 216),
+    ("kLSRErrorDomain", 301),
+];
+
+/// 認識結果（またはエラー内容）を受け取るリスナー
+type ResultListener =
+    Arc<dyn Fn(Result<RecognitionResult, RecognitionError>) + Send + Sync + 'static>;
+
+/// 登録済みのリスナー（認識スレッドと共有）
+type ResultListeners = Arc<Mutex<Vec<ResultListener>>>;
+
+/// 入力ノードのオーディオタップに渡すブロック
+type TapBlock = RcBlock<dyn Fn(NonNull<AVAudioPCMBuffer>, NonNull<AVAudioTime>)>;
+
+/// 入力バッファごとの音量（dBFS）を受け取るコールバック（オーディオスレッドから呼ばれる）
+pub type LevelCallback = Arc<dyn Fn(f32) + Send + Sync + 'static>;
+
+/// 認識開始時のオプション
+pub struct ListenOptions<'a> {
+    /// 指定すると、認識と同時に音声をファイルへ保存する
+    pub record_audio_path: Option<&'a str>,
+    /// 句読点を自動で付与する
+    pub adds_punctuation: bool,
+    /// 認識されやすくする語彙
+    pub contextual_strings: &'a [String],
+    /// 認識中の入力音量を受け取る
+    pub on_level: Option<LevelCallback>,
+}
 
 /// SFSpeechRecognizerのRustラッパー
 pub struct SpeechRecognizerWrapper {
@@ -21,7 +62,19 @@ pub struct SpeechRecognizerWrapper {
     recognition_request: Mutex<Option<Retained<SFSpeechAudioBufferRecognitionRequest>>>,
     recognition_task: Mutex<Option<Retained<SFSpeechRecognitionTask>>>,
     is_listening: Mutex<bool>,
-    result_callback: ResultCallback,
+    result_listeners: ResultListeners,
+    /// 録音中のファイル（録音しない場合は None）
+    audio_file: Arc<Mutex<Option<Retained<AVAudioFile>>>>,
+    /// 録音ファイルのパス
+    recording_path: Mutex<Option<String>>,
+    /// 設置中のオーディオタップのブロック（リスニング中のみ Some）
+    /// タップを外すまで確実に生きているよう、ここで所有して停止時に破棄する
+    tap_block: Mutex<Option<TapBlock>>,
+    /// 結果ハンドラを呼ぶキュー（既定のメインキューはメインの run loop が回っていないと届かない）
+    /// 認識器に設定したまま保持するだけで、直接は使わない
+    _callback_queue: Retained<NSOperationQueue>,
+    /// 認識を開始した専用スレッド（リスニング中のみ Some）
+    recognition_thread: Mutex<Option<RunLoopThread>>,
 }
 
 // 明示的にSend+Syncを実装（Objective-Cオブジェクトはスレッドセーフ）
@@ -46,6 +99,11 @@ impl SpeechRecognizerWrapper {
                 return Err("Speech recognizer is not available".to_string());
             }
 
+            // 結果ハンドラを専用のキューで順番に受け取る
+            let callback_queue = NSOperationQueue::new();
+            callback_queue.setMaxConcurrentOperationCount(1);
+            recognizer.setQueue(&callback_queue);
+
             // AVAudioEngineを初期化
             let audio_engine = AVAudioEngine::new();
 
@@ -55,39 +113,35 @@ impl SpeechRecognizerWrapper {
                 recognition_request: Mutex::new(None),
                 recognition_task: Mutex::new(None),
                 is_listening: Mutex::new(false),
-                result_callback: Arc::new(Mutex::new(None)),
+                result_listeners: Arc::new(Mutex::new(Vec::new())),
+                audio_file: Arc::new(Mutex::new(None)),
+                recording_path: Mutex::new(None),
+                tap_block: Mutex::new(None),
+                _callback_queue: callback_queue,
+                recognition_thread: Mutex::new(None),
             })
         }
     }
 
-    /// 音声認識の認可をリクエスト
-    #[allow(dead_code)]
-    pub fn request_authorization<F>(callback: F)
-    where
-        F: Fn(bool) + Send + 'static,
-    {
-        let callback = Arc::new(Mutex::new(Some(callback)));
-        let callback_clone = callback.clone();
-
-        let block = RcBlock::new(move |status: SFSpeechRecognizerAuthorizationStatus| {
-            // SFSpeechRecognizerAuthorizationStatus::Authorized == 3
-            let authorized = status == SFSpeechRecognizerAuthorizationStatus::Authorized;
-            if let Ok(mut guard) = callback_clone.lock() {
-                if let Some(cb) = guard.take() {
-                    cb(authorized);
-                }
-            }
-        });
-
+    /// 音声認識に対応しているロケール（"ja-JP" 形式）
+    pub fn supported_locales() -> Vec<String> {
         unsafe {
-            SFSpeechRecognizer::requestAuthorization(&block);
+            SFSpeechRecognizer::supportedLocales()
+                .to_vec()
+                .iter()
+                .map(|locale| locale.localeIdentifier().to_string().replace('_', "-"))
+                .collect()
         }
     }
 
     /// 音声認識を開始
-    pub fn start_listening<F>(&self, callback: F) -> Result<(), String>
+    ///
+    /// 認識タスクとオーディオエンジンは専用スレッドの run loop 上で開始し、結果ハンドラは
+    /// 専用のキューで呼ばれる。呼び出し元（Tauri のコマンドスレッドなど）の run loop が
+    /// 回っていなくても部分結果が届く。
+    pub fn start_listening<F>(&self, options: &ListenOptions, callback: F) -> Result<(), String>
     where
-        F: Fn(RecognitionResult) + Send + 'static,
+        F: Fn(Result<RecognitionResult, RecognitionError>) + Send + Sync + 'static,
     {
         // 既にリスニング中かチェック
         {
@@ -97,97 +151,55 @@ impl SpeechRecognizerWrapper {
             }
         }
 
-        // コールバックを設定
+        // コールバックを最初のリスナーとして登録
+        self.add_result_listener(callback)?;
+
+        let context = RecognitionContext {
+            recognizer: self.recognizer.clone(),
+            audio_engine: self.audio_engine.clone(),
+            result_listeners: self.result_listeners.clone(),
+            audio_file: self.audio_file.clone(),
+            record_audio_path: options.record_audio_path.map(str::to_string),
+            adds_punctuation: options.adds_punctuation,
+            contextual_strings: options.contextual_strings.to_vec(),
+            on_level: options.on_level.clone(),
+        };
+        let started = RunLoopThread::spawn("speech-recognition", move || unsafe { context.begin() });
+        let (thread, session) = match started {
+            Ok(started) => started,
+            Err(e) => {
+                self.result_listeners.lock().map_err(|e| e.to_string())?.clear();
+                return Err(e);
+            }
+        };
+
+        // 状態を更新
         {
-            let mut cb_guard = self.result_callback.lock().map_err(|e| e.to_string())?;
-            *cb_guard = Some(Box::new(callback));
+            let mut req_guard = self.recognition_request.lock().map_err(|e| e.to_string())?;
+            *req_guard = Some(session.request);
         }
-
-        unsafe {
-            // 認識リクエストを作成
-            let request = SFSpeechAudioBufferRecognitionRequest::new();
-            request.setShouldReportPartialResults(true);
-
-            // オーディオ入力ノードを取得
-            let input_node = self.audio_engine.inputNode();
-            let record_format = input_node.outputFormatForBus(0);
-
-            // コールバッククロージャを準備
-            let result_callback = self.result_callback.clone();
-            let result_block = RcBlock::new(
-                move |result: *mut SFSpeechRecognitionResult, error: *mut NSError| {
-                    if !error.is_null() {
-                        let error_ref = &*error;
-                        eprintln!("[Speech] Recognition error: {}", error_ref.localizedDescription());
-                    }
-                    if !result.is_null() {
-                        let result_ref = &*result;
-                        let transcription = result_ref.bestTranscription();
-                        let text = transcription.formattedString().to_string();
-                        let is_final = result_ref.isFinal();
-
-                        println!("[Speech] Recognized: '{}' (final: {})", text, is_final);
-
-                        if let Ok(cb_guard) = result_callback.lock() {
-                            if let Some(ref cb) = *cb_guard {
-                                cb(RecognitionResult { text, is_final });
-                            }
-                        }
-                    } else {
-                        println!("[Speech] Result is null");
-                    }
-                },
-            );
-
-            // 認識タスクを開始
-            let task = self
-                .recognizer
-                .recognitionTaskWithRequest_resultHandler(&request, &result_block);
-
-            // オーディオタップを設定
-            let request_clone = request.clone();
-            let tap_block =
-                RcBlock::new(move |buffer: NonNull<AVAudioPCMBuffer>, _when: NonNull<AVAudioTime>| {
-                    request_clone.appendAudioPCMBuffer(buffer.as_ref());
-                });
-
-            input_node.installTapOnBus_bufferSize_format_block(
-                0,
-                1024,
-                Some(&record_format),
-                &*tap_block as *const _ as *mut _,
-            );
-
-            // オーディオエンジンを開始
-            self.audio_engine.prepare();
-            self.audio_engine
-                .startAndReturnError()
-                .map_err(|e| format!("Failed to start audio engine: {}", e.localizedDescription()))?;
-
-            // 状態を更新
-            {
-                let mut req_guard = self.recognition_request.lock().map_err(|e| e.to_string())?;
-                *req_guard = Some(request);
-            }
-            {
-                let mut task_guard = self.recognition_task.lock().map_err(|e| e.to_string())?;
-                *task_guard = Some(task);
-            }
-            {
-                let mut is_listening = self.is_listening.lock().map_err(|e| e.to_string())?;
-                *is_listening = true;
-            }
+        {
+            let mut task_guard = self.recognition_task.lock().map_err(|e| e.to_string())?;
+            *task_guard = Some(session.task);
+        }
+        *self.recording_path.lock().map_err(|e| e.to_string())? = session.recording_path;
+        *self.tap_block.lock().map_err(|e| e.to_string())? = Some(session.tap_block);
+        *self.recognition_thread.lock().map_err(|e| e.to_string())? = Some(thread);
+        {
+            let mut is_listening = self.is_listening.lock().map_err(|e| e.to_string())?;
+            *is_listening = true;
         }
 
         Ok(())
     }
 
     /// 音声認識を停止
-    pub fn stop_listening(&self) -> Result<(), String> {
+    /// 録音していた場合は確定したファイルのパスを返す
+    pub fn stop_listening(&self) -> Result<Option<String>, String> {
         {
             let is_listening = self.is_listening.lock().map_err(|e| e.to_string())?;
             if !*is_listening {
-                return Ok(());
+                return Ok(None);
             }
         }
 
@@ -195,6 +207,8 @@ impl SpeechRecognizerWrapper {
             // オーディオエンジンを停止
             self.audio_engine.stop();
             self.audio_engine.inputNode().removeTapOnBus(0);
+            // タップを外してからブロックを破棄する
+            self.tap_block.lock().map_err(|e| e.to_string())?.take();
 
             // 認識リクエストを終了
             {
@@ -220,18 +234,41 @@ impl SpeechRecognizerWrapper {
                 *is_listening = false;
             }
 
-            // コールバックをクリア
+            // リスナーをすべてクリア
             {
-                let mut cb_guard = self.result_callback.lock().map_err(|e| e.to_string())?;
-                *cb_guard = None;
+                let mut listeners = self.result_listeners.lock().map_err(|e| e.to_string())?;
+                listeners.clear();
             }
         }
 
+        // 認識スレッドの run loop を止めて終了を待つ
+        if let Some(mut thread) = self.recognition_thread.lock().map_err(|e| e.to_string())?.take() {
+            thread.stop();
+        }
+
+        // 録音ファイルを解放してヘッダを確定させる
+        let recorded = self.audio_file.lock().map_err(|e| e.to_string())?.take().is_some();
+        let recording_path = self.recording_path.lock().map_err(|e| e.to_string())?.take();
+
+        Ok(if recorded { recording_path } else { None })
+    }
+
+    /// 認識結果のリスナーを追加（停止時にすべて解除される）
+    pub fn add_result_listener<F>(&self, listener: F) -> Result<(), String>
+    where
+        F: Fn(Result<RecognitionResult, RecognitionError>) + Send + Sync + 'static,
+    {
+        let mut listeners = self.result_listeners.lock().map_err(|e| e.to_string())?;
+        listeners.push(Arc::new(listener));
         Ok(())
     }
 
-    /// リスニング中かどうかを返す
-    #[allow(dead_code)]
+    /// オーディオエンジンが実際に動いているか（マイクを使用中か）
+    pub fn is_engine_running(&self) -> bool {
+        unsafe { self.audio_engine.isRunning() }
+    }
+
+    /// リスニング中かどうかを返す（エラーで認識タスクが終わっても停止するまでは true）
     pub fn is_listening(&self) -> bool {
         self.is_listening
             .lock()
@@ -239,3 +276,228 @@ impl SpeechRecognizerWrapper {
             .unwrap_or(false)
     }
 }
+
+/// 認識スレッドへ渡す認識器と設定
+struct RecognitionContext {
+    recognizer: Retained<SFSpeechRecognizer>,
+    audio_engine: Retained<AVAudioEngine>,
+    result_listeners: ResultListeners,
+    audio_file: Arc<Mutex<Option<Retained<AVAudioFile>>>>,
+    record_audio_path: Option<String>,
+    adds_punctuation: bool,
+    contextual_strings: Vec<String>,
+    on_level: Option<LevelCallback>,
+}
+
+/// 認識スレッドで開始した認識の状態（停止時に使う）
+struct RecognitionSession {
+    request: Retained<SFSpeechAudioBufferRecognitionRequest>,
+    task: Retained<SFSpeechRecognitionTask>,
+    /// 録音を開始できた場合のファイルパス
+    recording_path: Option<String>,
+    /// 設置したオーディオタップのブロック（停止してタップを外すまで保持する）
+    tap_block: TapBlock,
+}
+
+// SpeechRecognizerWrapper と同様に、Objective-C オブジェクトはスレッドセーフとして扱う
+unsafe impl Send for RecognitionContext {}
+unsafe impl Send for RecognitionSession {}
+
+impl RecognitionContext {
+    /// 認識タスクとオーディオエンジンを開始する（認識スレッド上で呼ぶ）
+    unsafe fn begin(self) -> Result<RecognitionSession, String> {
+        // 認識リクエストを作成
+        let request = SFSpeechAudioBufferRecognitionRequest::new();
+        request.setShouldReportPartialResults(true);
+        request.setAddsPunctuation(self.adds_punctuation);
+        if !self.contextual_strings.is_empty() {
+            let strings: Vec<Retained<NSString>> = self
+                .contextual_strings
+                .iter()
+                .map(|s| NSString::from_str(s))
+                .collect();
+            request.setContextualStrings(&NSArray::from_retained_slice(&strings));
+        }
+
+        // オーディオ入力ノードを取得
+        let input_node = self.audio_engine.inputNode();
+        let record_format = input_node.outputFormatForBus(0);
+
+        // コールバッククロージャを準備
+        let result_listeners = self.result_listeners.clone();
+        let result_block = RcBlock::new(
+            move |result: *mut SFSpeechRecognitionResult, error: *mut NSError| {
+                if !error.is_null() {
+                    let error_ref = &*error;
+                    let description = error_ref.localizedDescription().to_string();
+                    eprintln!("[Speech] Recognition error: {}", description);
+
+                    let error = RecognitionError {
+                        message: description,
+                        transient: is_transient_error(error_ref),
+                    };
+                    dispatch(&result_listeners, Err(error));
+                }
+                if !result.is_null() {
+                    let result_ref = &*result;
+                    let transcription = result_ref.bestTranscription();
+                    let text = transcription.formattedString().to_string();
+                    let is_final = result_ref.isFinal();
+
+                    println!("[Speech] Recognized: '{}' (final: {})", text, is_final);
+
+                    let mut recognition_result = RecognitionResult::new(text, is_final);
+                    recognition_result.alternatives = result_ref
+                        .transcriptions()
+                        .iter()
+                        .map(|transcription| transcription_alternative(&transcription))
+                        .collect();
+
+                    dispatch(&result_listeners, Ok(recognition_result));
+                } else {
+                    println!("[Speech] Result is null");
+                }
+            },
+        );
+
+        // 認識タスクを開始
+        let task = self
+            .recognizer
+            .recognitionTaskWithRequest_resultHandler(&request, &result_block);
+
+        // 録音ファイルを開く（失敗しても認識は続ける）
+        let mut recording_path = None;
+        if let Some(ref path) = self.record_audio_path {
+            let url = NSURL::fileURLWithPath(&NSString::from_str(path));
+            match AVAudioFile::initForWriting_settings_error(
+                AVAudioFile::alloc(),
+                &url,
+                &record_format.settings(),
+            ) {
+                Ok(file) => {
+                    *self.audio_file.lock().map_err(|e| e.to_string())? = Some(file);
+                    recording_path = Some(path.clone());
+                }
+                Err(e) => {
+                    eprintln!("[Speech] Failed to open audio file: {}", e.localizedDescription());
+                }
+            }
+        }
+
+        // オーディオタップを設定
+        let request_clone = request.clone();
+        let audio_file = self.audio_file.clone();
+        let on_level = self.on_level.clone();
+        let tap_block =
+            RcBlock::new(move |buffer: NonNull<AVAudioPCMBuffer>, _when: NonNull<AVAudioTime>| {
+                request_clone.appendAudioPCMBuffer(buffer.as_ref());
+
+                if let Some(ref on_level) = on_level {
+                    on_level(buffer_level_db(buffer.as_ref()));
+                }
+
+                // 録音ファイルへ書き出し（書き込み失敗時は録音だけ止めて認識は続ける）
+                if let Ok(mut file_guard) = audio_file.lock() {
+                    if let Some(ref file) = *file_guard {
+                        if let Err(e) = file.writeFromBuffer_error(buffer.as_ref()) {
+                            eprintln!("[Speech] Failed to write audio: {}", e.localizedDescription());
+                            *file_guard = None;
+                        }
+                    }
+                }
+            });
+
+        input_node.installTapOnBus_bufferSize_format_block(
+            0,
+            1024,
+            Some(&record_format),
+            &*tap_block as *const _ as *mut _,
+        );
+
+        // オーディオエンジンを開始
+        self.audio_engine.prepare();
+        if let Err(e) = self.audio_engine.startAndReturnError() {
+            // 開始できなかった場合はタップと認識タスクを片付けてから返す
+            input_node.removeTapOnBus(0);
+            request.endAudio();
+            task.cancel();
+            *self.audio_file.lock().map_err(|e| e.to_string())? = None;
+            return Err(describe_engine_start_error(&e));
+        }
+
+        Ok(RecognitionSession {
+            request,
+            task,
+            recording_path,
+            tap_block,
+        })
+    }
+}
+
+/// 登録済みのリスナーすべてに結果を配る
+///
+/// ロックを保持したままリスナーを呼ぶと、リスナー内での追加や停止がデッドロックするため、
+/// 一覧を複製してからロックの外で呼び出す
+fn dispatch(listeners: &ResultListeners, result: Result<RecognitionResult, RecognitionError>) {
+    let snapshot: Vec<ResultListener> = match listeners.lock() {
+        Ok(listeners) => listeners.clone(),
+        Err(_) => return,
+    };
+    for listener in snapshot {
+        listener(result.clone());
+    }
+}
+
+/// 認識候補のテキストと、セグメントの平均信頼度を取り出す
+unsafe fn transcription_alternative(transcription: &SFTranscription) -> RecognitionAlternative {
+    let segments = transcription.segments();
+    let confidence = if segments.is_empty() {
+        0.0
+    } else {
+        segments.iter().map(|segment| segment.confidence()).sum::<f32>() / segments.len() as f32
+    };
+
+    RecognitionAlternative {
+        transcript: transcription.formattedString().to_string(),
+        confidence,
+    }
+}
+
+/// 無音などで認識タスクが終わっただけのエラーか
+fn is_transient_error(error: &NSError) -> bool {
+    let code = error.code();
+    let domain = error.domain().to_string();
+    TRANSIENT_ERRORS
+        .iter()
+        .any(|(transient_domain, transient_code)| {
+            domain == *transient_domain && code == *transient_code
+        })
+}
+
+/// オーディオエンジンの開始失敗を解析し、マイクが使用中の可能性があれば分かるようにする
+///
+/// macOS では AVAudioSession から占有中のプロセスを取得できないため、
+/// CoreAudio のエラーコードから推定した手がかりをメッセージに含める。
+fn describe_engine_start_error(error: &NSError) -> String {
+    let code = error.code();
+    let domain = error.domain().to_string();
+    let description = error.localizedDescription().to_string();
+
+    if MIC_BUSY_ERROR_CODES.contains(&code) {
+        format!(
+            "Failed to start audio engine: the microphone appears to be in use by another application. \
+             Close apps that may be recording (video calls, voice memos, other dictation tools) and try again. \
+             ({} {}: {})",
+            domain, code, description
+        )
+    } else if AUDIO_UNIT_INIT_ERROR_CODES.contains(&code) {
+        format!(
+            "Failed to start audio engine: the input device could not be initialized. \
+             The microphone may be busy or disconnected; check other recording apps and the input device settings. \
+             ({} {}: {})",
+            domain, code, description
+        )
+    } else {
+        format!("Failed to start audio engine: {} ({} {})", description, domain, code)
+    }
+}
@@ -0,0 +1,104 @@
+use core_foundation::date::CFDate;
+use core_foundation::runloop::{kCFRunLoopDefaultMode, CFRunLoop, CFRunLoopTimer, CFRunLoopTimerRef};
+use std::ffi::c_void;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// run loop を維持するためのタイマーの発火間隔（秒）。実質的に発火しない長さにする
+const KEEP_ALIVE_INTERVAL_SECS: f64 = 60.0 * 60.0 * 24.0 * 365.0;
+
+/// run loop を一度に回す最長時間（回り始める前に `stop` された場合の取りこぼし対策）
+const RUN_SLICE: Duration = Duration::from_secs(1);
+
+/// 専用スレッドで回し続ける run loop
+///
+/// 入力ソースの無い run loop はすぐに抜けてしまうため、発火しないタイマーを登録して
+/// `stop` されるまでスレッドを生かしておく。
+pub struct RunLoopThread {
+    run_loop: CFRunLoop,
+    stop_flag: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl RunLoopThread {
+    /// `setup` を新しいスレッドで実行し、成功したらそのスレッドの run loop を回し始める
+    ///
+    /// `setup` の戻り値は呼び出し元に返す。失敗した場合はスレッドを終了してエラーを返す。
+    pub fn spawn<T, F>(name: &str, setup: F) -> Result<(Self, T), String>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> Result<T, String> + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel();
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let flag = stop_flag.clone();
+
+        let handle = thread::Builder::new()
+            .name(name.to_string())
+            .spawn(move || {
+                let value = match setup() {
+                    Ok(value) => value,
+                    Err(e) => {
+                        let _ = tx.send(Err(e));
+                        return;
+                    }
+                };
+
+                let run_loop = CFRunLoop::get_current();
+                let fire_date = CFDate::now().abs_time() + KEEP_ALIVE_INTERVAL_SECS;
+                let keep_alive = CFRunLoopTimer::new(
+                    fire_date,
+                    KEEP_ALIVE_INTERVAL_SECS,
+                    0,
+                    0,
+                    keep_alive_callout,
+                    std::ptr::null_mut(),
+                );
+                unsafe {
+                    run_loop.add_timer(&keep_alive, kCFRunLoopDefaultMode);
+                }
+                let _ = tx.send(Ok((run_loop, value)));
+
+                // `stop` が呼ばれるまで回し続ける
+                while !flag.load(Ordering::Relaxed) {
+                    CFRunLoop::run_in_mode(unsafe { kCFRunLoopDefaultMode }, RUN_SLICE, false);
+                }
+            })
+            .map_err(|e| e.to_string())?;
+
+        match rx.recv() {
+            Ok(Ok((run_loop, value))) => Ok((
+                Self {
+                    run_loop,
+                    stop_flag,
+                    handle: Some(handle),
+                },
+                value,
+            )),
+            Ok(Err(e)) => {
+                let _ = handle.join();
+                Err(e)
+            }
+            Err(_) => Err(format!("Thread '{}' exited unexpectedly", name)),
+        }
+    }
+
+    /// run loop を止めてスレッドの終了を待つ
+    pub fn stop(&mut self) {
+        self.stop_flag.store(true, Ordering::Relaxed);
+        self.run_loop.stop();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for RunLoopThread {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+extern "C" fn keep_alive_callout(_timer: CFRunLoopTimerRef, _info: *mut c_void) {}
@@ -0,0 +1,40 @@
+use std::time::{Duration, Instant};
+
+/// 長いポーズを境に発話をセグメントに分ける（簡易な話者分離）
+///
+/// 話者の識別はせず、前の認識結果から一定時間以上空いたら
+/// 「別の人の発話かもしれない」として次のセグメントに進める
+pub struct PauseSegmenter {
+    /// この時間以上空いたら次のセグメントにする（0 なら区切らない）
+    pause: Duration,
+    /// 現在のセグメント番号（認識の開始ごとに 0 から）
+    index: usize,
+    /// 最後に認識結果を受け取った時刻
+    last_result_at: Option<Instant>,
+}
+
+impl PauseSegmenter {
+    /// 新しいPauseSegmenterを作成
+    pub fn new(pause_ms: u64) -> Self {
+        Self {
+            pause: Duration::from_millis(pause_ms),
+            index: 0,
+            last_result_at: None,
+        }
+    }
+
+    /// 認識結果を受け取った時刻から、その結果のセグメント番号を決める
+    ///
+    /// 新しいセグメントに入った場合は、境界になったポーズの長さも返す
+    pub fn segment(&mut self, now: Instant) -> (usize, Option<Duration>) {
+        let pause = self
+            .last_result_at
+            .replace(now)
+            .map(|last| now.duration_since(last))
+            .filter(|pause| !self.pause.is_zero() && *pause >= self.pause);
+        if pause.is_some() {
+            self.index += 1;
+        }
+        (self.index, pause)
+    }
+}
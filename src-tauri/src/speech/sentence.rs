@@ -0,0 +1,61 @@
+/// 言語ごとの既定の文の区切り文字
+pub fn default_delimiters(language: &str) -> &'static str {
+    let language = language.to_lowercase();
+    if language.starts_with("ja") || language.starts_with("zh") {
+        "。！？!?"
+    } else {
+        ".!?"
+    }
+}
+
+/// テキストを区切り文字で文に分割する
+///
+/// - 区切り文字は直前の文に含める（「。」「！？」のように続く場合はまとめて含める）
+/// - 半角の `.` は直後が空白か末尾のときだけ区切りとみなす（「3.5」などを分割しない）
+/// - 末尾の区切り文字のない部分も1文として返す
+/// - 前後の空白は取り除き、空の文は返さない
+pub fn split_sentences(text: &str, delimiters: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut sentences = Vec::new();
+    let mut current = String::new();
+
+    let mut i = 0;
+    while i < chars.len() {
+        current.push(chars[i]);
+
+        if is_boundary(&chars, i, delimiters) {
+            // 連続する区切り文字は同じ文に含める
+            while i + 1 < chars.len() && delimiters.contains(chars[i + 1]) {
+                i += 1;
+                current.push(chars[i]);
+            }
+            push_sentence(&mut sentences, &current);
+            current.clear();
+        }
+        i += 1;
+    }
+    push_sentence(&mut sentences, &current);
+
+    sentences
+}
+
+fn is_boundary(chars: &[char], i: usize, delimiters: &str) -> bool {
+    let c = chars[i];
+    if !delimiters.contains(c) {
+        return false;
+    }
+    if c == '.' {
+        return match chars.get(i + 1) {
+            Some(next) => next.is_whitespace(),
+            None => true,
+        };
+    }
+    true
+}
+
+fn push_sentence(sentences: &mut Vec<String>, sentence: &str) {
+    let sentence = sentence.trim();
+    if !sentence.is_empty() {
+        sentences.push(sentence.to_string());
+    }
+}
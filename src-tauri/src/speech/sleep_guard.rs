@@ -0,0 +1,79 @@
+use objc2::rc::Retained;
+use objc2::runtime::{NSObjectProtocol, ProtocolObject};
+use objc2_foundation::{NSActivityOptions, NSProcessInfo, NSString};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// 認識中に抑止するスリープの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SleepPrevention {
+    /// 抑止しない
+    Off,
+    /// システムのアイドルスリープだけを抑止（ディスプレイは消える）
+    #[default]
+    System,
+    /// ディスプレイのスリープも抑止
+    Display,
+}
+
+impl FromStr for SleepPrevention {
+    type Err = String;
+
+    /// 種類名を解釈する（大文字小文字は区別しない）。不明な名前は System にせずエラーにする
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s.to_lowercase().as_str() {
+            "off" | "none" => Ok(SleepPrevention::Off),
+            "system" => Ok(SleepPrevention::System),
+            "display" => Ok(SleepPrevention::Display),
+            _ => Err(format!(
+                "Unknown sleep prevention: {} (expected off, system or display)",
+                s
+            )),
+        }
+    }
+}
+
+impl SleepPrevention {
+    fn activity_options(self) -> Option<NSActivityOptions> {
+        match self {
+            SleepPrevention::Off => None,
+            // UserInitiated にはシステムのアイドルスリープ抑止が含まれる
+            SleepPrevention::System => Some(NSActivityOptions::UserInitiated),
+            SleepPrevention::Display => Some(
+                NSActivityOptions::UserInitiated | NSActivityOptions::IdleDisplaySleepDisabled,
+            ),
+        }
+    }
+}
+
+/// スリープ抑止のアサーション（`NSProcessInfo.beginActivity`）
+///
+/// Drop で必ず解除するので、保持している値を捨てればリークしない。
+pub struct SleepGuard {
+    activity: Retained<ProtocolObject<dyn NSObjectProtocol>>,
+}
+
+// アクティビティのトークンは不透明なオブジェクトで、NSProcessInfo はスレッドセーフ
+unsafe impl Send for SleepGuard {}
+unsafe impl Sync for SleepGuard {}
+
+impl SleepGuard {
+    /// スリープ抑止を開始（Off なら None）
+    pub fn begin(kind: SleepPrevention) -> Option<Self> {
+        let options = kind.activity_options()?;
+        let reason = NSString::from_str("Speech recognition in progress");
+        let activity = NSProcessInfo::processInfo().beginActivityWithOptions_reason(options, &reason);
+        println!("[SleepGuard] Began activity ({:?})", kind);
+        Some(Self { activity })
+    }
+}
+
+impl Drop for SleepGuard {
+    fn drop(&mut self) {
+        unsafe {
+            NSProcessInfo::processInfo().endActivity(&self.activity);
+        }
+        println!("[SleepGuard] Ended activity");
+    }
+}
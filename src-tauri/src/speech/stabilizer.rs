@@ -0,0 +1,129 @@
+use std::time::{Duration, Instant};
+
+use crate::speech::config::RecognitionResult;
+
+/// 部分結果の安定した先頭部分を「準確定」として扱うためのトラッカー
+///
+/// 部分結果の先頭部分が、最初にその形になってから一定時間変化しなければ、
+/// その部分を committed_prefix として固定する
+pub struct PartialStabilizer {
+    /// 準確定済みのテキスト
+    committed: String,
+    /// 直前の部分結果の各文字と、その文字までの先頭部分が変化しなくなった時刻
+    since: Vec<(char, Instant)>,
+    /// 準確定とみなすまでの時間（0 なら無効）
+    stable_after: Duration,
+}
+
+impl PartialStabilizer {
+    /// 新しいPartialStabilizerを作成
+    pub fn new(stable_after_ms: u64) -> Self {
+        Self {
+            committed: String::new(),
+            since: Vec::new(),
+            stable_after: Duration::from_millis(stable_after_ms),
+        }
+    }
+
+    /// 認識結果に committed_prefix / pending を設定する
+    pub fn apply(&mut self, result: &mut RecognitionResult) {
+        self.apply_at(result, Instant::now());
+    }
+
+    fn apply_at(&mut self, result: &mut RecognitionResult, now: Instant) {
+        if result.is_final {
+            // final は全文確定。次の発話に備えてリセット
+            result.committed_prefix = result.text.clone();
+            result.pending = String::new();
+            self.committed.clear();
+            self.since.clear();
+            return;
+        }
+
+        // 前回と共通の先頭部分は変化しなくなった時刻を引き継ぎ、残りは今から数える
+        let unchanged = self
+            .since
+            .iter()
+            .zip(result.text.chars())
+            .take_while(|((previous, _), current)| previous == current)
+            .count();
+        self.since.truncate(unchanged);
+        self.since
+            .extend(result.text.chars().skip(unchanged).map(|c| (c, now)));
+
+        if !self.stable_after.is_zero() {
+            // 一定時間変化していない最長の先頭部分を準確定にする
+            // （短い先頭部分ほど早く変化しなくなるので、時刻は先頭から単調に増える）
+            let stable_chars = self
+                .since
+                .iter()
+                .take_while(|(_, since)| now.duration_since(*since) >= self.stable_after)
+                .count();
+            let stable: String = result.text.chars().take(stable_chars).collect();
+            if stable.starts_with(&self.committed) && stable.len() > self.committed.len() {
+                self.committed = stable;
+            }
+        }
+
+        result.committed_prefix = self.committed.clone();
+        result.pending = match result.text.strip_prefix(&self.committed) {
+            Some(rest) => rest.to_string(),
+            // 認識器が準確定部分を書き換えた場合は、確定済みの文字数以降を未確定として扱う
+            None => result
+                .text
+                .chars()
+                .skip(self.committed.chars().count())
+                .collect(),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn partial(text: &str) -> RecognitionResult {
+        RecognitionResult {
+            text: text.to_string(),
+            is_final: false,
+            committed_prefix: String::new(),
+            pending: String::new(),
+            plain_text: None,
+            segment_index: 0,
+            alternatives: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn frequent_partials_still_commit_a_prefix_that_stays_unchanged() {
+        let mut stabilizer = PartialStabilizer::new(500);
+        let start = Instant::now();
+        // 部分結果は 200ms ごとに届き、前回との間隔は一度も 500ms に届かない
+        let mut result = partial("");
+        for (ms, text) in [
+            (0, "今日は"),
+            (200, "今日はいい"),
+            (400, "今日はいい天気"),
+            (600, "今日はいい天気です"),
+        ] {
+            result = partial(text);
+            stabilizer.apply_at(&mut result, start + Duration::from_millis(ms));
+        }
+        assert_eq!(result.committed_prefix, "今日は");
+        assert_eq!(result.pending, "いい天気です");
+    }
+
+    #[test]
+    fn a_rewritten_prefix_starts_counting_again() {
+        let mut stabilizer = PartialStabilizer::new(500);
+        let start = Instant::now();
+        let mut result = partial("今日は");
+        stabilizer.apply_at(&mut result, start);
+        let mut result = partial("今朝は");
+        stabilizer.apply_at(&mut result, start + Duration::from_millis(400));
+        let mut result = partial("今朝はいい");
+        stabilizer.apply_at(&mut result, start + Duration::from_millis(600));
+        assert_eq!(result.committed_prefix, "今");
+        assert_eq!(result.pending, "朝はいい");
+    }
+}
@@ -0,0 +1,94 @@
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::speech::config::RecognitionResult;
+
+/// 結果を送る処理（`speech:result` の送信）
+type Sender = Box<dyn Fn(&RecognitionResult) + Send + Sync + 'static>;
+
+/// 部分結果の送信を最小間隔で間引く（UI のちらつき防止）
+///
+/// 間隔内に届いた部分結果は最新のものだけを残し、間隔が空いた時点でまとめて送る。
+/// final は待たずにすぐ送り、送られていない部分結果は捨てる
+pub struct PartialThrottle {
+    /// 部分結果を送る最小間隔（0 なら間引かない）
+    interval: Duration,
+    /// 遅れて送るスレッドと共有する状態
+    state: Arc<Mutex<ThrottleState>>,
+    sender: Arc<Sender>,
+}
+
+#[derive(Default)]
+struct ThrottleState {
+    /// 最後に部分結果を送った時刻
+    last_sent: Option<Instant>,
+    /// 間隔が空くのを待っている最新の部分結果
+    pending: Option<RecognitionResult>,
+    /// 遅れて送るスレッドが待機中か
+    scheduled: bool,
+}
+
+impl PartialThrottle {
+    /// 新しいPartialThrottleを作成
+    pub fn new<F>(interval_ms: u64, sender: F) -> Self
+    where
+        F: Fn(&RecognitionResult) + Send + Sync + 'static,
+    {
+        Self {
+            interval: Duration::from_millis(interval_ms),
+            state: Arc::new(Mutex::new(ThrottleState::default())),
+            sender: Arc::new(Box::new(sender)),
+        }
+    }
+
+    /// 結果を送る（部分結果は間隔に応じて間引く）
+    ///
+    /// 送信は状態のロック中に行うので、遅れて送る部分結果が final より後に届くことはない
+    pub fn submit(&self, result: RecognitionResult) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        let now = Instant::now();
+        let elapsed = state.last_sent.map(|sent| now.duration_since(sent));
+        let due = match elapsed {
+            Some(elapsed) => elapsed >= self.interval,
+            None => true,
+        };
+        if result.is_final || due {
+            state.pending = None;
+            if !result.is_final {
+                state.last_sent = Some(now);
+            }
+            (self.sender)(&result);
+            return;
+        }
+
+        state.pending = Some(result);
+        if state.scheduled {
+            return;
+        }
+        state.scheduled = true;
+        let delay = self.interval - elapsed.unwrap_or_default();
+        let shared = self.state.clone();
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            thread::sleep(delay);
+            let Ok(mut state) = shared.lock() else {
+                return;
+            };
+            state.scheduled = false;
+            if let Some(result) = state.pending.take() {
+                state.last_sent = Some(Instant::now());
+                sender(&result);
+            }
+        });
+    }
+
+    /// 送られていない部分結果を捨てる（認識の停止や言語切替時）
+    pub fn cancel(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.pending = None;
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use std::time::{Duration, Instant};
+
+use crate::speech::config::RecognitionResult;
+
+/// 咳払いやノイズによる短すぎる確定結果を捨てるフィルタ
+///
+/// 発話の長さは、最初の部分結果を受け取ってから final を受け取るまでの時間で測る
+pub struct UtteranceFilter {
+    /// これより文字数が少ない final は捨てる（0 なら無効）
+    min_chars: usize,
+    /// これより発話が短い final は捨てる（0 なら無効）
+    min_duration: Duration,
+    /// 発話中の最初の部分結果を受け取った時刻
+    started_at: Option<Instant>,
+}
+
+impl UtteranceFilter {
+    /// 新しいUtteranceFilterを作成
+    pub fn new(min_chars: usize, min_duration_ms: u64) -> Self {
+        Self {
+            min_chars,
+            min_duration: Duration::from_millis(min_duration_ms),
+            started_at: None,
+        }
+    }
+
+    /// 結果を送ってよいかを判定する（部分結果は常に通す）
+    ///
+    /// final を受け取ると次の発話に備えて計測をリセットする
+    pub fn accept(&mut self, result: &RecognitionResult, now: Instant) -> bool {
+        if !result.is_final {
+            self.started_at.get_or_insert(now);
+            return true;
+        }
+
+        let started_at = self.started_at.take();
+        let chars = result.text.trim().chars().count();
+        if chars < self.min_chars {
+            return false;
+        }
+        // 部分結果なしでいきなり final が来た場合は長さ 0 とみなす
+        let duration = started_at
+            .map(|started_at| now.duration_since(started_at))
+            .unwrap_or_default();
+        duration >= self.min_duration
+    }
+}
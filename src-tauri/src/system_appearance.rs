@@ -0,0 +1,99 @@
+use crate::events;
+use block2::RcBlock;
+use objc2::msg_send;
+use objc2::rc::Retained;
+use objc2::runtime::{AnyClass, AnyObject};
+use objc2_foundation::{
+    NSDistributedNotificationCenter, NSNotification, NSOperationQueue, NSString,
+};
+use serde::Serialize;
+use std::ptr::NonNull;
+use std::sync::{Arc, Mutex};
+use tauri::AppHandle;
+
+/// Posted (as a distributed notification) when the user switches between dark and light
+const THEME_CHANGED_NOTIFICATION: &str = "AppleInterfaceThemeChangedNotification";
+
+/// Whether macOS is showing the dark or the light appearance
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SystemAppearance {
+    Dark,
+    Light,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SystemAppearancePayload {
+    pub appearance: SystemAppearance,
+}
+
+/// The appearance NSApp is drawn with (`NSApp.effectiveAppearance`)
+///
+/// Every dark variant (including the high-contrast one) counts as dark.
+pub fn current() -> Result<SystemAppearance, String> {
+    let class = AnyClass::get(c"NSApplication").ok_or("NSApplication is not available")?;
+    unsafe {
+        let app: Option<Retained<AnyObject>> = msg_send![class, sharedApplication];
+        let app = app.ok_or("NSApp is not available")?;
+        let appearance: Option<Retained<AnyObject>> = msg_send![&*app, effectiveAppearance];
+        let appearance = appearance.ok_or("NSApp has no effective appearance")?;
+        let name: Option<Retained<NSString>> = msg_send![&*appearance, name];
+        let is_dark = name.is_some_and(|name| name.to_string().contains("Dark"));
+        Ok(if is_dark {
+            SystemAppearance::Dark
+        } else {
+            SystemAppearance::Light
+        })
+    }
+}
+
+/// Emit `system:appearance-changed` (and `system-appearance-changed`, see
+/// `events::LEGACY_EVENT_NAMES`) whenever the user switches between dark and light
+///
+/// Must be called on the main thread. The notification is posted before NSApp's
+/// appearance has been updated, so it is read again on the next turn of the main
+/// queue, and the event is only sent when the result differs from the last one.
+/// The registration lasts for the rest of the process.
+pub fn watch_changes(app: &AppHandle) {
+    let last = Arc::new(Mutex::new(current().ok()));
+    let app = app.clone();
+    let block = RcBlock::new(move |_notification: NonNull<NSNotification>| {
+        let app = app.clone();
+        let last = last.clone();
+        let read_later = RcBlock::new(move || emit_if_changed(&app, &last));
+        NSOperationQueue::mainQueue().addOperationWithBlock(&read_later);
+    });
+    let name = NSString::from_str(THEME_CHANGED_NOTIFICATION);
+    let center = NSDistributedNotificationCenter::defaultCenter();
+    let observer = unsafe {
+        center.addObserverForName_object_queue_usingBlock(
+            Some(&name),
+            None,
+            Some(&NSOperationQueue::mainQueue()),
+            &block,
+        )
+    };
+    // Never removed, so the observer is kept for the rest of the process
+    std::mem::forget(observer);
+}
+
+fn emit_if_changed(app: &AppHandle, last: &Mutex<Option<SystemAppearance>>) {
+    let appearance = match current() {
+        Ok(appearance) => appearance,
+        Err(e) => {
+            eprintln!("[appearance] {}", e);
+            return;
+        }
+    };
+    let Ok(mut last) = last.lock() else {
+        return;
+    };
+    if *last == Some(appearance) {
+        return;
+    }
+    *last = Some(appearance);
+    let payload = SystemAppearancePayload { appearance };
+    if let Err(e) = events::emit(app, events::SYSTEM_APPEARANCE_CHANGED, payload) {
+        eprintln!("[appearance] Failed to emit appearance change: {}", e);
+    }
+}
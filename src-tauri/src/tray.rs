@@ -0,0 +1,191 @@
+use crate::events;
+use crate::speech::{SpeechAudioLevelPayload, SpeechRecognitionState, SpeechStatePayload};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::image::Image;
+use tauri::tray::{TrayIcon, TrayIconBuilder};
+use tauri::{AppHandle, Listener, Runtime};
+
+const TRAY_ID: &str = "main";
+
+/// Size of the generated microphone icon (pixels)
+const MIC_ICON_SIZE: u32 = 32;
+
+/// Minimum interval between icon redraws driven by the audio level
+/// (`speech:audio-level` arrives about 10 times a second)
+const LEVEL_REDRAW_INTERVAL: Duration = Duration::from_millis(150);
+
+/// Levels (dBFS) drawn as the dimmest and the brightest microphone
+const QUIET_DB: f32 = -50.0;
+const LOUD_DB: f32 = -10.0;
+
+/// Brightness steps of the microphone; the icon is only redrawn when the step changes
+const LEVEL_STEPS: u8 = 4;
+
+const MIC_COLOR: [u8; 3] = [230, 57, 70];
+const PROCESSING_BADGE_COLOR: [u8; 3] = [52, 120, 246];
+const ERROR_BADGE_COLOR: [u8; 3] = [255, 159, 10];
+
+/// Tray icon that reflects the speech recognition state
+///
+/// Idle shows the app icon, Listening a microphone that pulses with the input level,
+/// and Processing/Error the app icon with a colored badge.
+struct SpeechTray<R: Runtime> {
+    tray: TrayIcon<R>,
+    app_icon: Image<'static>,
+    state: Mutex<TrayState>,
+}
+
+struct TrayState {
+    speech: SpeechRecognitionState,
+    level_step: u8,
+    last_redraw: Option<Instant>,
+}
+
+/// Create the tray icon and keep it in sync with `speech:state-changed` and `speech:audio-level`
+pub fn setup<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
+    let app_icon = app
+        .default_window_icon()
+        .map(|icon| Image::new_owned(icon.rgba().to_vec(), icon.width(), icon.height()))
+        .ok_or("No app icon for the tray")?;
+    let tray = TrayIconBuilder::with_id(TRAY_ID)
+        .icon(app_icon.clone())
+        .tooltip("funhou")
+        .build(app)
+        .map_err(|e| e.to_string())?;
+
+    let speech_tray = Arc::new(SpeechTray {
+        tray,
+        app_icon,
+        state: Mutex::new(TrayState {
+            speech: SpeechRecognitionState::Idle,
+            level_step: 0,
+            last_redraw: None,
+        }),
+    });
+
+    let state_tray = speech_tray.clone();
+    app.listen_any(
+        events::SPEECH_STATE_CHANGED,
+        move |event| match serde_json::from_str::<SpeechStatePayload>(event.payload()) {
+            Ok(payload) => state_tray.set_speech_state(payload.state),
+            Err(e) => eprintln!("[tray] Invalid speech state {}: {}", event.payload(), e),
+        },
+    );
+    app.listen_any(events::SPEECH_AUDIO_LEVEL, move |event| {
+        if let Ok(payload) = serde_json::from_str::<SpeechAudioLevelPayload>(event.payload()) {
+            speech_tray.set_level(payload.level_db);
+        }
+    });
+    Ok(())
+}
+
+impl<R: Runtime> SpeechTray<R> {
+    fn set_speech_state(&self, speech: SpeechRecognitionState) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        if state.speech == speech {
+            return;
+        }
+        state.speech = speech;
+        state.level_step = 0;
+        state.last_redraw = Some(Instant::now());
+        self.redraw(&state);
+    }
+
+    fn set_level(&self, level_db: f32) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        if state.speech != SpeechRecognitionState::Listening {
+            return;
+        }
+        let step = level_step(level_db);
+        let now = Instant::now();
+        let too_soon = state
+            .last_redraw
+            .is_some_and(|last| now.duration_since(last) < LEVEL_REDRAW_INTERVAL);
+        if step == state.level_step || too_soon {
+            return;
+        }
+        state.level_step = step;
+        state.last_redraw = Some(now);
+        self.redraw(&state);
+    }
+
+    fn redraw(&self, state: &TrayState) {
+        let (icon, tooltip) = match state.speech {
+            SpeechRecognitionState::Idle => (self.app_icon.clone(), "funhou"),
+            SpeechRecognitionState::Listening => (mic_icon(state.level_step), "funhou - 録音中"),
+            SpeechRecognitionState::Processing => (
+                with_badge(&self.app_icon, PROCESSING_BADGE_COLOR),
+                "funhou - 認識中",
+            ),
+            SpeechRecognitionState::Error => (
+                with_badge(&self.app_icon, ERROR_BADGE_COLOR),
+                "funhou - 音声認識エラー",
+            ),
+        };
+        if let Err(e) = self.tray.set_icon(Some(icon)) {
+            eprintln!("[tray] Failed to update icon: {}", e);
+        }
+        if let Err(e) = self.tray.set_tooltip(Some(tooltip)) {
+            eprintln!("[tray] Failed to update tooltip: {}", e);
+        }
+    }
+}
+
+/// Quantize an input level into `0..LEVEL_STEPS`
+fn level_step(level_db: f32) -> u8 {
+    let ratio = ((level_db - QUIET_DB) / (LOUD_DB - QUIET_DB)).clamp(0.0, 1.0);
+    ((ratio * (LEVEL_STEPS - 1) as f32).round() as u8).min(LEVEL_STEPS - 1)
+}
+
+/// Draw a microphone whose opacity grows with the level step
+fn mic_icon(level_step: u8) -> Image<'static> {
+    let alpha = 96.0 + 159.0 * level_step as f64 / (LEVEL_STEPS - 1) as f64;
+    let size = MIC_ICON_SIZE;
+    let mut rgba = vec![0u8; (size * size * 4) as usize];
+
+    for y in 0..size {
+        for x in 0..size {
+            // Pixel center in 0..1 coordinates
+            let px = (x as f64 + 0.5) / size as f64;
+            let py = (y as f64 + 0.5) / size as f64;
+
+            // Capsule (the microphone head), the U-shaped holder, the stem and the base
+            let capsule = (px - 0.5).hypot(py - py.clamp(0.27, 0.42)) <= 0.15;
+            let ring = (px - 0.5).hypot(py - 0.42);
+            let holder = py >= 0.42 && (0.24..=0.30).contains(&ring);
+            let stem = (0.47..=0.53).contains(&px) && (0.70..=0.86).contains(&py);
+            let base = (0.32..=0.68).contains(&px) && (0.84..=0.90).contains(&py);
+
+            if capsule || holder || stem || base {
+                let i = ((y * size + x) * 4) as usize;
+                rgba[i..i + 3].copy_from_slice(&MIC_COLOR);
+                rgba[i + 3] = alpha as u8;
+            }
+        }
+    }
+    Image::new_owned(rgba, size, size)
+}
+
+/// Draw a filled circle in the bottom-right corner of the icon
+fn with_badge(icon: &Image<'_>, color: [u8; 3]) -> Image<'static> {
+    let (width, height) = (icon.width(), icon.height());
+    let mut rgba = icon.rgba().to_vec();
+    let radius = width.min(height) as f64 / 4.0;
+    let (cx, cy) = (width as f64 - radius, height as f64 - radius);
+
+    for y in 0..height {
+        for x in 0..width {
+            if (x as f64 + 0.5 - cx).hypot(y as f64 + 0.5 - cy) <= radius {
+                let i = ((y * width + x) * 4) as usize;
+                rgba[i..i + 3].copy_from_slice(&color);
+                rgba[i + 3] = 255;
+            }
+        }
+    }
+    Image::new_owned(rgba, width, height)
+}
@@ -35,6 +35,7 @@ import { TaskIdentifier, TaskClaudeSession as TaskClaudeSessionType } from '@/ty
 import { DailySummarySidebar } from '@/components/DailySummarySidebar'
 import { GeminiToolbar } from '@/components/GeminiToolbar'
 import { GeminiSaveDialog } from '@/components/GeminiSaveDialog'
+import { SidebarResizeHandle } from '@/components/SidebarResizeHandle'
 import { useGeminiEntryIntegration } from '@/hooks/useGeminiEntryIntegration'
 import { getSettings, applyFont, applyFontSize } from '@/lib/settings'
 import { applyTheme, ThemeVariant } from '@/lib/themes'
@@ -516,6 +517,8 @@ function App() {
   return (
     <ClaudeTerminalSessionProvider>
     <div className="app">
+      {/* 画面端に寄せたサイドバーの幅を変えるドラッグハンドル */}
+      <SidebarResizeHandle />
       {/* 日次サマリーサイドバーのトグルボタン（左下に配置） */}
       {!summarySidebarOpen && (
         <button
@@ -1,4 +1,5 @@
 import { useEffect, useState, useMemo, useRef } from 'react'
+import { AlertTriangle } from 'lucide-react'
 import { useClaudeLogs } from '../hooks/useClaudeLogs'
 import { Button } from './ui/button'
 import { Input } from './ui/input'
@@ -332,6 +333,7 @@ export function ClaudeLogImporter({
                     <div className="font-medium">{project.name}</div>
                     <div className="text-sm text-gray-500 flex gap-4">
                       <span>{project.session_count} セッション</span>
+                      {project.project_type && <span>{project.project_type}</span>}
                       {project.last_updated && (
                         <span>{formatTimestamp(project.last_updated)}</span>
                       )}
@@ -355,8 +357,11 @@ export function ClaudeLogImporter({
                     className="p-3 border rounded cursor-pointer hover:bg-gray-100 dark:hover:bg-gray-800"
                     onClick={() => handleSessionSelect(session)}
                   >
-                    <div className="font-medium">
-                      {truncateText(session.first_message, 50) || 'No message'}
+                    <div className="font-medium flex items-center gap-1">
+                      {session.ended_with_error && (
+                        <AlertTriangle className="h-4 w-4 shrink-0 text-red-500" aria-label="エラー終了" />
+                      )}
+                      <span>{truncateText(session.first_message, 50) || 'No message'}</span>
                     </div>
                     <div className="text-xs text-gray-400 font-mono mt-1">
                       {session.session_id}
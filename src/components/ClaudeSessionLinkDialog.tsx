@@ -154,6 +154,7 @@ export function ClaudeSessionLinkDialog({
                     <div className="font-medium">{project.name}</div>
                     <div className="text-sm text-gray-500 flex gap-4">
                       <span>{project.session_count} セッション</span>
+                      {project.project_type && <span>{project.project_type}</span>}
                       {project.last_updated && (
                         <span>{formatTimestamp(project.last_updated)}</span>
                       )}
@@ -1,4 +1,5 @@
 import { useState, useEffect, useMemo } from 'react'
+import { AlertTriangle } from 'lucide-react'
 import Database from '@tauri-apps/plugin-sql'
 import { CwdSelector } from './CwdSelector'
 import { Button } from './ui/button'
@@ -414,6 +415,7 @@ export function ClaudeTerminalDialog({
                       <div className="font-medium">{project.name}</div>
                       <div className="text-sm text-gray-500 flex gap-4">
                         <span>{project.session_count} セッション</span>
+                        {project.project_type && <span>{project.project_type}</span>}
                         {project.last_updated && (
                           <span>{formatTimestamp(project.last_updated)}</span>
                         )}
@@ -446,8 +448,11 @@ export function ClaudeTerminalDialog({
                       }`}
                       onClick={() => session.cwd && handleSessionSelect(session)}
                     >
-                      <div className="font-medium">
-                        {truncateText(session.first_message, 50) || 'No message'}
+                      <div className="font-medium flex items-center gap-1">
+                        {session.ended_with_error && (
+                          <AlertTriangle className="h-4 w-4 shrink-0 text-red-500" aria-label="エラー終了" />
+                        )}
+                        <span>{truncateText(session.first_message, 50) || 'No message'}</span>
                       </div>
                       <div className="text-xs text-gray-400 font-mono mt-1">
                         {session.session_id}
@@ -204,6 +204,7 @@ export function ClaudeTerminalWidget({ isOpen, onToggle }: ClaudeTerminalWidgetP
   const {
     getActiveSessions,
     writeToSession,
+    safeWriteToSession,
     terminateSession,
     updateSessionName,
     setDialogOpen,
@@ -244,9 +245,11 @@ export function ClaudeTerminalWidget({ isOpen, onToggle }: ClaudeTerminalWidgetP
   // 入力送信
   const handleSendInput = useCallback((sessionId: string, input: string) => {
     // テキストを送信してからEnterキー（\r）を別途送信
-    writeToSession(sessionId, input)
+    // 複数行のテキストは行ごとに実行されないよう bracketed paste で囲む
+    // Enter は貼り付けとして扱われないよう囲まずに送る
+    safeWriteToSession(sessionId, input)
     writeToSession(sessionId, '\r')
-  }, [writeToSession])
+  }, [writeToSession, safeWriteToSession])
 
   // アクティブセッションがない、またはダイアログが開いている場合は表示しない
   if (activeSessions.length === 0 || isDialogOpen) {
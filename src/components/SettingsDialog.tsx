@@ -1,6 +1,5 @@
 import { useState, useEffect } from 'react'
 import Database from '@tauri-apps/plugin-sql'
-import { getCurrentWindow } from '@tauri-apps/api/window'
 import { invoke } from '@tauri-apps/api/core'
 import {
   Dialog,
@@ -83,9 +82,8 @@ export function SettingsDialog({ open, onOpenChange, db, onFontChange, onFontSiz
       setAlwaysOnTopState(checked)
       await setAlwaysOnTop(db, checked)
 
-      // ウィンドウの最前面表示設定を変更
-      const window = getCurrentWindow()
-      await window.setAlwaysOnTop(checked)
+      // ウィンドウの最前面表示設定を変更（次回起動時にも適用されるよう Rust 側にも保存される）
+      await invoke('set_always_on_top', { enabled: checked })
     } catch (error) {
       console.error('設定の変更に失敗しました:', error)
       // エラーが発生した場合は元の状態に戻す
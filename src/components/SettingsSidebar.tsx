@@ -1,6 +1,5 @@
 import { useState, useEffect } from 'react'
 import Database from '@tauri-apps/plugin-sql'
-import { getCurrentWindow } from '@tauri-apps/api/window'
 import { invoke } from '@tauri-apps/api/core'
 import { Settings, ChevronRight } from 'lucide-react'
 import { Switch } from '@/components/ui/switch'
@@ -146,8 +145,7 @@ export function SettingsSidebar({
     try {
       setAlwaysOnTopState(checked)
       await setAlwaysOnTop(db, checked)
-      const window = getCurrentWindow()
-      await window.setAlwaysOnTop(checked)
+      await invoke('set_always_on_top', { enabled: checked })
     } catch (error) {
       console.error('設定の変更に失敗しました:', error)
       setAlwaysOnTopState(!checked)
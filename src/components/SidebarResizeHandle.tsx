@@ -0,0 +1,80 @@
+import { useEffect, useRef, useState } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { getAppStatus } from '@/lib/appStatus'
+import { onSidebarEdgeChanged } from '@/lib/windowBridge'
+import type { ScreenEdge } from '@/lib/settings'
+
+/**
+ * 左右の画面端に寄せたサイドバーの内側の縁をドラッグして幅を変えるハンドル
+ * 幅は autohide の設定に保存され、次回起動時も同じ幅で開く
+ */
+export function SidebarResizeHandle() {
+  const [edge, setEdge] = useState<ScreenEdge | null>(null)
+  const dragRef = useRef<{ startX: number; startWidth: number; width: number | null } | null>(null)
+  const frameRef = useRef<number | null>(null)
+
+  useEffect(() => {
+    getAppStatus()
+      .then((status) => setEdge(status.autohide.config.edge))
+      .catch((error) => console.error('Failed to get autohide edge:', error))
+
+    // 設定や自動選択で寄せる端が変わったら追従する
+    const unlisten = onSidebarEdgeChanged(({ edge }) => setEdge(edge))
+    return () => {
+      unlisten.then((fn) => fn())
+    }
+  }, [])
+
+  if (edge !== 'left' && edge !== 'right') {
+    return null
+  }
+
+  const resize = (width: number, persist: boolean) => {
+    invoke('set_sidebar_width', { width, persist }).catch((error) => {
+      console.error('Failed to resize sidebar:', error)
+    })
+  }
+
+  const handlePointerDown = (event: React.PointerEvent<HTMLDivElement>) => {
+    event.currentTarget.setPointerCapture(event.pointerId)
+    // ウィンドウ自体が動く（右端では左の縁が動く）ので画面座標で測る
+    dragRef.current = { startX: event.screenX, startWidth: window.innerWidth, width: null }
+  }
+
+  const handlePointerMove = (event: React.PointerEvent<HTMLDivElement>) => {
+    const drag = dragRef.current
+    if (!drag) return
+    const delta = event.screenX - drag.startX
+    const width = Math.round(drag.startWidth + (edge === 'left' ? delta : -delta))
+    drag.width = width
+    // ドラッグ中は保存せずにリサイズだけする（1フレームに1回まで）
+    if (frameRef.current !== null) cancelAnimationFrame(frameRef.current)
+    frameRef.current = requestAnimationFrame(() => {
+      frameRef.current = null
+      resize(width, false)
+    })
+  }
+
+  const handlePointerUp = (event: React.PointerEvent<HTMLDivElement>) => {
+    event.currentTarget.releasePointerCapture(event.pointerId)
+    const width = dragRef.current?.width ?? null
+    dragRef.current = null
+    if (frameRef.current !== null) {
+      cancelAnimationFrame(frameRef.current)
+      frameRef.current = null
+    }
+    // 離したときの幅で一度だけ保存する
+    if (width !== null) resize(width, true)
+  }
+
+  return (
+    <div
+      className={`sidebar-resize-handle edge-${edge}`}
+      onPointerDown={handlePointerDown}
+      onPointerMove={handlePointerMove}
+      onPointerUp={handlePointerUp}
+      onPointerCancel={handlePointerUp}
+      aria-label="サイドバーの幅を変更"
+    />
+  )
+}
@@ -798,6 +798,7 @@ export function TaskClaudeSessionsDialog({
                         <div className="text-xs text-muted-foreground mt-1">
                           {project.session_count} セッション
                           {project.last_updated && ` • ${formatTimestamp(project.last_updated)}`}
+                          {project.project_type && ` • ${project.project_type}`}
                         </div>
                       </div>
                     ))}
@@ -10,16 +10,21 @@ import {
 import {
   spawnClaudeTerminal,
   resumeClaudeTerminal,
+  onPtyInput,
+  onPtyActiveChanged,
   type ClaudeTerminalSession,
+  type SafeWriteOptions,
 } from '../lib/claudeTerminal'
 import {
   createTerminalWindow,
   closeTerminalWindow,
   sendToTerminalWindow,
   listenFromTerminalWindow,
+  onWindowReopened,
   type WindowState,
   type SessionSyncPayload,
 } from '../lib/windowBridge'
+import { resumeClaudeSessionInPty } from '../lib/claudeLogs'
 import type { IDisposable } from 'tauri-pty'
 import type { UnlistenFn } from '@tauri-apps/api/event'
 
@@ -67,6 +72,12 @@ export interface TerminalSession {
 // バッファをRefで管理するための型
 interface SessionBuffer {
   chunks: string[]
+  /** chunks の各要素の UTF-8 のバイト数 */
+  chunkBytes: number[]
+  /** chunks 全体のバイト数 */
+  bytes: number
+  /** 上限を超えて古い出力を捨てたか */
+  truncated: boolean
   lastActivityAt: Date
 }
 
@@ -77,18 +88,38 @@ interface ClaudeTerminalSessionContextValue {
   activeSessionId: string | null
 
   // セッション操作
-  createSession: (cwd: string, claudeSessionId?: string, name?: string, initialSize?: { cols: number; rows: number }) => Promise<string>
+  createSession: (cwd: string, claudeSessionId?: string, name?: string, initialSize?: { cols: number; rows: number }, spawnOptions?: { cleanEnv?: boolean; lineMode?: boolean; timestampOutput?: boolean }) => Promise<string>
   getSession: (sessionId: string) => TerminalSession | undefined
   getActiveSessions: () => TerminalSession[]
   terminateSession: (sessionId: string, graceful?: boolean) => Promise<void>
   resizeSession: (sessionId: string, cols: number, rows: number) => void
   updateSessionName: (sessionId: string, name: string) => void
   updateSessionClaudeId: (sessionId: string, claudeSessionId: string) => void
+  /** 既存のPTYタブ（シェルのプロンプト待ち）で Claude Code セッションを再開する */
+  resumeClaudeInPty: (ptyId: string, claudeSessionId: string, cwd: string) => Promise<void>
 
   // 入出力
   writeToSession: (sessionId: string, data: string) => void
+  /** 改行を含む入力を bracketed paste で安全に書き込む */
+  safeWriteToSession: (sessionId: string, data: string, options?: SafeWriteOptions) => void
+  /** 音声認識の確定前の結果を入力行に仮表示する（前回の仮表示を置き換える） */
+  previewSpeechInSession: (sessionId: string, text: string) => void
+  /** 仮表示を確定した認識結果に置き換える（Enter は送らない） */
+  commitSpeechInSession: (sessionId: string, text: string) => void
+  /** 音声認識の仮表示を消す */
+  cancelSpeechInSession: (sessionId: string) => void
   getSessionOutput: (sessionId: string) => string[]
+  /** アプリ内で入力して確定した行の履歴（古い順。パスワードなど echo off 中の入力は含まない） */
+  getPtyInputHistory: (sessionId: string) => string[]
   subscribeToOutput: (sessionId: string, callback: (data: string) => void) => () => void
+  /**
+   * 出力を購読者に流すセッションを1つに絞る（null で全セッションに戻す）
+   * 他のセッションの出力はバッファにだけ溜め、切り替え時に requestPtyRedraw で再送する
+   * 別ウィンドウで開いているセッションは常に流す
+   */
+  setActivePty: (sessionId: string | null) => void
+  /** セッションのバッファを購読者に再送する（画面をリセットしてから全体を書き直す） */
+  requestPtyRedraw: (sessionId: string) => void
 
   // UI状態
   setActiveSession: (sessionId: string | null) => void
@@ -106,7 +137,32 @@ interface ClaudeTerminalSessionContextValue {
 
 const ClaudeTerminalSessionContext = createContext<ClaudeTerminalSessionContextValue | null>(null)
 
-const MAX_BUFFER_SIZE = 10000
+// セッションごとに保持する出力の上限（UTF-8 のバイト数）。超えたら古いチャンクから捨てる
+const MAX_BUFFER_BYTES = 4 * 1024 * 1024
+// 古い出力を捨てたバッファを再送するときに先頭に付ける行
+const TRUNCATED_NOTICE = '\x1b[2m[古い出力は省略されました]\x1b[0m\r\n'
+
+const textEncoder = new TextEncoder()
+
+// 上限を超えた分を古いチャンクから捨てる（最新のチャンクは上限を超えていても残す）
+function trimBuffer(buffer: SessionBuffer) {
+  let dropCount = 0
+  while (buffer.bytes > MAX_BUFFER_BYTES && dropCount < buffer.chunks.length - 1) {
+    buffer.bytes -= buffer.chunkBytes[dropCount]
+    dropCount++
+  }
+  if (dropCount > 0) {
+    buffer.chunks.splice(0, dropCount)
+    buffer.chunkBytes.splice(0, dropCount)
+    buffer.truncated = true
+  }
+}
+
+// 再送用のバッファの内容（古い出力を捨てていれば、そのことを先頭で示す）
+function bufferedOutput(buffer: SessionBuffer | undefined): string[] {
+  if (!buffer) return []
+  return buffer.truncated ? [TRUNCATED_NOTICE, ...buffer.chunks] : buffer.chunks
+}
 
 export function ClaudeTerminalSessionProvider({ children }: { children: ReactNode }) {
   const [sessions, setSessions] = useState<Map<string, TerminalSession>>(new Map())
@@ -129,6 +185,9 @@ export function ClaudeTerminalSessionProvider({ children }: { children: ReactNod
   const ptyDisposersRef = useRef<Map<string, IDisposable>>(new Map())
   // バッファをRefで管理（React state更新を避けてパフォーマンス向上）
   const sessionBuffersRef = useRef<Map<string, SessionBuffer>>(new Map())
+  // 出力を流すセッション（null なら全セッション）と、流さずにバッファだけ溜めたセッション
+  const activePtyIdRef = useRef<string | null>(null)
+  const pendingRedrawRef = useRef<Set<string>>(new Set())
   // ウィンドウイベントリスナーを管理
   const windowListenersRef = useRef<Map<string, UnlistenFn>>(new Map())
 
@@ -162,20 +221,28 @@ export function ClaudeTerminalSessionProvider({ children }: { children: ReactNod
     // バッファをRefに追加（React state更新なし = 高速）
     let buffer = sessionBuffersRef.current.get(sessionId)
     if (!buffer) {
-      buffer = { chunks: [], lastActivityAt: new Date() }
+      buffer = { chunks: [], chunkBytes: [], bytes: 0, truncated: false, lastActivityAt: new Date() }
       sessionBuffersRef.current.set(sessionId, buffer)
     }
+    const chunkBytes = textEncoder.encode(filteredData).length
     buffer.chunks.push(filteredData)
+    buffer.chunkBytes.push(chunkBytes)
+    buffer.bytes += chunkBytes
     buffer.lastActivityAt = new Date()
 
     // バッファサイズ制限（Refに対して直接操作）
-    if (buffer.chunks.length > MAX_BUFFER_SIZE) {
-      buffer.chunks = buffer.chunks.slice(-MAX_BUFFER_SIZE / 2)
-    }
+    trimBuffer(buffer)
 
     // 購読者に通知（フィルタリング済みデータ）- これが最優先
+    // 非アクティブなセッションはバッファにだけ溜め、アクティブになったときに再送する
+    const activePtyId = activePtyIdRef.current
+    const streaming = activePtyId === null
+      || activePtyId === sessionId
+      || windowStatesRef.current.get(sessionId)?.isOpen === true
     const subscribers = outputSubscribersRef.current.get(sessionId)
-    if (subscribers) {
+    if (!streaming) {
+      pendingRedrawRef.current.add(sessionId)
+    } else if (subscribers) {
       subscribers.forEach((callback) => callback(filteredData))
     }
 
@@ -239,7 +306,10 @@ export function ClaudeTerminalSessionProvider({ children }: { children: ReactNod
 
   // セッションの作成
   // initialSizeが指定された場合、そのサイズでPTYを起動する（xterm初期化後に呼ぶ場合に使用）
-  const createSession = useCallback(async (cwd: string, claudeSessionId?: string, name?: string, initialSize?: { cols: number; rows: number }): Promise<string> => {
+  // spawnOptions.cleanEnvが指定された場合、ユーザーのrcを読み込まないクリーンなシェルで起動する
+  // spawnOptions.lineModeが指定された場合、出力を行単位でも届ける（pty:line イベント）
+  // spawnOptions.timestampOutputが指定された場合、出力を受信時刻付きでも届ける（pty:output イベント）
+  const createSession = useCallback(async (cwd: string, claudeSessionId?: string, name?: string, initialSize?: { cols: number; rows: number }, spawnOptions?: { cleanEnv?: boolean; lineMode?: boolean; timestampOutput?: boolean }): Promise<string> => {
     const sessionId = generateSessionId()
 
     // 初期サイズが指定されていない場合はデフォルト値を使用
@@ -268,8 +338,8 @@ export function ClaudeTerminalSessionProvider({ children }: { children: ReactNod
     try {
       // PTYを起動（指定されたサイズ、または デフォルトサイズで起動）
       const ptySession = claudeSessionId
-        ? await resumeClaudeTerminal(claudeSessionId, { cwd, cols, rows })
-        : await spawnClaudeTerminal({ cwd, cols, rows })
+        ? await resumeClaudeTerminal(claudeSessionId, { cwd, cols, rows, cleanEnv: spawnOptions?.cleanEnv, lineMode: spawnOptions?.lineMode, timestampOutput: spawnOptions?.timestampOutput })
+        : await spawnClaudeTerminal({ cwd, cols, rows, cleanEnv: spawnOptions?.cleanEnv, lineMode: spawnOptions?.lineMode, timestampOutput: spawnOptions?.timestampOutput })
 
       // onDataリスナーを設定
       const disposer = ptySession.onData((data) => {
@@ -291,7 +361,10 @@ export function ClaudeTerminalSessionProvider({ children }: { children: ReactNod
         setSessions(updatedMap)
       }
 
+      // 新しいタブに切り替える（購読者はアタッチ時にバッファを読むので再送は要らない）
       setActiveSessionId(sessionId)
+      activePtyIdRef.current = sessionId
+      pendingRedrawRef.current.delete(sessionId)
       return sessionId
     } catch (err) {
       const errorMessage = err instanceof Error ? err.message : String(err)
@@ -374,6 +447,10 @@ export function ClaudeTerminalSessionProvider({ children }: { children: ReactNod
 
     // バッファをクリア
     sessionBuffersRef.current.delete(sessionId)
+    pendingRedrawRef.current.delete(sessionId)
+    if (activePtyIdRef.current === sessionId) {
+      activePtyIdRef.current = null
+    }
 
     // ステータス更新追跡もクリア
     lastStatusUpdateRef.current.delete(sessionId)
@@ -408,6 +485,27 @@ export function ClaudeTerminalSessionProvider({ children }: { children: ReactNod
     }
   }, [])
 
+  // 複数行の入力が行ごとに実行されないよう、bracketed paste で囲んで書き込む
+  const safeWriteToSession = useCallback((sessionId: string, data: string, options?: SafeWriteOptions) => {
+    const session = sessionsRef.current.get(sessionId)
+    if (session?.pty) {
+      session.pty.safeWrite(data, options)
+    }
+  }, [])
+
+  // 音声認識の途中結果の仮表示
+  const previewSpeechInSession = useCallback((sessionId: string, text: string) => {
+    sessionsRef.current.get(sessionId)?.pty?.previewSpeech(text)
+  }, [])
+
+  const commitSpeechInSession = useCallback((sessionId: string, text: string) => {
+    sessionsRef.current.get(sessionId)?.pty?.commitSpeech(text)
+  }, [])
+
+  const cancelSpeechInSession = useCallback((sessionId: string) => {
+    sessionsRef.current.get(sessionId)?.pty?.cancelSpeech()
+  }, [])
+
   // セッションのリサイズ（refを使って常に最新のsessionsを参照）
   const resizeSession = useCallback((sessionId: string, cols: number, rows: number) => {
     const session = sessionsRef.current.get(sessionId)
@@ -419,8 +517,13 @@ export function ClaudeTerminalSessionProvider({ children }: { children: ReactNod
   // セッション出力の取得（Refからバッファを取得 - パフォーマンス最適化）
   const getSessionOutput = useCallback((sessionId: string): string[] => {
     // Refから取得（stateよりも高速）
-    const buffer = sessionBuffersRef.current.get(sessionId)
-    return buffer?.chunks ?? []
+    return bufferedOutput(sessionBuffersRef.current.get(sessionId))
+  }, [])
+
+  // 入力履歴の取得
+  const getPtyInputHistory = useCallback((sessionId: string): string[] => {
+    const session = sessionsRef.current.get(sessionId)
+    return session?.pty?.getInputHistory() ?? []
   }, [])
 
   // 出力の購読
@@ -438,6 +541,84 @@ export function ClaudeTerminalSessionProvider({ children }: { children: ReactNod
     }
   }, [])
 
+  // バッファの再送（RIS で画面とスクロールバックをリセットしてから全体を書き直す）
+  const requestPtyRedraw = useCallback((sessionId: string) => {
+    pendingRedrawRef.current.delete(sessionId)
+    const buffer = sessionBuffersRef.current.get(sessionId)
+    const subscribers = outputSubscribersRef.current.get(sessionId)
+    if (!subscribers) return
+
+    const data = '\x1bc' + bufferedOutput(buffer).join('')
+    subscribers.forEach((callback) => callback(data))
+  }, [])
+
+  // 出力を流すセッションの切り替え
+  const setActivePty = useCallback((sessionId: string | null) => {
+    activePtyIdRef.current = sessionId
+
+    // 流していなかった間の出力を再送する（null に戻した場合は溜まっていた全セッション）
+    const targets = sessionId === null ? Array.from(pendingRedrawRef.current) : [sessionId]
+    for (const id of targets) {
+      if (pendingRedrawRef.current.has(id)) {
+        requestPtyRedraw(id)
+      }
+    }
+  }, [requestPtyRedraw])
+
+  // タブの切り替え。表示するセッションの出力だけを流し、溜まっていた出力を再送する
+  const setActiveSession = useCallback((sessionId: string | null) => {
+    setActiveSessionId(sessionId)
+    setActivePty(sessionId)
+  }, [setActivePty])
+
+  // 閉じていたメインウィンドウが再度開かれたら、動き続けていたセッションの画面を書き直す
+  useEffect(() => {
+    const unlisten = onWindowReopened(() => {
+      sessionsRef.current.forEach((session, sessionId) => {
+        if (session.status !== 'stopped') {
+          requestPtyRedraw(sessionId)
+        }
+      })
+    })
+    return () => {
+      unlisten.then((fn) => fn())
+    }
+  }, [requestPtyRedraw])
+
+  // バックエンドのコマンド（resume_claude_in_pty など）からの入力を該当する PTY に書き込む
+  useEffect(() => {
+    const unlisten = onPtyInput(({ pid, data }) => {
+      for (const session of sessionsRef.current.values()) {
+        if (session.pty?.pty.pid === pid) {
+          session.pty.write(data)
+          return
+        }
+      }
+    })
+    return () => {
+      unlisten.then((fn) => fn())
+    }
+  }, [])
+
+  // バックエンドの set_active_pty による切り替え（pid をセッションIDに読み替える）
+  useEffect(() => {
+    const unlisten = onPtyActiveChanged(({ pid }) => {
+      if (pid === null) {
+        setActivePty(null)
+        return
+      }
+      for (const [sessionId, session] of sessionsRef.current) {
+        if (session.pty?.pty.pid === pid) {
+          setActivePty(sessionId)
+          return
+        }
+      }
+    })
+    return () => {
+      unlisten.then((fn) => fn())
+    }
+  }, [setActivePty])
+
   const setDialogOpen = useCallback((open: boolean) => {
     setIsDialogOpen(open)
   }, [])
@@ -476,6 +657,25 @@ export function ClaudeTerminalSessionProvider({ children }: { children: ReactNod
     })
   }, [])
 
+  // 既存のPTYタブで claude --resume を実行する
+  const resumeClaudeInPty = useCallback(async (ptyId: string, claudeSessionId: string, cwd: string): Promise<void> => {
+    const session = sessionsRef.current.get(ptyId)
+    if (!session?.pty) {
+      throw new Error(`Terminal session not found: ${ptyId}`)
+    }
+
+    // sidechain チェックとエスケープは Rust 側で行い、コマンドは pty:input で届く
+    await resumeClaudeSessionInPty(session.pty.pty.pid, claudeSessionId, cwd)
+
+    setSessions((prev) => {
+      const current = prev.get(ptyId)
+      if (!current) return prev
+      const newMap = new Map(prev)
+      newMap.set(ptyId, { ...current, cwd, claudeSessionId })
+      return newMap
+    })
+  }, [])
+
   // セッションを別ウィンドウで開く
   const openInWindow = useCallback(async (sessionId: string): Promise<void> => {
     const session = sessionsRef.current.get(sessionId)
@@ -520,7 +720,7 @@ export function ClaudeTerminalSessionProvider({ children }: { children: ReactNod
         const buffer = sessionBuffersRef.current.get(sessionId)
         if (buffer && buffer.chunks.length > 0) {
           // 過去のバッファを一括送信
-          sendToTerminalWindow(sessionId, { type: 'buffer', data: buffer.chunks })
+          sendToTerminalWindow(sessionId, { type: 'buffer', data: bufferedOutput(buffer) })
         }
 
         // forwarderを登録（以降の出力をリアルタイムで転送）
@@ -646,10 +846,18 @@ export function ClaudeTerminalSessionProvider({ children }: { children: ReactNod
     resizeSession,
     updateSessionName,
     updateSessionClaudeId,
+    resumeClaudeInPty,
     writeToSession,
+    safeWriteToSession,
+    previewSpeechInSession,
+    commitSpeechInSession,
+    cancelSpeechInSession,
     getSessionOutput,
+    getPtyInputHistory,
     subscribeToOutput,
-    setActiveSession: setActiveSessionId,
+    setActivePty,
+    requestPtyRedraw,
+    setActiveSession,
     isDialogOpen,
     setDialogOpen,
     widgetExpanded,
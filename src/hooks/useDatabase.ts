@@ -2,7 +2,7 @@ import { useState, useEffect } from 'react'
 import Database from '@tauri-apps/plugin-sql'
 import { getDb } from '@/lib/database'
 import { getSettings } from '@/lib/settings'
-import { getCurrentWindow } from '@tauri-apps/api/window'
+import { invoke } from '@tauri-apps/api/core'
 
 export function useDatabase() {
   const [database, setDatabase] = useState<Database | null>(null)
@@ -16,10 +16,10 @@ export function useDatabase() {
     setDatabase(db)
 
     // 設定を読み込んでウィンドウに適用
+    // （最前面表示は起動時に Rust 側で適用済み。DB の設定と食い違っていれば合わせて保存する）
     try {
       const settings = await getSettings(db)
-      const window = getCurrentWindow()
-      await window.setAlwaysOnTop(settings.alwaysOnTop)
+      await invoke('set_always_on_top', { enabled: settings.alwaysOnTop })
     } catch (error) {
       console.error('設定の適用に失敗しました:', error)
     }
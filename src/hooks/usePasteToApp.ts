@@ -76,11 +76,15 @@ export function usePasteToApp() {
         return false
       }
 
+      if (result.truncated) {
+        setError(`テキストが長すぎるため切り詰めて送信しました（元の長さ: ${result.original_length} バイト）`)
+      }
+
       return true
     } catch (e) {
       const errorMessage = e instanceof Error ? e.message : String(e)
       setError(errorMessage)
-      setLastResult({ success: false, error: errorMessage })
+      setLastResult({ success: false, error: errorMessage, truncated: false, original_length: new TextEncoder().encode(text).length })
       return false
     } finally {
       setIsPasting(false)
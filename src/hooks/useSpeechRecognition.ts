@@ -1,11 +1,19 @@
 import { useState, useEffect, useCallback, useRef } from 'react'
 import { invoke } from '@tauri-apps/api/core'
 import { listen, UnlistenFn } from '@tauri-apps/api/event'
-import type { SpeechRecognitionState, RecognitionResult } from '@/types/speech'
+import { AppEvents } from '@/lib/events'
+import type { SpeechRecognitionState, SpeechStatePayload, RecognitionResult, SpeechDisabledPayload, HandsFreeState, HandsFreeStatePayload, SpeechProfile, WebSpeechResult, SpeechSentencePayload, SpeechSegmentBoundaryPayload, SpeechSession, SpeechHistoryFilter, DecorationRule, SleepPrevention } from '@/types/speech'
 
 interface UseSpeechRecognitionProps {
-  /** 認識結果を受け取るコールバック */
-  onResult?: (text: string, isFinal: boolean) => void
+  /**
+   * 認識結果を受け取るコールバック
+   * setDecoration で装飾した場合、plainText に装飾前のテキストが入る
+   */
+  onResult?: (text: string, isFinal: boolean, plainText?: string) => void
+  /** 確定テキストを文ごとに受け取るコールバック（setSentenceSplit で有効化） */
+  onSentence?: (sentence: SpeechSentencePayload) => void
+  /** 長いポーズで話者セグメントが区切られたときのコールバック */
+  onSegmentBoundary?: (boundary: SpeechSegmentBoundaryPayload) => void
   /** エラー発生時のコールバック */
   onError?: (error: string) => void
 }
@@ -23,47 +31,165 @@ interface UseSpeechRecognitionReturn {
   stopRecognition: () => Promise<void>
   /** 音声認識を開始/停止トグル */
   toggleRecognition: () => Promise<void>
+  /**
+   * マイク（オーディオエンジン）が実際に動いているか確認する
+   * 状態とずれていればバックエンドで補正され、speech:state-changed で state も更新される
+   */
+  checkListening: () => Promise<boolean>
+  /** 連続エラーで無効化された音声認識を再度有効にする */
+  resetErrors: () => Promise<void>
+  /** ハンズフリーモードの状態 */
+  handsFreeState: HandsFreeState
+  /** ハンズフリーモード（音量で自動開始/停止）を切り替える */
+  setHandsFree: (enabled: boolean) => Promise<void>
+  /** 定義済みのプロファイル一覧を取得 */
+  listProfiles: () => Promise<SpeechProfile[]>
+  /** プロファイルを適用 */
+  applyProfile: (name: string) => Promise<void>
+  /**
+   * 認識言語を変更（リスニング中なら進行中のテキストを確定してから切り替える）
+   * resume が false なら切替後に再開しない
+   */
+  setLanguage: (language: string, resume?: boolean) => Promise<void>
+  /**
+   * アプリの表示言語（ロケール）を通知する
+   * 追従が有効なら対応する認識言語に切り替え、切り替えた言語を返す（対応が無ければ現状維持で null）
+   */
+  setAppLocale: (locale: string) => Promise<string | null>
+  /** 認識言語をアプリの表示言語に追従させるかを切り替える（無効なら setLanguage の設定が優先） */
+  setFollowAppLocale: (enabled: boolean) => Promise<string | null>
+  /** 録音などの保存先ディレクトリを設定（null で既定の app_data_dir に戻す） */
+  setDataDir: (path: string | null) => Promise<void>
+  /**
+   * 確定テキストの文分割を切り替える
+   * delimiters を省略すると言語ごとの既定の区切り文字を使う
+   */
+  setSentenceSplit: (enabled: boolean, delimiters?: string) => Promise<void>
+  /**
+   * 文字数・発話時間（ms）がこれ未満の確定結果を誤認識として捨てる（0 で無効）
+   * 次に認識を開始したときから反映される
+   */
+  setMinUtterance: (minChars: number, minMs: number) => Promise<void>
+  /**
+   * 認識結果の間がこの時間（ms）以上空いたら話者セグメントを区切る（0 で区切らない）
+   * 次に認識を開始したときから反映される
+   */
+  setSegmentPause: (pauseMs: number) => Promise<void>
+  /**
+   * 部分結果を受け取る最小間隔（ms）を設定する。間隔内は最新の部分結果だけが届き、final は遅れない
+   * 0 ですべての部分結果を受け取る。次に認識を開始したときから反映される
+   */
+  setPartialDebounce: (debounceMs: number) => Promise<void>
+  /**
+   * 確定テキストの Markdown 装飾（「コードブロック」「引用」などのトリガー語）を切り替える
+   * rules を省略すると現在のトリガー語の設定のまま
+   */
+  setDecoration: (enabled: boolean, rules?: DecorationRule[]) => Promise<void>
+  /** 認識中に抑止するスリープの種類を設定（既定は system） */
+  setSleepPrevention: (kind: SleepPrevention) => Promise<void>
+  /** 認識セッションの履歴を新しい順に取得（言語・開始日で絞り込める） */
+  getHistory: (filter?: SpeechHistoryFilter) => Promise<SpeechSession[]>
+  /** ユーザー辞書の単語を登録順に取得 */
+  listDictionary: () => Promise<string[]>
+  /**
+   * ユーザー辞書に単語を追加する（次に認識を開始したときから認識されやすくなる）
+   * 追加後の一覧を返す（失敗したら null）
+   */
+  addDictionaryWord: (word: string) => Promise<string[] | null>
+  /** ユーザー辞書から単語を削除し、削除後の一覧を返す（失敗したら null） */
+  removeDictionaryWord: (word: string) => Promise<string[] | null>
 }
 
 export function useSpeechRecognition({
   onResult,
+  onSentence,
+  onSegmentBoundary,
   onError,
 }: UseSpeechRecognitionProps = {}): UseSpeechRecognitionReturn {
   const [state, setState] = useState<SpeechRecognitionState>('Idle')
   const [isAvailable, setIsAvailable] = useState(true)
+  const [handsFreeState, setHandsFreeState] = useState<HandsFreeState>('Off')
 
   // コールバックをrefで保持して最新の値を参照できるようにする
   const onResultRef = useRef(onResult)
+  const onSentenceRef = useRef(onSentence)
+  const onSegmentBoundaryRef = useRef(onSegmentBoundary)
   const onErrorRef = useRef(onError)
 
   useEffect(() => {
     onResultRef.current = onResult
+    onSentenceRef.current = onSentence
+    onSegmentBoundaryRef.current = onSegmentBoundary
     onErrorRef.current = onError
-  }, [onResult, onError])
+  }, [onResult, onSentence, onSegmentBoundary, onError])
 
   // 状態変更イベントと認識結果イベントをリッスン
   useEffect(() => {
     let unlistenState: UnlistenFn | undefined
     let unlistenResult: UnlistenFn | undefined
+    let unlistenSentence: UnlistenFn | undefined
+    let unlistenSegment: UnlistenFn | undefined
+    let unlistenDisabled: UnlistenFn | undefined
+    let unlistenHandsFree: UnlistenFn | undefined
 
     const setupListeners = async () => {
       // 状態変更イベント
-      unlistenState = await listen<SpeechRecognitionState>(
-        'speech-state-changed',
+      unlistenState = await listen<SpeechStatePayload>(
+        AppEvents.SpeechStateChanged,
         (event) => {
-          setState(event.payload)
+          setState(event.payload.state)
         }
       )
 
       // 認識結果イベント
-      unlistenResult = await listen<RecognitionResult>(
-        'speech-recognition-result',
+      // 形式設定により RecognitionResult か WebSpeechResult のどちらかが届く
+      unlistenResult = await listen<RecognitionResult | WebSpeechResult>(
+        AppEvents.SpeechResult,
         (event) => {
-          if (onResultRef.current) {
-            onResultRef.current(event.payload.text, event.payload.is_final)
+          if (!onResultRef.current) return
+          const payload = event.payload
+          if ('alternatives' in payload) {
+            onResultRef.current(payload.alternatives[0]?.transcript ?? '', payload.isFinal)
+          } else {
+            onResultRef.current(payload.text, payload.is_final, payload.plain_text)
           }
         }
       )
+
+      // 文分割イベント（確定時のみ）
+      unlistenSentence = await listen<SpeechSentencePayload>(
+        AppEvents.SpeechSentence,
+        (event) => {
+          onSentenceRef.current?.(event.payload)
+        }
+      )
+
+      // 話者セグメントの境界イベント
+      unlistenSegment = await listen<SpeechSegmentBoundaryPayload>(
+        AppEvents.SpeechSegmentBoundary,
+        (event) => {
+          onSegmentBoundaryRef.current?.(event.payload)
+        }
+      )
+
+      // 連続エラーによる自動無効化イベント
+      unlistenDisabled = await listen<SpeechDisabledPayload>(
+        AppEvents.SpeechDisabled,
+        (event) => {
+          setIsAvailable(false)
+          onErrorRef.current?.(
+            `音声認識でエラーが${event.payload.error_count}回続いたため無効化しました: ${event.payload.last_error}`
+          )
+        }
+      )
+
+      // ハンズフリーモードの状態変更イベント
+      unlistenHandsFree = await listen<HandsFreeStatePayload>(
+        AppEvents.SpeechHandsFreeStateChanged,
+        (event) => {
+          setHandsFreeState(event.payload.state)
+        }
+      )
     }
 
     setupListeners()
@@ -71,6 +197,10 @@ export function useSpeechRecognition({
     return () => {
       unlistenState?.()
       unlistenResult?.()
+      unlistenSentence?.()
+      unlistenSegment?.()
+      unlistenDisabled?.()
+      unlistenHandsFree?.()
     }
   }, [])
 
@@ -100,14 +230,232 @@ export function useSpeechRecognition({
     }
   }, [])
 
-  // トグル
+  // トグル（状態の判定はバックエンドで行い、フロントの state とのズレを避ける）
   const toggleRecognition = useCallback(async () => {
-    if (state === 'Listening') {
-      await stopRecognition()
-    } else {
-      await startRecognition()
+    try {
+      await invoke<boolean>('toggle_speech_recognition')
+    } catch (error) {
+      const errorMessage = error instanceof Error ? error.message : String(error)
+      onErrorRef.current?.(errorMessage)
+      setState('Error')
+
+      if (errorMessage.includes('not available')) {
+        setIsAvailable(false)
+      }
+    }
+  }, [])
+
+  // 実際のマイク状態を確認
+  const checkListening = useCallback(async (): Promise<boolean> => {
+    try {
+      return await invoke<boolean>('is_speech_actually_listening')
+    } catch (error) {
+      const errorMessage = error instanceof Error ? error.message : String(error)
+      onErrorRef.current?.(errorMessage)
+      return false
+    }
+  }, [])
+
+  // 連続エラーをリセットして再度有効化
+  const resetErrors = useCallback(async () => {
+    try {
+      await invoke('reset_speech_errors')
+      setIsAvailable(true)
+    } catch (error) {
+      const errorMessage = error instanceof Error ? error.message : String(error)
+      onErrorRef.current?.(errorMessage)
+    }
+  }, [])
+
+  // ハンズフリーモードの切り替え
+  const setHandsFree = useCallback(async (enabled: boolean) => {
+    try {
+      await invoke('set_speech_hands_free', { enabled })
+    } catch (error) {
+      const errorMessage = error instanceof Error ? error.message : String(error)
+      onErrorRef.current?.(errorMessage)
+    }
+  }, [])
+
+  // プロファイル一覧を取得
+  const listProfiles = useCallback(async (): Promise<SpeechProfile[]> => {
+    try {
+      return await invoke<SpeechProfile[]>('list_speech_profiles')
+    } catch (error) {
+      const errorMessage = error instanceof Error ? error.message : String(error)
+      onErrorRef.current?.(errorMessage)
+      return []
+    }
+  }, [])
+
+  // プロファイルを適用
+  const applyProfile = useCallback(async (name: string) => {
+    try {
+      await invoke('apply_speech_profile', { name })
+    } catch (error) {
+      const errorMessage = error instanceof Error ? error.message : String(error)
+      onErrorRef.current?.(errorMessage)
+    }
+  }, [])
+
+  // 認識言語を変更
+  const setLanguage = useCallback(async (language: string, resume = true) => {
+    try {
+      await invoke('set_speech_language', { language, resume })
+    } catch (error) {
+      const errorMessage = error instanceof Error ? error.message : String(error)
+      onErrorRef.current?.(errorMessage)
+    }
+  }, [])
+
+  // アプリロケールを通知
+  const setAppLocale = useCallback(async (locale: string): Promise<string | null> => {
+    try {
+      return await invoke<string | null>('set_speech_app_locale', { locale })
+    } catch (error) {
+      const errorMessage = error instanceof Error ? error.message : String(error)
+      onErrorRef.current?.(errorMessage)
+      return null
+    }
+  }, [])
+
+  // アプリにはロケール設定が無いので、WebView の表示言語（システムの言語設定）を通知する
+  // 追従の有効/無効に関わらず通知しておき、有効にしたときにすぐ合わせられるようにする
+  useEffect(() => {
+    const notifyLocale = () => {
+      setAppLocale(navigator.language)
+    }
+    notifyLocale()
+    window.addEventListener('languagechange', notifyLocale)
+    return () => window.removeEventListener('languagechange', notifyLocale)
+  }, [setAppLocale])
+
+  // アプリロケールへの追従を切り替え
+  const setFollowAppLocale = useCallback(async (enabled: boolean): Promise<string | null> => {
+    try {
+      return await invoke<string | null>('set_speech_follow_app_locale', { enabled })
+    } catch (error) {
+      const errorMessage = error instanceof Error ? error.message : String(error)
+      onErrorRef.current?.(errorMessage)
+      return null
     }
-  }, [state, startRecognition, stopRecognition])
+  }, [])
+
+  // データ保存先を設定
+  const setDataDir = useCallback(async (path: string | null) => {
+    try {
+      await invoke('set_speech_data_dir', { path })
+    } catch (error) {
+      const errorMessage = error instanceof Error ? error.message : String(error)
+      onErrorRef.current?.(errorMessage)
+    }
+  }, [])
+
+  // 文分割の切り替え
+  const setSentenceSplit = useCallback(async (enabled: boolean, delimiters?: string) => {
+    try {
+      await invoke('set_speech_sentence_split', { enabled, delimiters: delimiters ?? null })
+    } catch (error) {
+      const errorMessage = error instanceof Error ? error.message : String(error)
+      onErrorRef.current?.(errorMessage)
+    }
+  }, [])
+
+  // 短すぎる確定結果を捨てる基準を設定
+  const setMinUtterance = useCallback(async (minChars: number, minMs: number) => {
+    try {
+      await invoke('set_speech_min_utterance', { minChars, minMs })
+    } catch (error) {
+      const errorMessage = error instanceof Error ? error.message : String(error)
+      onErrorRef.current?.(errorMessage)
+    }
+  }, [])
+
+  // 話者セグメントを区切るポーズの長さを設定
+  const setSegmentPause = useCallback(async (pauseMs: number) => {
+    try {
+      await invoke('set_speech_segment_pause', { pauseMs })
+    } catch (error) {
+      const errorMessage = error instanceof Error ? error.message : String(error)
+      onErrorRef.current?.(errorMessage)
+    }
+  }, [])
+
+  // 部分結果の間引き
+  const setPartialDebounce = useCallback(async (debounceMs: number) => {
+    try {
+      await invoke('set_speech_partial_debounce', { debounceMs })
+    } catch (error) {
+      const errorMessage = error instanceof Error ? error.message : String(error)
+      onErrorRef.current?.(errorMessage)
+    }
+  }, [])
+
+  // Markdown 装飾の切り替え
+  const setDecoration = useCallback(async (enabled: boolean, rules?: DecorationRule[]) => {
+    try {
+      await invoke('set_speech_decoration', { enabled, rules: rules ?? null })
+    } catch (error) {
+      const errorMessage = error instanceof Error ? error.message : String(error)
+      onErrorRef.current?.(errorMessage)
+    }
+  }, [])
+
+  // スリープ抑止の種類を設定
+  const setSleepPrevention = useCallback(async (kind: SleepPrevention) => {
+    try {
+      await invoke('set_speech_sleep_prevention', { kind })
+    } catch (error) {
+      const errorMessage = error instanceof Error ? error.message : String(error)
+      onErrorRef.current?.(errorMessage)
+    }
+  }, [])
+
+  // 認識セッションの履歴を取得
+  const getHistory = useCallback(async (filter: SpeechHistoryFilter = {}): Promise<SpeechSession[]> => {
+    try {
+      return await invoke<SpeechSession[]>('get_speech_history', {
+        language: filter.language ?? null,
+        from: filter.from ?? null,
+        to: filter.to ?? null,
+      })
+    } catch (error) {
+      const errorMessage = error instanceof Error ? error.message : String(error)
+      onErrorRef.current?.(errorMessage)
+      return []
+    }
+  }, [])
+
+  // ユーザー辞書
+  const listDictionary = useCallback(async (): Promise<string[]> => {
+    try {
+      return await invoke<string[]>('list_speech_dictionary')
+    } catch (error) {
+      const errorMessage = error instanceof Error ? error.message : String(error)
+      onErrorRef.current?.(errorMessage)
+      return []
+    }
+  }, [])
+
+  const addDictionaryWord = useCallback(async (word: string): Promise<string[] | null> => {
+    try {
+      return await invoke<string[]>('add_speech_dictionary_word', { word })
+    } catch (error) {
+      const errorMessage = error instanceof Error ? error.message : String(error)
+      onErrorRef.current?.(errorMessage)
+      return null
+    }
+  }, [])
+
+  const removeDictionaryWord = useCallback(async (word: string): Promise<string[] | null> => {
+    try {
+      return await invoke<string[]>('remove_speech_dictionary_word', { word })
+    } catch (error) {
+      const errorMessage = error instanceof Error ? error.message : String(error)
+      onErrorRef.current?.(errorMessage)
+      return null
+    }
+  }, [])
 
   return {
     state,
@@ -116,5 +464,25 @@ export function useSpeechRecognition({
     startRecognition,
     stopRecognition,
     toggleRecognition,
+    checkListening,
+    resetErrors,
+    handsFreeState,
+    setHandsFree,
+    listProfiles,
+    applyProfile,
+    setLanguage,
+    setAppLocale,
+    setFollowAppLocale,
+    setDataDir,
+    setSentenceSplit,
+    setMinUtterance,
+    setSegmentPause,
+    setPartialDebounce,
+    setDecoration,
+    setSleepPrevention,
+    getHistory,
+    listDictionary,
+    addDictionaryWord,
+    removeDictionaryWord,
   }
 }
@@ -0,0 +1,74 @@
+import { invoke } from '@tauri-apps/api/core'
+import type { SpeechConfig, SpeechRecognitionState } from '@/types/speech'
+import type { ScreenEdge } from './settings'
+
+/** 自動で隠す（autohide）設定 */
+export interface AutohideConfig {
+  enabled: boolean
+  edge: ScreenEdge
+  /** 隠したときに見えている幅（px） */
+  visible_pixels: number
+  animation_duration_ms: number
+  /** スライドのイージング */
+  easing: 'linear' | 'ease_in' | 'ease_out' | 'ease_in_out'
+  /** 表示時にフォーカスを奪う */
+  steal_focus_on_show: boolean
+  /** 画面端にカーソルを当てると表示する */
+  hover_reveal: boolean
+  /** ホバーの判定をハンドルの高さに限定する */
+  trigger_on_handle_only: boolean
+  /** ホバーの判定に使う画面端の帯の太さ（pt） */
+  trigger_zone_pixels: number
+  /** カーソルが画面端に留まってから表示するまでの時間（ms） */
+  reveal_delay_ms: number
+  /** ホバーで表示したウィンドウからカーソルが離れて隠すまでの時間（ms） */
+  auto_hide_delay_ms: number
+  /** ディスプレイ配置から画面端を自動で選ぶ */
+  auto_edge: boolean
+  /** フォーカスを失ったら隠す */
+  hide_on_blur: boolean
+  /** 画面端ごとに最後に使ったウィンドウの y（ディスプレイ上端からの距離） */
+  edge_y: Partial<Record<ScreenEdge, number>>
+  /** 左右の端で画面の高さのうちサイドバーが占める割合（1 ならウィンドウの高さのまま） */
+  height_fraction: number
+  /** 高さが 1 未満のときの配置（上寄せ・中央） */
+  vertical_anchor: 'top' | 'center'
+  /** ウィンドウを常に最前面に表示する */
+  always_on_top: boolean
+  /** 画面端側の角を四角くして隙間なく貼り付いて見せる（autohide 有効時のみ） */
+  dock_appearance: boolean
+  /** 貼り付けている間もウィンドウの影を残す */
+  dock_shadow: boolean
+  /** サイドバーの上に空ける余白（pt） */
+  top_margin: number
+  /** サイドバーの下に空ける余白（pt） */
+  bottom_margin: number
+  /** ドラッグで変えたサイドバーの幅（pt、null なら初期の幅） */
+  sidebar_width: number | null
+  /** サイドバーを固定表示中（自動で隠れない。実行中だけの状態で、設定ファイルには保存されない） */
+  pinned: boolean
+}
+
+/** 各サブシステムの現在の状態 */
+export interface AppStatus {
+  autohide: {
+    config: AutohideConfig
+    /** サイドバーが表示中か（画面端に隠れていないか） */
+    visible: boolean
+  }
+  speech: {
+    state: SpeechRecognitionState
+    config: SpeechConfig
+    /** 連続エラーで自動的に無効化されているか（reset_speech_errors で解除） */
+    disabled_by_errors: boolean
+  }
+  /** 実行中の PTY シェルの PID（PtySessions に登録されたもの） */
+  terminal_sessions: number[]
+  /** autohide のイベント監視に必要なアクセシビリティ権限があるか */
+  accessibility_trusted: boolean
+}
+
+// 起動時の初期化用に、各サブシステムの状態をまとめて取得する
+export async function getAppStatus(): Promise<AppStatus> {
+  return invoke<AppStatus>('get_app_status')
+}
@@ -1,11 +1,14 @@
 import { invoke } from '@tauri-apps/api/core'
 import { listen, UnlistenFn } from '@tauri-apps/api/event'
+import { AppEvents, LEGACY_EVENT_NAMES } from './events'
 
 export interface ProjectInfo {
   name: string
   path: string
   session_count: number
   last_updated: string | null
+  /** cwd のファイル（Cargo.toml・package.json など）から推定した主要言語（rust・typescript など） */
+  project_type: string | null
 }
 
 export interface SessionSummary {
@@ -13,15 +16,67 @@ export interface SessionSummary {
   project_path: string
   cwd: string | null
   git_branch: string | null
+  /** cwd がログに無く、同じプロジェクトの他セッションやディレクトリ名から推定した値か */
+  cwd_estimated: boolean
+  /** git_branch がログに無く、時刻の近い他セッションから流用した値か */
+  git_branch_estimated: boolean
   first_message: string | null
   timestamp: string | null
+  /** timestamp をローカルタイムゾーンで整形した表示用文字列（例: 2025-01-15 18:30:00 +09:00） */
+  timestamp_local: string | null
   message_count: number
+  /** セッションファイルのサイズ（バイト） */
+  file_size_bytes: number
+  /** セッションファイルの行数 */
+  line_count: number
+  /** API エラーなどで明確にエラー終了したセッションか */
+  ended_with_error: boolean
 }
 
+/** 日付バケット（今日・昨日・今週・それ以前・不明）ごとのセッション */
+export interface SessionGroup {
+  label: string
+  sessions: SessionSummary[]
+}
+
+/** 移動したプロジェクトの一致の仕方（ディレクトリ末尾名 / git リポジトリ名） */
+export type ProjectMatch = 'directory_name' | 'repository_name'
+
+/** cwd が移動・リネームされた場合に対応していそうな Claude プロジェクト */
+export interface ProjectCandidate {
+  /** Claude のプロジェクトディレクトリ（projectPath としてそのまま渡せる） */
+  project_dir: string
+  /** セッションに記録された cwd */
+  cwd: string | null
+  matched_by: ProjectMatch
+  /** 記録された cwd が今も存在するか（存在しなければ移動した可能性が高い） */
+  cwd_exists: boolean
+  session_count: number
+  last_updated: string | null
+}
+
+/** メッセージの種類（Thinking は拡張思考ブロック） */
+export type MessageKind = 'Text' | 'Thinking'
+
 export interface ConversationMessage {
   role: string
   content: string
   timestamp: string
+  /** timestamp をローカルタイムゾーンで整形した表示用文字列 */
+  timestamp_local: string | null
+  kind: MessageKind
+  /** セッション内の時系列順の位置（逆順やページングで取得しても変わらない） */
+  index: number
+}
+
+/** readClaudeSession の逆順・ページング指定 */
+export interface ReadSessionPaging {
+  /** 最新のメッセージを先頭にする */
+  reverse?: boolean
+  /** 返す順序での読み飛ばし件数 */
+  offset?: number
+  /** 最大件数（reverse と組み合わせると最新 N 件） */
+  limit?: number
 }
 
 export async function listClaudeProjects(): Promise<ProjectInfo[]> {
@@ -32,11 +87,88 @@ export async function listClaudeSessions(projectPath: string): Promise<SessionSu
   return invoke<SessionSummary[]>('list_claude_sessions', { projectPath })
 }
 
+/**
+ * cwd が移動・リネームされてセッションが見つからないときに、対応していそうなプロジェクトを探す
+ * listClaudeSessions は候補が1つなら自動で使い、複数ならエラーになるので、ここで選ばせる
+ */
+export async function findClaudeProjectCandidates(cwd: string): Promise<ProjectCandidate[]> {
+  return invoke<ProjectCandidate[]>('find_claude_project_candidates', { cwd })
+}
+
+export async function listClaudeSessionsGrouped(projectPath: string): Promise<SessionGroup[]> {
+  return invoke<SessionGroup[]>('list_claude_sessions_grouped', { projectPath })
+}
+
+// includeThinkingがtrueのときだけ思考ブロックも返す（長いものは切り詰め済み）
+// mergeConsecutiveがtrueなら同じ話者・種類の連続したメッセージを1つにまとめる
+// pagingで逆順（最新が先頭）や件数の指定ができる（index は時系列順のまま）
 export async function readClaudeSession(
   projectPath: string,
-  sessionId: string
+  sessionId: string,
+  includeThinking = false,
+  mergeConsecutive = false,
+  paging: ReadSessionPaging = {}
 ): Promise<ConversationMessage[]> {
-  return invoke<ConversationMessage[]>('read_claude_session', { projectPath, sessionId })
+  return invoke<ConversationMessage[]>('read_claude_session', {
+    projectPath,
+    sessionId,
+    includeThinking,
+    mergeConsecutive,
+    reverse: paging.reverse ?? false,
+    offset: paging.offset ?? null,
+    limit: paging.limit ?? null,
+  })
+}
+
+export interface ClaudeMessagePayload {
+  stream_id: string
+  index: number
+  message: ConversationMessage
+}
+
+export interface ClaudeReadCompletePayload {
+  stream_id: string
+  message_count: number
+  cancelled: boolean
+  error: string | null
+}
+
+// パースしたそばから claude:message イベントで送る（大きなセッションを逐次表示する用）
+// streamId は呼び出し側で決める（invoke が返る前に届くイベントも区別できるよう、
+// 先に onClaudeMessage で購読してから呼ぶ）。同じIDで cancelClaudeSessionStream から中断できる
+export async function streamClaudeSession(
+  streamId: string,
+  projectPath: string,
+  sessionId: string,
+  includeThinking = false
+): Promise<void> {
+  return invoke<void>('stream_claude_session', {
+    streamId,
+    projectPath,
+    sessionId,
+    includeThinking,
+  })
+}
+
+// 既に終了していた場合は false
+export async function cancelClaudeSessionStream(streamId: string): Promise<boolean> {
+  return invoke<boolean>('cancel_claude_session_stream', { streamId })
+}
+
+export function onClaudeMessage(
+  callback: (payload: ClaudeMessagePayload) => void
+): Promise<UnlistenFn> {
+  return listen<ClaudeMessagePayload>(AppEvents.ClaudeMessage, (event) => {
+    callback(event.payload)
+  })
+}
+
+export function onClaudeReadComplete(
+  callback: (payload: ClaudeReadCompletePayload) => void
+): Promise<UnlistenFn> {
+  return listen<ClaudeReadCompletePayload>(AppEvents.ClaudeReadComplete, (event) => {
+    callback(event.payload)
+  })
 }
 
 export async function launchClaudeCode(cwd: string, prompt?: string): Promise<string> {
@@ -51,6 +183,159 @@ export async function resumeClaudeCode(
   return invoke<void>('resume_claude_code', { sessionId, cwd, prompt })
 }
 
+/**
+ * アプリ内のPTYタブ（プロセスID ptyPid）でセッションを再開する
+ * コマンドは pty:input イベントで届き、そのタブに書き込まれる（sidechain セッションや不正なIDはエラーになる）
+ */
+export async function resumeClaudeSessionInPty(ptyPid: number, sessionId: string, cwd: string): Promise<void> {
+  return invoke<void>('resume_claude_in_pty', { ptyId: ptyPid, sessionId, cwd })
+}
+
+/** exportClaudeSession の機密情報の除外オプション（共有用） */
+export interface RedactOptions {
+  /** [REDACTED] に置き換える正規表現 */
+  patterns?: string[]
+  /** system-reminder やコマンドのラッパーなど、Claude Code が挿入したテキストを除く（既定は true） */
+  excludeSystem?: boolean
+  /** API キーやトークン、秘密情報らしき環境変数の値を自動で検出して置き換える（既定は true） */
+  detectApiKeys?: boolean
+}
+
+/** exportClaudeSession の結果 */
+export interface SessionExport {
+  /** 書き出したファイルのパス */
+  path: string
+  /** [REDACTED] に置き換えた件数 */
+  redacted_count: number
+  /** system のテキストとして除いたメッセージ数 */
+  excluded_count: number
+}
+
+/**
+ * セッションを Markdown でエクスポートする
+ * outputPath を省略するとデフォルトのエクスポート先に日時とタイトルからファイル名を付けて保存する
+ * redact を指定すると、共有前に機密情報を [REDACTED] に置き換える
+ */
+export async function exportClaudeSession(
+  projectPath: string,
+  sessionId: string,
+  outputPath?: string,
+  redact?: RedactOptions
+): Promise<SessionExport> {
+  return invoke<SessionExport>('export_claude_session', {
+    projectPath,
+    sessionId,
+    outputPath,
+    redact: redact !== undefined,
+    redactPatterns: redact?.patterns,
+    excludeSystem: redact?.excludeSystem,
+    detectApiKeys: redact?.detectApiKeys,
+  })
+}
+
+/**
+ * セッションを単体で表示できる HTML でエクスポートし、書き出したパスを返す
+ * CSS と画像は埋め込まれ、印刷用のスタイルも含むので PDF への変換にも使える
+ * outputPath の扱いは exportClaudeSession と同じ
+ */
+export async function exportClaudeSessionHtml(
+  projectPath: string,
+  sessionId: string,
+  outputPath?: string
+): Promise<string> {
+  return invoke<string>('export_claude_session_html', { projectPath, sessionId, outputPath })
+}
+
+/** getClaudeSessionText のオプション */
+export interface SessionTextOptions {
+  /** ツールの呼び出しと結果も含める（既定は false） */
+  includeToolOutput?: boolean
+  /** メッセージ・ツール出力ごとの最大文字数（超えた分は切り詰める） */
+  maxCharsPerPart?: number
+}
+
+/**
+ * セッション全体を role ラベル付きのプレーンテキストで取得する（別の LLM への入力用）
+ */
+export async function getClaudeSessionText(
+  projectPath: string,
+  sessionId: string,
+  options: SessionTextOptions = {}
+): Promise<string> {
+  return invoke<string>('get_claude_session_text', {
+    projectPath,
+    sessionId,
+    includeToolOutput: options.includeToolOutput,
+    maxCharsPerPart: options.maxCharsPerPart,
+  })
+}
+
+/** セッションで変更されたファイル（ファイルごとに最後の操作で分類） */
+export interface SessionEditedFiles {
+  /** セッション中に新しく作られたファイル */
+  created: string[]
+  edited: string[]
+  /** Bash の rm / git rm で削除されたファイル */
+  deleted: string[]
+}
+
+/**
+ * セッションが作成・編集・削除したファイルの一覧を取得する
+ * Write / Edit などのツール呼び出しから集める（エラーになった呼び出しは除く）
+ */
+export async function getSessionEditedFiles(
+  projectPath: string,
+  sessionId: string
+): Promise<SessionEditedFiles> {
+  return invoke<SessionEditedFiles>('get_session_edited_files', { projectPath, sessionId })
+}
+
+/** デフォルトのエクスポート先を設定（null でダウンロードフォルダに戻す） */
+export async function setExportDir(path: string | null): Promise<void> {
+  return invoke<void>('set_export_dir', { path })
+}
+
+/** デフォルトのエクスポート先を取得 */
+export async function getExportDir(): Promise<string> {
+  return invoke<string>('get_export_dir')
+}
+
+export interface ClaudeSearchHit {
+  project_path: string
+  session_id: string
+  role: string
+  snippet: string
+  timestamp: string
+  timestamp_local: string | null
+}
+
+// 全セッションのメッセージを部分一致（大文字小文字を区別しない）で検索
+// projectPath を指定するとそのプロジェクト内だけを検索する
+export async function searchClaudeLogs(
+  query: string,
+  projectPath?: string,
+  limit?: number
+): Promise<ClaudeSearchHit[]> {
+  return invoke<ClaudeSearchHit[]>('search_claude_logs', { query, projectPath, limit })
+}
+
+// 検索インデックスを作り直す（起動時にも自動で構築される）。戻り値はインデックス済みのセッション数
+export async function buildClaudeSearchIndex(): Promise<number> {
+  return invoke<number>('build_claude_search_index')
+}
+
+export interface FoundSession {
+  /** セッションを含む Claude のプロジェクトディレクトリ */
+  project_path: string
+  session: SessionSummary
+}
+
+// セッション ID だけからプロジェクトを逆引きする（検索インデックスがあれば利用する）
+// 見つからない場合と、複数のプロジェクトで見つかった場合（該当プロジェクトを列挙）はエラーになる
+export async function findSession(sessionId: string): Promise<FoundSession> {
+  return invoke<FoundSession>('find_session', { sessionId })
+}
+
 // Event types
 export interface ClaudeSessionFinishedPayload {
   session_id: string
@@ -59,10 +344,46 @@ export interface ClaudeSessionFinishedPayload {
 }
 
 // Listen for Claude session finished events
-export function onClaudeSessionFinished(
+// このイベントはアプリの外から送られ、送り手はまだ旧名（claude-session-finished）を使っている。
+// 移行が終わるまで新旧両方の名前を購読する（アプリ内から emitAppEvent で送ると2回届くので送らない）
+export async function onClaudeSessionFinished(
   callback: (payload: ClaudeSessionFinishedPayload) => void
 ): Promise<UnlistenFn> {
-  return listen<ClaudeSessionFinishedPayload>('claude-session-finished', (event) => {
+  const handler = (event: { payload: ClaudeSessionFinishedPayload }) => callback(event.payload)
+  const unlistenNew = await listen<ClaudeSessionFinishedPayload>(AppEvents.ClaudeSessionFinished, handler)
+  const legacy = LEGACY_EVENT_NAMES[AppEvents.ClaudeSessionFinished]
+  if (!legacy) return unlistenNew
+  try {
+    const unlistenLegacy = await listen<ClaudeSessionFinishedPayload>(legacy, handler)
+    return () => {
+      unlistenNew()
+      unlistenLegacy()
+    }
+  } catch (error) {
+    unlistenNew()
+    throw error
+  }
+}
+
+export interface ClaudeSessionUpdatedPayload {
+  project_path: string
+  session_id: string
+}
+
+// セッションファイルの更新をポーリングで監視（intervalMsが短いほどバッテリー消費が増える）
+export async function startClaudePolling(intervalMs: number): Promise<void> {
+  return invoke<void>('start_claude_polling', { intervalMs })
+}
+
+export async function stopClaudePolling(): Promise<void> {
+  return invoke<void>('stop_claude_polling')
+}
+
+// Listen for Claude session updated events (polling mode)
+export function onClaudeSessionUpdated(
+  callback: (payload: ClaudeSessionUpdatedPayload) => void
+): Promise<UnlistenFn> {
+  return listen<ClaudeSessionUpdatedPayload>(AppEvents.ClaudeSessionUpdated, (event) => {
     callback(event.payload)
   })
 }
@@ -113,3 +434,39 @@ export async function listSessionsForCwd(cwd: string): Promise<SessionSummary[]>
 export async function getLatestSessionForCwd(cwd: string): Promise<SessionSummary | null> {
   return invoke<SessionSummary | null>('get_latest_session_for_cwd', { cwd })
 }
+
+export interface SessionCleanupResult {
+  /** zip に書き出したセッション数 */
+  archived_count: number
+  /** ゴミ箱へ移動したセッション数 */
+  deleted_count: number
+  /** 作成した zip のパス（セッションが無いなど zip を作らなかった場合は null） */
+  output_zip: string | null
+}
+
+// プロジェクトの全セッションをzipにまとめる（deleteAfterで元ファイルをゴミ箱へ）
+export async function archiveProjectSessions(
+  projectPath: string,
+  outputZip: string,
+  options: { includeSidechain?: boolean; deleteAfter?: boolean } = {}
+): Promise<SessionCleanupResult> {
+  return invoke<SessionCleanupResult>('archive_project_sessions', {
+    projectPath,
+    outputZip,
+    includeSidechain: options.includeSidechain ?? false,
+    deleteAfter: options.deleteAfter ?? false,
+  })
+}
+
+// プロジェクトの全セッションをゴミ箱へ移動（requireBackupならbackupZipへの書き出しが必須）
+export async function deleteProjectSessions(
+  projectPath: string,
+  options: { includeSidechain?: boolean; requireBackup?: boolean; backupZip?: string } = {}
+): Promise<SessionCleanupResult> {
+  return invoke<SessionCleanupResult>('delete_project_sessions', {
+    projectPath,
+    includeSidechain: options.includeSidechain ?? false,
+    requireBackup: options.requireBackup ?? true,
+    backupZip: options.backupZip,
+  })
+}
@@ -1,11 +1,88 @@
+import { invoke } from '@tauri-apps/api/core'
+import { listen, type UnlistenFn } from '@tauri-apps/api/event'
+import { homeDir } from '@tauri-apps/api/path'
 import { spawn, type IPty, type IDisposable } from 'tauri-pty'
+import { AppEvents, emitAppEvent } from './events'
 
 export interface TerminalOptions {
   cwd: string
   cols?: number
   rows?: number
+  /** 起動するシェル（省略時は /bin/zsh） */
+  shell?: string
+  /** ユーザーの rc を読み込まず、最小限の環境変数でシェルを起動する */
+  cleanEnv?: boolean
+  /**
+   * 出力を行単位でも届ける（onLine と `pty:line` イベント）
+   * 生の出力（onData）はターミナル表示に必要なので、行モードでも併せて届く
+   */
+  lineMode?: boolean
+  /**
+   * 出力チャンクに受信時刻を付けて届ける（onOutput と `pty:output` イベント）
+   * onData には従来通り文字列のまま届く
+   */
+  timestampOutput?: boolean
 }
 
+/** `pty:line` イベントのペイロード */
+export interface PtyLinePayload {
+  pid: number
+  /** 改行を除いた1行 */
+  line: string
+  /** 上限を超えたため途中で区切った行 */
+  truncated: boolean
+}
+
+/** `pty:output` イベントのペイロード */
+export interface PtyOutputPayload {
+  pid: number
+  /** 出力チャンク（onData に届くものと同じ） */
+  data: string
+  /** 受信時刻（Unix エポックからのミリ秒） */
+  timestamp: number
+}
+
+/** `pty:input` イベントのペイロード（バックエンドから PTY への書き込みの依頼） */
+export interface PtyInputPayload {
+  pid: number
+  /** PTY にそのまま書き込む入力 */
+  data: string
+}
+
+/**
+ * バックエンドからの PTY への書き込みを購読する
+ * PTY に書き込めるのはフロントだけなので、バックエンドのコマンドはこのイベントで入力を送ってくる
+ */
+export function onPtyInput(callback: (payload: PtyInputPayload) => void): Promise<UnlistenFn> {
+  return listen<PtyInputPayload>(AppEvents.PtyInput, (event) => {
+    callback(event.payload)
+  })
+}
+
+export interface PtyActivePayload {
+  /** 出力を流す PTY（null なら全て） */
+  pid: number | null
+}
+
+/** バックエンドの set_active_pty による、出力を流す PTY の切り替えを購読する */
+export function onPtyActiveChanged(callback: (payload: PtyActivePayload) => void): Promise<UnlistenFn> {
+  return listen<PtyActivePayload>(AppEvents.PtyActiveChanged, (event) => {
+    callback(event.payload)
+  })
+}
+
+const DEFAULT_SHELL = '/bin/zsh'
+
+// クリーン環境でも claude などのコマンドが見つかるよう最低限通す PATH
+const CLEAN_PATH_DIRS = [
+  '/opt/homebrew/bin',
+  '/usr/local/bin',
+  '/usr/bin',
+  '/bin',
+  '/usr/sbin',
+  '/sbin',
+]
+
 // PTY用の環境変数を設定（Finderからダブルクリックで起動した場合でも色が出るようにする）
 // 注意: ブラウザ環境なのでprocess.envは使用できない。最低限必要な環境変数のみ設定。
 const PTY_ENV: Record<string, string> = {
@@ -15,12 +92,463 @@ const PTY_ENV: Record<string, string> = {
   SHELL: '/bin/zsh',
 }
 
+// bracketed paste の囲みシーケンスと、子プロセスがモードを切り替えるシーケンス
+const BRACKETED_PASTE_START = '\x1b[200~'
+const BRACKETED_PASTE_END = '\x1b[201~'
+const BRACKETED_PASTE_ENABLE = '\x1b[?2004h'
+const BRACKETED_PASTE_DISABLE = '\x1b[?2004l'
+
+// 1行の上限（改行が来ないまま超えた場合はそこで区切る）
+const MAX_LINE_LENGTH = 16 * 1024
+
+// 入力履歴の上限（超えたら古いものから捨てる）
+const MAX_INPUT_HISTORY = 500
+
+// パスワード入力を促すプロンプト（出力の最終行がこれに一致するときの入力は記録しない）
+const PASSWORD_PROMPT_PATTERN = /(password|passphrase|パスワード)[^\n]*[:：]\s*$/i
+
+// 出力の最終行として保持する長さ
+const MAX_PROMPT_LENGTH = 256
+
+// プロンプト判定の前に取り除く CSI シーケンス
+// eslint-disable-next-line no-control-regex
+const CSI_PATTERN = /\x1b\[[0-9;?]*[ -/]*[@-~]/g
+
+export interface SafeWriteOptions {
+  /** 子プロセスの対応状況に関わらず bracketed paste で囲む */
+  forceBracketedPaste?: boolean
+}
+
 export interface ClaudeTerminalSession {
   pty: IPty
   write: (data: string) => void
+  /**
+   * 改行を含む入力を、子プロセスが対応していれば bracketed paste で囲んで書き込む
+   * PTY への書き込み口はフロントの tauri-plugin-pty にしかないため、バックエンドのコマンドではなくここで囲む
+   */
+  safeWrite: (data: string, options?: SafeWriteOptions) => void
+  /** 子プロセスが bracketed paste モードを有効にしているか */
+  isBracketedPasteEnabled: () => boolean
   resize: (cols: number, rows: number) => void
   kill: () => void
   onData: (callback: (data: string) => void) => IDisposable
+  /** 行単位の出力を購読する（lineMode で起動した場合のみ届く） */
+  onLine: (callback: (line: string, truncated: boolean) => void) => IDisposable
+  /** 受信時刻付きの出力を購読する（timestampOutput で起動した場合のみ届く） */
+  onOutput: (callback: (output: PtyOutputPayload) => void) => IDisposable
+  /**
+   * write / safeWrite で改行まで入力された行の履歴（古い順、シェルの history とは別）
+   * echo off 中やパスワードのプロンプトに対する入力は記録しない
+   * 履歴はフロントエンドだけが保持する（入力を書き込むのがフロントエンドなので、
+   * バックエンドのコマンドからは取得できない）
+   */
+  getInputHistory: () => string[]
+  /**
+   * 音声認識の確定前の結果を入力行に仮表示する（前回の仮表示を置き換える）
+   * 仮表示中にキー入力があると仮表示を消し、その発話の残りの結果は無視する
+   */
+  previewSpeech: (text: string) => void
+  /** 仮表示を確定した認識結果に置き換える（Enter は送らない） */
+  commitSpeech: (text: string) => void
+  /** 仮表示を消す */
+  cancelSpeech: () => void
+}
+
+/**
+ * チャンク単位の出力を行に分割する
+ * 改行で終わっていない末尾は次のチャンクまで保持する
+ */
+export class LineSplitter {
+  private buffer = ''
+
+  constructor(private readonly maxLineLength = MAX_LINE_LENGTH) {}
+
+  /** チャンクを追加し、完成した行を返す */
+  push(data: string): { line: string; truncated: boolean }[] {
+    const lines: { line: string; truncated: boolean }[] = []
+    const parts = (this.buffer + data).split('\n')
+    this.buffer = parts.pop() ?? ''
+
+    for (const part of parts) {
+      lines.push(...this.splitLong(part.replace(/\r$/, '')))
+    }
+
+    // 改行が来ないまま長くなった場合は上限ごとに区切って出す
+    while (this.buffer.length > this.maxLineLength) {
+      lines.push({ line: this.buffer.slice(0, this.maxLineLength), truncated: true })
+      this.buffer = this.buffer.slice(this.maxLineLength)
+    }
+
+    return lines
+  }
+
+  private splitLong(line: string): { line: string; truncated: boolean }[] {
+    const result: { line: string; truncated: boolean }[] = []
+    let rest = line
+    while (rest.length > this.maxLineLength) {
+      result.push({ line: rest.slice(0, this.maxLineLength), truncated: true })
+      rest = rest.slice(this.maxLineLength)
+    }
+    result.push({ line: rest, truncated: false })
+    return result
+  }
+}
+
+/**
+ * キー入力から改行で確定した行を組み立てる
+ * Backspace / Ctrl-U / Ctrl-C による行編集を反映し、矢印キーなどのエスケープシーケンスは無視する
+ */
+export class InputLineRecorder {
+  private line: string[] = []
+
+  /** 入力途中の行が無いか */
+  get isEmpty(): boolean {
+    return this.line.length === 0
+  }
+
+  /** 入力を追加し、確定した行（空行は除く）を返す */
+  push(data: string): string[] {
+    const lines: string[] = []
+    const chars = Array.from(
+      data.split(BRACKETED_PASTE_START).join('').split(BRACKETED_PASTE_END).join('')
+    )
+
+    for (let i = 0; i < chars.length; i++) {
+      const char = chars[i]
+      switch (char) {
+        case '\r':
+        case '\n': {
+          const line = this.line.join('').trim()
+          if (line) lines.push(line)
+          this.line = []
+          break
+        }
+        case '\x7f':
+        case '\b':
+          this.line.pop()
+          break
+        case '\x15': // Ctrl-U
+        case '\x03': // Ctrl-C
+          this.line = []
+          break
+        case '\x1b':
+          i = skipEscapeSequence(chars, i)
+          break
+        default:
+          // Tab などの制御文字はシェル側の補完結果が分からないため無視する
+          if (char >= ' ') this.line.push(char)
+      }
+    }
+
+    return lines
+  }
+}
+
+/**
+ * ESC から始まるシーケンスの最後の位置を返す
+ * CSI（ESC [ ... 終端文字）と SS3（ESC O x）を読み飛ばし、それ以外は ESC の次の1文字まで
+ */
+function skipEscapeSequence(chars: string[], start: number): number {
+  const next = chars[start + 1]
+  if (next === '[') {
+    let i = start + 2
+    while (i < chars.length && !(chars[i] >= '@' && chars[i] <= '~')) i++
+    return i
+  }
+  if (next === 'O') return start + 2
+  return start + 1
+}
+
+/**
+ * 音声認識の途中結果を PTY の入力行に仮表示するための書き込みを組み立てる
+ * \r で行頭に戻すとシェルや Claude Code に行が送信されてしまうため、
+ * 前回の表示と異なる部分だけを文字数分の DEL で消して書き直す
+ */
+export class SpeechPreviewLine {
+  private shown: string[] = []
+  /** キー入力で中断された（確定かキャンセルまで結果を無視する） */
+  private interrupted = false
+
+  /** 仮表示中か */
+  get isActive(): boolean {
+    return this.shown.length > 0
+  }
+
+  /** 途中結果を表示するための書き込みを返す */
+  update(text: string): string {
+    if (this.interrupted) return ''
+    return this.replace(text)
+  }
+
+  /** 仮表示を確定テキストに置き換える書き込みを返す（中断された発話なら null） */
+  commit(text: string): string | null {
+    if (this.interrupted) {
+      this.interrupted = false
+      return null
+    }
+    const data = this.replace(text)
+    this.shown = []
+    return data
+  }
+
+  /** 仮表示を消す書き込みを返す */
+  cancel(): string {
+    this.interrupted = false
+    return this.replace('')
+  }
+
+  /** キー入力の前に仮表示を消す書き込みを返す */
+  interrupt(): string {
+    if (!this.isActive) return ''
+    this.interrupted = true
+    return this.replace('')
+  }
+
+  private replace(text: string): string {
+    // 改行は行の送信になるため仮表示では空白にする
+    const next = Array.from(text.replace(/[\r\n]+/g, ' '))
+    let common = 0
+    while (common < this.shown.length && common < next.length && this.shown[common] === next[common]) {
+      common++
+    }
+    const data = '\x7f'.repeat(this.shown.length - common) + next.slice(common).join('')
+    this.shown = next
+    return data
+  }
+}
+
+/**
+ * PTYをリサイズし、フォアグラウンドプロセスに確実にSIGWINCHを届ける
+ * （環境によってはリサイズだけではTUIが再描画されないため）
+ */
+function resizePty(pty: IPty, cols: number, rows: number): void {
+  pty.resize(cols, rows)
+  invoke('signal_pty_resize', { pid: pty.pid }).catch((error) => {
+    console.warn('[claudeTerminal] Failed to signal resize:', error)
+  })
+}
+
+/**
+ * rc を読み込まないためのシェルごとのフラグ
+ */
+function cleanShellArgs(shell: string): string[] {
+  const name = shell.split('/').pop() ?? shell
+  switch (name) {
+    case 'zsh':
+      return ['-f'] // --no-rcs 相当
+    case 'bash':
+      return ['--noprofile', '--norc']
+    default:
+      return []
+  }
+}
+
+/**
+ * PTYでシェルを起動する
+ * cleanEnv の場合は env -i で環境変数を空にしてから、最小限の値だけ渡して起動する
+ */
+async function spawnShell(options: TerminalOptions, cols: number, rows: number): Promise<IPty> {
+  const shell = options.shell ?? DEFAULT_SHELL
+
+  if (!options.cleanEnv) {
+    // ログインシェル経由で起動（PATHを継承するため）
+    return spawn(shell, ['-l'], {
+      cols,
+      rows,
+      cwd: options.cwd,
+      env: { ...PTY_ENV, SHELL: shell },
+    })
+  }
+
+  const home = await homeDir()
+  const cleanEnv: Record<string, string> = {
+    ...PTY_ENV,
+    SHELL: shell,
+    HOME: home,
+    PATH: [`${home}/.local/bin`, ...CLEAN_PATH_DIRS].join(':'),
+  }
+  const envArgs = Object.entries(cleanEnv).map(([key, value]) => `${key}=${value}`)
+
+  return spawn('/usr/bin/env', ['-i', ...envArgs, shell, ...cleanShellArgs(shell)], {
+    cols,
+    rows,
+    cwd: options.cwd,
+  })
+}
+
+/**
+ * 入力を bracketed paste で囲む
+ * 入力中に囲みシーケンスが含まれていると途中で貼り付けが終了してしまうため取り除く
+ */
+export function wrapBracketedPaste(data: string): string {
+  const sanitized = data
+    .split(BRACKETED_PASTE_START).join('')
+    .split(BRACKETED_PASTE_END).join('')
+  return `${BRACKETED_PASTE_START}${sanitized}${BRACKETED_PASTE_END}`
+}
+
+/**
+ * PTYをセッションとして包み、出力から bracketed paste モードの状態を追跡する
+ * lineMode の場合は出力を行に分割して onLine の購読者と `pty:line` イベントに届ける
+ * timestampOutput の場合は出力に受信時刻を付けて onOutput の購読者と `pty:output` イベントに届ける
+ */
+function createTerminalSession(
+  pty: IPty,
+  { lineMode = false, timestampOutput = false }: Pick<TerminalOptions, 'lineMode' | 'timestampOutput'> = {}
+): ClaudeTerminalSession {
+  let bracketedPasteEnabled = false
+  const lineListeners = new Set<(line: string, truncated: boolean) => void>()
+  const outputListeners = new Set<(output: PtyOutputPayload) => void>()
+
+  // ウィンドウを閉じてもシェルを生かしておき、アプリ終了時に片付けてもらう
+  invoke('register_pty', { pid: pty.pid }).catch((error) => {
+    console.warn('[claudeTerminal] Failed to register PTY:', error)
+  })
+
+  if (timestampOutput) {
+    pty.onData((data) => {
+      const payload: PtyOutputPayload = { pid: pty.pid, data, timestamp: Date.now() }
+      outputListeners.forEach((listener) => listener(payload))
+      emitAppEvent(AppEvents.PtyOutput, payload).catch((error) => {
+        console.warn('[claudeTerminal] Failed to emit output:', error)
+      })
+    })
+  }
+
+  if (lineMode) {
+    const splitter = new LineSplitter()
+    pty.onData((data) => {
+      for (const { line, truncated } of splitter.push(data)) {
+        lineListeners.forEach((listener) => listener(line, truncated))
+        const payload: PtyLinePayload = { pid: pty.pid, line, truncated }
+        emitAppEvent(AppEvents.PtyLine, payload).catch((error) => {
+          console.warn('[claudeTerminal] Failed to emit line:', error)
+        })
+      }
+    })
+  }
+  // チャンクの境界でシーケンスが分断される場合に備えて末尾を保持
+  let tail = ''
+
+  // 入力履歴。行はその行を打ち始めたときに echo が有効だったと分かってから記録する
+  // （echo off やパスワードのプロンプト中に始まった行は記録しない）
+  const inputHistory: string[] = []
+  const inputRecorder = new InputLineRecorder()
+  let lineRecordable: Promise<boolean> = Promise.resolve(true)
+  // 記録を入力順に行うためのキュー
+  let historyQueue: Promise<void> = Promise.resolve()
+  let lastOutputLine = ''
+
+  pty.onData((data) => {
+    const text = (lastOutputLine + data).split(/\r?\n/).pop() ?? ''
+    lastOutputLine = text.slice(-MAX_PROMPT_LENGTH)
+  })
+
+  // echo の判定結果はプロンプトごとに使い回す（行ごとに ps / stty を起動しない）
+  // 空のプロンプトは別の場面と区別できないので使い回さない
+  let echoCheck: { prompt: string; echo: Promise<boolean> } | null = null
+  const isEchoEnabled = (prompt: string): Promise<boolean> => {
+    if (prompt && echoCheck?.prompt === prompt) return echoCheck.echo
+    const echo = invoke<boolean>('is_pty_echo_enabled', { pid: pty.pid }).catch((error) => {
+      // 判定できなかった行は記録しない。次の行では判定し直す
+      console.warn('[claudeTerminal] Failed to check terminal echo:', error)
+      if (echoCheck?.echo === echo) echoCheck = null
+      return false
+    })
+    echoCheck = prompt ? { prompt, echo } : null
+    return echo
+  }
+
+  const recordInput = (data: string) => {
+    if (inputRecorder.isEmpty) {
+      const prompt = lastOutputLine.replace(CSI_PATTERN, '')
+      lineRecordable = PASSWORD_PROMPT_PATTERN.test(prompt)
+        ? Promise.resolve(false)
+        : isEchoEnabled(prompt)
+    }
+
+    for (const line of inputRecorder.push(data)) {
+      // 判定が返るまで行を保留する
+      const recordable = lineRecordable
+      historyQueue = historyQueue.then(async () => {
+        if (!(await recordable) || inputHistory[inputHistory.length - 1] === line) return
+        inputHistory.push(line)
+        if (inputHistory.length > MAX_INPUT_HISTORY) inputHistory.shift()
+      })
+    }
+  }
+
+  pty.onData((data) => {
+    const text = tail + data
+    const enableIndex = text.lastIndexOf(BRACKETED_PASTE_ENABLE)
+    const disableIndex = text.lastIndexOf(BRACKETED_PASTE_DISABLE)
+    if (enableIndex !== disableIndex) {
+      bracketedPasteEnabled = enableIndex > disableIndex
+    }
+    tail = text.slice(-(BRACKETED_PASTE_ENABLE.length - 1))
+  })
+
+  // 音声認識の仮表示。入力の記録には確定した結果だけを残す
+  const speechPreview = new SpeechPreviewLine()
+  const writePreview = (data: string) => {
+    if (data) pty.write(data)
+  }
+
+  const safeWrite = (data: string, options?: SafeWriteOptions) => {
+    writePreview(speechPreview.interrupt())
+    recordInput(data)
+    const hasNewline = /[\r\n]/.test(data)
+    if (hasNewline && (bracketedPasteEnabled || options?.forceBracketedPaste)) {
+      pty.write(wrapBracketedPaste(data))
+    } else {
+      pty.write(data)
+    }
+  }
+
+  return {
+    pty,
+    write: (data: string) => {
+      writePreview(speechPreview.interrupt())
+      recordInput(data)
+      pty.write(data)
+    },
+    safeWrite,
+    isBracketedPasteEnabled: () => bracketedPasteEnabled,
+    resize: (cols: number, rows: number) => resizePty(pty, cols, rows),
+    kill: () => {
+      // 明示的な終了だけがプロセスを止める（登録を外してから終了する）
+      invoke('kill_pty', { pid: pty.pid }).catch((error) => {
+        console.warn('[claudeTerminal] Failed to kill PTY:', error)
+      })
+      pty.kill()
+    },
+    onData: (callback: (data: string) => void) => pty.onData(callback),
+    onLine: (callback: (line: string, truncated: boolean) => void) => {
+      lineListeners.add(callback)
+      return { dispose: () => lineListeners.delete(callback) }
+    },
+    onOutput: (callback: (output: PtyOutputPayload) => void) => {
+      outputListeners.add(callback)
+      return { dispose: () => outputListeners.delete(callback) }
+    },
+    getInputHistory: () => [...inputHistory],
+    previewSpeech: (text: string) => writePreview(speechPreview.update(text)),
+    commitSpeech: (text: string) => {
+      if (/[\r\n]/.test(text)) {
+        // 複数行は仮表示を消してから貼り付けとして書き込む
+        const erase = speechPreview.commit('')
+        if (erase === null) return
+        writePreview(erase)
+        safeWrite(text)
+        return
+      }
+      const data = speechPreview.commit(text)
+      if (data === null) return
+      recordInput(text)
+      writePreview(data)
+    },
+    cancelSpeech: () => writePreview(speechPreview.cancel()),
+  }
 }
 
 /**
@@ -32,26 +560,14 @@ export async function spawnClaudeTerminal(
   const cols = options.cols ?? 80
   const rows = options.rows ?? 24
 
-  // ログインシェル経由でclaudeを起動（PATHを継承するため）
-  const pty = spawn('/bin/zsh', ['-l'], {
-    cols,
-    rows,
-    cwd: options.cwd,
-    env: PTY_ENV,
-  })
+  const pty = await spawnShell(options, cols, rows)
 
   // cdで指定ディレクトリに移動してからclaudeコマンドを送信
   // ログインシェルはcwdオプションを無視することがあるため
   const escapedCwd = options.cwd.replace(/'/g, "'\\''")
   pty.write(`cd '${escapedCwd}' && claude\n`)
 
-  return {
-    pty,
-    write: (data: string) => pty.write(data),
-    resize: (cols: number, rows: number) => pty.resize(cols, rows),
-    kill: () => pty.kill(),
-    onData: (callback: (data: string) => void) => pty.onData(callback),
-  }
+  return createTerminalSession(pty, options)
 }
 
 /**
@@ -64,22 +580,11 @@ export async function resumeClaudeTerminal(
   const cols = options.cols ?? 80
   const rows = options.rows ?? 24
 
-  const pty = spawn('/bin/zsh', ['-l'], {
-    cols,
-    rows,
-    cwd: options.cwd,
-    env: PTY_ENV,
-  })
+  const pty = await spawnShell(options, cols, rows)
 
   const escapedCwd = options.cwd.replace(/'/g, "'\\''")
   const escapedSessionId = sessionId.replace(/'/g, "'\\''")
   pty.write(`cd '${escapedCwd}' && claude --resume '${escapedSessionId}'\n`)
 
-  return {
-    pty,
-    write: (data: string) => pty.write(data),
-    resize: (cols: number, rows: number) => pty.resize(cols, rows),
-    kill: () => pty.kill(),
-    onData: (callback: (data: string) => void) => pty.onData(callback),
-  }
+  return createTerminalSession(pty, options)
 }
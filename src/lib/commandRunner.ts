@@ -0,0 +1,37 @@
+import { invoke } from '@tauri-apps/api/core'
+
+export interface CommandOutput {
+  stdout: string
+  stderr: string
+  /** 終了コード（シグナルで終了した場合やタイムアウト時は null） */
+  exit_code: number | null
+  /** タイムアウトで強制終了したか */
+  timed_out: boolean
+  /** 出力がサイズ上限を超えて切り詰められたか */
+  truncated: boolean
+}
+
+export interface RunCommandOptions {
+  env?: Record<string, string>
+  /** タイムアウト（ms、省略時は30秒） */
+  timeoutMs?: number
+  /** stdout/stderr それぞれの上限バイト数（省略時は1MB） */
+  maxOutputBytes?: number
+}
+
+// PTYを使わずにコマンドを1回だけ実行して出力を取得する
+export async function runCommand(
+  cwd: string,
+  cmd: string,
+  args: string[] = [],
+  options: RunCommandOptions = {}
+): Promise<CommandOutput> {
+  return invoke<CommandOutput>('run_command', {
+    cwd,
+    cmd,
+    args,
+    env: options.env,
+    timeoutMs: options.timeoutMs,
+    maxOutputBytes: options.maxOutputBytes,
+  })
+}
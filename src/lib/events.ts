@@ -0,0 +1,65 @@
+import { emit } from '@tauri-apps/api/event'
+
+/**
+ * アプリ全体のイベント名（`domain:event` 形式）
+ * バックエンドの src-tauri/src/events.rs と対応する。ペイロードの型は各ドメインのモジュールにある
+ */
+export const AppEvents = {
+  WindowVisibilityChanged: 'window:visibility-changed',
+  SidebarVisibilityChanged: 'sidebar:visibility-changed',
+  SidebarEdgeChanged: 'sidebar:edge-changed',
+  WindowReopened: 'window:reopened',
+
+  SpeechStateChanged: 'speech:state-changed',
+  SpeechResult: 'speech:result',
+  SpeechSentence: 'speech:sentence',
+  SpeechSegmentBoundary: 'speech:segment-boundary',
+  SpeechAudioLevel: 'speech:audio-level',
+  SpeechHandsFreeStateChanged: 'speech:hands-free-state-changed',
+  SpeechDisabled: 'speech:disabled',
+
+  ClaudeSessionUpdated: 'claude:session-updated',
+  ClaudeSessionFinished: 'claude:session-finished',
+  ClaudeMessage: 'claude:message',
+  ClaudeReadComplete: 'claude:read-complete',
+
+  SystemAppearanceChanged: 'system:appearance-changed',
+
+  PtyLine: 'pty:line',
+  PtyOutput: 'pty:output',
+  PtyInput: 'pty:input',
+  PtyActiveChanged: 'pty:active-changed',
+} as const
+
+export type AppEventName = (typeof AppEvents)[keyof typeof AppEvents]
+
+/**
+ * `domain:event` 形式にする前のイベント名（新 → 旧）
+ * 移行期間中は旧名でも送信しているので、旧名を購読している箇所もそのまま動く。
+ * 旧名の購読が無くなったものから削除する
+ */
+export const LEGACY_EVENT_NAMES: Partial<Record<AppEventName, string>> = {
+  [AppEvents.WindowVisibilityChanged]: 'window-visibility-changed',
+  [AppEvents.SpeechStateChanged]: 'speech-state-changed',
+  [AppEvents.SpeechResult]: 'speech-recognition-result',
+  [AppEvents.SpeechSentence]: 'speech-sentence',
+  [AppEvents.SpeechAudioLevel]: 'speech-audio-level',
+  [AppEvents.SpeechHandsFreeStateChanged]: 'speech-hands-free-state-changed',
+  [AppEvents.SpeechDisabled]: 'speech-disabled',
+  [AppEvents.ClaudeSessionUpdated]: 'claude-session-updated',
+  // アプリの外から旧名で送られてくるので、onClaudeSessionFinished は両方の名前を購読する
+  [AppEvents.ClaudeSessionFinished]: 'claude-session-finished',
+  [AppEvents.ClaudeMessage]: 'claude-message',
+  [AppEvents.ClaudeReadComplete]: 'claude-read-complete',
+  [AppEvents.SystemAppearanceChanged]: 'system-appearance-changed',
+  [AppEvents.PtyLine]: 'pty-line',
+}
+
+/**
+ * フロントエンドからイベントを送る（旧名があれば同じペイロードで旧名でも送る）
+ */
+export async function emitAppEvent<T>(event: AppEventName, payload: T): Promise<void> {
+  await emit(event, payload)
+  const legacy = LEGACY_EVENT_NAMES[event]
+  if (legacy) await emit(legacy, payload)
+}
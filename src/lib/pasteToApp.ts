@@ -3,6 +3,10 @@ import { invoke } from '@tauri-apps/api/core'
 export interface PasteResult {
   success: boolean
   error?: string
+  /** 上限を超えたためテキストが切り詰められたか */
+  truncated: boolean
+  /** 切り詰め前のテキスト長（UTF-8 のバイト数。string.length の UTF-16 単位とは異なる） */
+  original_length: number
 }
 
 export interface AppInfo {
@@ -31,12 +35,29 @@ export async function getRunningApps(): Promise<AppInfo[]> {
  * @param text 送信するテキスト
  * @param targetApp 送信先アプリ名
  * @param bundleId 送信先アプリのBundle ID（オプション、あればより確実にアクティブ化できる）
+ * @param maxBytes 送信するテキストの上限バイト数（省略時は100KB）
+ * @param activationTimeoutMs 送信先アプリが前面になるまで待つ最大時間（省略時は2秒、超えると失敗）
  * @returns 送信結果
  */
-export async function pasteTextToApp(text: string, targetApp: string, bundleId?: string): Promise<PasteResult> {
+export async function pasteTextToApp(
+  text: string,
+  targetApp: string,
+  bundleId?: string,
+  maxBytes?: number,
+  activationTimeoutMs?: number
+): Promise<PasteResult> {
   console.log('[pasteToApp] Invoking paste_text_to_app with text:', text.substring(0, 50), 'target:', targetApp, 'bundleId:', bundleId)
   try {
-    const result = await invoke<PasteResult>('paste_text_to_app', { text, targetApp, bundleId })
+    const result = await invoke<PasteResult>('paste_text_to_app', {
+      text,
+      targetApp,
+      bundleId,
+      maxBytes,
+      activationTimeoutMs,
+    })
+    if (result.truncated) {
+      console.warn('[pasteToApp] Text was truncated from', result.original_length, 'bytes')
+    }
     console.log('[pasteToApp] Result:', result)
     return result
   } catch (error) {
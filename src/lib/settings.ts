@@ -1,7 +1,7 @@
 import Database from '@tauri-apps/plugin-sql'
 import { ThemeVariant } from './themes'
 
-export type ScreenEdge = 'left' | 'right'
+export type ScreenEdge = 'left' | 'right' | 'top' | 'bottom'
 
 export interface Settings {
   alwaysOnTop: boolean
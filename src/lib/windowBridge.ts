@@ -1,5 +1,7 @@
 import { emit, listen, type UnlistenFn } from '@tauri-apps/api/event'
 import { invoke } from '@tauri-apps/api/core'
+import { AppEvents } from './events'
+import type { ScreenEdge } from './settings'
 
 /**
  * ウィンドウ間で同期するセッションデータのペイロード
@@ -10,6 +12,119 @@ export interface SessionSyncPayload {
   data: unknown
 }
 
+/**
+ * window:visibility-changed イベントのペイロード
+ * autohide で隠れた（または通常の show/hide で非表示になった）ときに visible: false が届く
+ */
+export interface WindowVisibilityPayload {
+  visible: boolean
+}
+
+/**
+ * ウィンドウの表示/非表示を監視する（非表示の間は描画やポーリングを止める用）
+ */
+export function onWindowVisibilityChanged(
+  callback: (visible: boolean) => void
+): Promise<UnlistenFn> {
+  return listen<WindowVisibilityPayload>(AppEvents.WindowVisibilityChanged, (event) => {
+    callback(event.payload.visible)
+  })
+}
+
+/**
+ * 閉じて隠していたメインウィンドウが Dock から再度開かれたときに呼ばれる
+ * （PTY セッションは閉じている間も動き続けているので、画面を書き直す用）
+ */
+export function onWindowReopened(callback: () => void): Promise<UnlistenFn> {
+  return listen(AppEvents.WindowReopened, () => {
+    callback()
+  })
+}
+
+/** サイドバーが出入りしたきっかけ（手動切替・フォーカス喪失・画面端・固定/固定解除） */
+export type SidebarVisibilityReason = 'toggle' | 'blur' | 'edge' | 'pin'
+
+/**
+ * sidebar:visibility-changed イベントのペイロード
+ * autohide のサイドバーが実際に表示/非表示に切り替わったときだけ届く
+ */
+export interface SidebarVisibilityPayload {
+  visible: boolean
+  reason: SidebarVisibilityReason
+}
+
+/**
+ * autohide のサイドバーの表示/非表示と、そのきっかけを監視する（ツールバーのアイコン同期用）
+ */
+export function onSidebarVisibilityChanged(
+  callback: (payload: SidebarVisibilityPayload) => void
+): Promise<UnlistenFn> {
+  return listen<SidebarVisibilityPayload>(AppEvents.SidebarVisibilityChanged, (event) => {
+    callback(event.payload)
+  })
+}
+
+/** sidebar:edge-changed イベントのペイロード */
+export interface SidebarEdgePayload {
+  edge: ScreenEdge
+}
+
+/**
+ * autohide のサイドバーを寄せる画面端の変更を監視する（手動の変更と自動選択の両方で届く）
+ */
+export function onSidebarEdgeChanged(
+  callback: (payload: SidebarEdgePayload) => void
+): Promise<UnlistenFn> {
+  return listen<SidebarEdgePayload>(AppEvents.SidebarEdgeChanged, (event) => {
+    callback(event.payload)
+  })
+}
+
+/**
+ * autohide のサイドバーを表示する（すでに表示中なら何もしない）
+ * 表示されていれば true を返す
+ */
+export async function showSidebar(): Promise<boolean> {
+  return invoke<boolean>('show_sidebar')
+}
+
+/**
+ * autohide のサイドバーを画面端に隠す（すでに隠れていれば何もしない）
+ * まだ表示されていれば true（autohide が無効、または固定表示中）
+ */
+export async function hideSidebar(): Promise<boolean> {
+  return invoke<boolean>('hide_sidebar')
+}
+
+/** macOS の外観（ダーク/ライト） */
+export type SystemAppearance = 'dark' | 'light'
+
+/**
+ * system:appearance-changed イベントのペイロード
+ * ユーザーがダーク/ライトを切り替えたときだけ届く
+ */
+export interface SystemAppearancePayload {
+  appearance: SystemAppearance
+}
+
+/**
+ * macOS の現在の外観を取得する
+ */
+export async function getSystemAppearance(): Promise<SystemAppearance> {
+  return invoke<SystemAppearance>('get_system_appearance')
+}
+
+/**
+ * macOS の外観の切り替えを監視する（テーマやターミナルの配色を合わせる用）
+ */
+export function onSystemAppearanceChanged(
+  callback: (appearance: SystemAppearance) => void
+): Promise<UnlistenFn> {
+  return listen<SystemAppearancePayload>(AppEvents.SystemAppearanceChanged, (event) => {
+    callback(event.payload.appearance)
+  })
+}
+
 /**
  * ウィンドウの状態
  */
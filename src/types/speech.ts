@@ -5,10 +5,178 @@ export type SpeechRecognitionState =
   | 'Processing'
   | 'Error'
 
+/** ハンズフリーモードの状態 */
+export type HandsFreeState = 'Off' | 'Waiting' | 'Listening'
+
 /** 認識結果 */
 export interface RecognitionResult {
   /** 認識されたテキスト */
   text: string
   /** 確定かどうか */
   is_final: boolean
+  /** 準確定（安定して変化しなくなった）先頭部分 */
+  committed_prefix: string
+  /** 準確定部分を除いた未確定の残り */
+  pending: string
+  /** Markdown 装飾を付ける前のテキスト（装飾した場合のみ。誤爆時に元へ戻す用） */
+  plain_text?: string
+  /** 話者セグメントの番号（長いポーズのたびに増える。認識の開始ごとに 0 から） */
+  segment_index: number
+}
+
+/** 認識中に抑止するスリープの種類（display はディスプレイのスリープも抑止） */
+export type SleepPrevention = 'off' | 'system' | 'display'
+
+/** 認識結果イベントの形式 */
+export type SpeechResultFormat = 'simple' | 'web_speech'
+
+/** 認識候補 */
+export interface RecognitionAlternative {
+  transcript: string
+  /** 信頼度（0.0〜1.0、部分結果では 0） */
+  confidence: number
+}
+
+/**
+ * Web Speech API の SpeechRecognitionResult 互換の認識結果（web_speech 形式）
+ * - is_final → isFinal
+ * - text → alternatives[0].transcript
+ * - committed_prefix / pending は含まれない
+ */
+export interface WebSpeechResult {
+  isFinal: boolean
+  alternatives: RecognitionAlternative[]
+}
+
+/** speech:state-changed イベントのペイロード */
+export interface SpeechStatePayload {
+  state: SpeechRecognitionState
+}
+
+/** speech:hands-free-state-changed イベントのペイロード */
+export interface HandsFreeStatePayload {
+  state: HandsFreeState
+}
+
+/** speech:audio-level イベントのペイロード（認識中、約 100ms ごと） */
+export interface SpeechAudioLevelPayload {
+  /** 入力音量（dBFS） */
+  level_db: number
+}
+
+/** speech:disabled イベントのペイロード
+export interface SpeechDisabledPayload {
+  /** 連続エラー回数 */
+  error_count: number
+  /** 最後に発生したエラー */
+  last_error: string
+}
+
+/** speech:sentence イベントのペイロード（確定テキストを文ごとに分割したもの） */
+export interface SpeechSentencePayload {
+  /** 文のテキスト（区切り文字を含む） */
+  text: string
+  /** 確定テキスト内での文の位置（0始まり） */
+  index: number
+  /** 確定テキストの最後の文かどうか */
+  is_last: boolean
+}
+
+/**
+ * speech:segment-boundary イベントのペイロード
+ * 長いポーズの後に認識結果が届いたとき（別の話者の発話かもしれない境界）に送られる
+ */
+export interface SpeechSegmentBoundaryPayload {
+  /** 新しく始まったセグメントの番号 */
+  segment_index: number
+  /** 境界になったポーズの長さ（ms） */
+  pause_ms: number
+}
+
+/** 付与する Markdown 装飾 */
+export type DecorationKind = 'code_block' | 'inline_code' | 'quote' | 'bullet'
+
+/** トリガー語と装飾の対応 */
+export interface DecorationRule {
+  /** 確定テキストの先頭でこの語が話されたら装飾する */
+  trigger: string
+  kind: DecorationKind
+  /** 対象の認識言語（前方一致。"ja" で "ja-JP" も含む、null なら全言語） */
+  language?: string | null
+}
+
+/** 1回の認識セッション（開始から停止まで）の履歴 */
+export interface SpeechSession {
+  /** 開始時刻（RFC 3339） */
+  started_at: string
+  /** 終了時刻（RFC 3339） */
+  ended_at: string | null
+  /** 認識言語（ja-JP等） */
+  language: string
+  /** 確定テキスト（複数回確定した場合は改行区切り） */
+  text: string
+  /** セッション中にエラーが発生したかどうか */
+  had_error: boolean
+  /** 最後に発生したエラー */
+  last_error: string | null
+}
+
+/** 履歴の絞り込み条件 */
+export interface SpeechHistoryFilter {
+  /** 言語（前方一致。"ja" で "ja-JP" も含む） */
+  language?: string
+  /** この日以降に開始したセッション（YYYY-MM-DD） */
+  from?: string
+  /** この日以前に開始したセッション（YYYY-MM-DD） */
+  to?: string
+}
+
+/** 名前付きの認識設定（speech_profiles.json で定義） */
+export interface SpeechProfile {
+  name: string
+  /** 認識言語（ja-JP等） */
+  language: string
+  /** 句読点を自動で付与する */
+  adds_punctuation: boolean
+  /** 認識されやすくする語彙 */
+  vocabulary: string[]
+}
+
+/** 音声認識の設定（get_app_status で取得） */
+export interface SpeechConfig {
+  enabled: boolean
+  /** 認識言語（ja-JP等） */
+  language: string
+  /** アプリの表示言語に認識言語を追従させる */
+  follow_app_locale: boolean
+  /** 最後に通知されたアプリロケール */
+  app_locale: string | null
+  adds_punctuation: boolean
+  vocabulary: string[]
+  /** 適用中のプロファイル名 */
+  profile: string | null
+  commit_stable_ms: number
+  /** この文字数未満の確定結果は捨てる（0 で無効） */
+  min_utterance_chars: number
+  /** 発話がこの時間（ms）未満の確定結果は捨てる（0 で無効） */
+  min_utterance_ms: number
+  /** 認識結果の間がこの時間（ms）以上空いたら別のセグメントにする（0 で区切らない） */
+  segment_pause_ms: number
+  /** 部分結果を送る最小間隔（ms）。間隔内は最新の部分結果だけを送る（0 で間引かない） */
+  partial_debounce_ms: number
+  record_audio_path: string | null
+  data_dir: string | null
+  max_consecutive_errors: number
+  normalize: boolean
+  result_format: SpeechResultFormat
+  split_sentences: boolean
+  sentence_delimiters: string | null
+  decorate: boolean
+  decoration_rules: DecorationRule[]
+  prevent_sleep: SleepPrevention
+  hands_free: boolean
+  hands_free_start_db: number
+  hands_free_start_ms: number
+  hands_free_stop_db: number
+  hands_free_stop_ms: number
 }